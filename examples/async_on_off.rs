@@ -0,0 +1,82 @@
+//! The getting started example from the crate documentation, using the async variant of [OnOff](homelander::traits::on_off::OnOff).
+//!
+//! Run with `cargo run --example async_on_off --features async`
+
+use homelander::fulfillment::request::Input;
+use homelander::traits::on_off::AsyncOnOff;
+use homelander::traits::{CombinedDeviceError, DeviceInfo, DeviceName, GoogleHomeDevice};
+use homelander::{async_trait, AsyncDevice, DeviceType, Homelander, Request};
+
+#[derive(Debug)]
+struct MyDevice(bool);
+
+// Implement the basic GoogleHomeDevice trait,
+// This gives the basic information required for every device
+impl GoogleHomeDevice for MyDevice {
+    fn get_device_info(&self) -> DeviceInfo {
+        DeviceInfo {
+            model: "mydevice".to_string(),
+            manufacturer: "mydevice company".to_string(),
+            hw: "0.1.0".to_string(),
+            sw: "0.1.0".to_string(),
+        }
+    }
+
+    fn will_report_state(&self) -> bool {
+        // Will this Device be reporting state to Google?
+        // Note that as of August 6 2022, this isn't implemented in Homelander yet,
+        // Until it is, this should *always* be false.
+        false
+    }
+
+    fn get_device_name(&self) -> DeviceName {
+        DeviceName {
+            name: "MyDevice".to_string(),
+            default_names: Vec::new(),
+            nicknames: Vec::new(),
+        }
+    }
+
+    fn is_online(&self) -> bool {
+        true
+    }
+
+    fn disconnect(&mut self) {
+        // Handle your disconnect here
+    }
+}
+
+// Implement a device specific trait. E.g. AsyncOnOff
+// This is where you'd await e.g. the cloud API of your device
+#[async_trait]
+impl AsyncOnOff for MyDevice {
+    async fn is_on(&self) -> Result<bool, CombinedDeviceError> {
+        Ok(self.0)
+    }
+
+    async fn set_on(&mut self, on: bool) -> Result<(), CombinedDeviceError> {
+        self.0 = on;
+        Ok(())
+    }
+}
+
+#[tokio::main(flavor = "current_thread")]
+async fn main() {
+    // Create the device
+    let mut device = AsyncDevice::new(MyDevice(false), DeviceType::Outlet, "my_id".to_string());
+    // Register the OnOff trait
+    device.set_on_off();
+
+    // Create the Homelander struct
+    let mut homelander = Homelander::new("my_user_id".to_string());
+    homelander.add_async_device(device);
+
+    // Let homelander handle the request and create a response
+    // The response can then be returned to Google as JSON
+    let request = Request {
+        request_id: String::default(),
+        inputs: vec![Input::Sync],
+    };
+    let response = homelander.handle_request_async(request).await;
+    println!("{response:?}");
+}
@@ -0,0 +1,164 @@
+//! # Homelander derive
+//! Derive macros for [Homelander](https://docs.rs/homelander).
+//! Enable the `derive` feature of Homelander instead of depending on this crate directly.
+
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use quote::quote;
+use syn::meta::ParseNestedMeta;
+use syn::{parse_macro_input, DeriveInput, ExprArray, Ident, LitBool, LitStr};
+
+/// Implement `GoogleHomeDevice` from static metadata.
+///
+/// The metadata is provided with the `google_home` attribute:
+/// - `manufacturer`, `model`, `name`: Required
+/// - `hw`, `sw`: The hardware and software version. Default: the version of your crate
+/// - `nicknames`, `default_names`: Lists of alternative names. Default: empty
+/// - `room_hint`: Default: none
+/// - `will_report_state`: Default: `false`
+/// - `is_online`: The name of a `fn(&self) -> bool` on the struct which reports if the device is online. Default: always online
+/// - `disconnect`: The name of a `fn(&mut self)` on the struct which handles a DISCONNECT. Default: nothing happens
+///
+/// ```ignore
+/// #[derive(Debug, GoogleHomeDevice)]
+/// #[google_home(manufacturer = "mydevice company", model = "mydevice", name = "MyDevice", is_online = "is_connected")]
+/// struct MyDevice {
+///     connected: bool,
+/// }
+///
+/// impl MyDevice {
+///     fn is_connected(&self) -> bool {
+///         self.connected
+///     }
+/// }
+/// ```
+#[proc_macro_derive(GoogleHomeDevice, attributes(google_home))]
+pub fn derive_google_home_device(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match expand(input) {
+        Ok(tokens) => tokens.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
+#[derive(Default)]
+struct Attributes {
+    manufacturer: Option<LitStr>,
+    model: Option<LitStr>,
+    name: Option<LitStr>,
+    hw: Option<LitStr>,
+    sw: Option<LitStr>,
+    nicknames: Option<ExprArray>,
+    default_names: Option<ExprArray>,
+    room_hint: Option<LitStr>,
+    will_report_state: Option<LitBool>,
+    is_online: Option<Ident>,
+    disconnect: Option<Ident>,
+}
+
+impl Attributes {
+    fn parse(input: &DeriveInput) -> syn::Result<Self> {
+        let mut attributes = Self::default();
+        for attr in input.attrs.iter().filter(|attr| attr.path().is_ident("google_home")) {
+            attr.parse_nested_meta(|meta| attributes.parse_meta(meta))?;
+        }
+
+        Ok(attributes)
+    }
+
+    fn parse_meta(&mut self, meta: ParseNestedMeta) -> syn::Result<()> {
+        let key = meta.path.get_ident().map(Ident::to_string).unwrap_or_default();
+        match key.as_str() {
+            "manufacturer" => self.manufacturer = Some(meta.value()?.parse()?),
+            "model" => self.model = Some(meta.value()?.parse()?),
+            "name" => self.name = Some(meta.value()?.parse()?),
+            "hw" => self.hw = Some(meta.value()?.parse()?),
+            "sw" => self.sw = Some(meta.value()?.parse()?),
+            "nicknames" => self.nicknames = Some(meta.value()?.parse()?),
+            "default_names" => self.default_names = Some(meta.value()?.parse()?),
+            "room_hint" => self.room_hint = Some(meta.value()?.parse()?),
+            "will_report_state" => self.will_report_state = Some(meta.value()?.parse()?),
+            "is_online" => self.is_online = Some(meta.value()?.parse::<LitStr>()?.parse()?),
+            "disconnect" => self.disconnect = Some(meta.value()?.parse::<LitStr>()?.parse()?),
+            _ => return Err(meta.error("unknown google_home attribute")),
+        }
+
+        Ok(())
+    }
+}
+
+fn required(value: Option<LitStr>, key: &str) -> syn::Result<LitStr> {
+    value.ok_or_else(|| syn::Error::new(Span::call_site(), format!("missing `#[google_home({key} = \"...\")]`")))
+}
+
+fn expand(input: DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let attributes = Attributes::parse(&input)?;
+    let manufacturer = required(attributes.manufacturer, "manufacturer")?;
+    let model = required(attributes.model, "model")?;
+    let name = required(attributes.name, "name")?;
+
+    let hw = match attributes.hw {
+        Some(hw) => quote!(#hw),
+        None => quote!(env!("CARGO_PKG_VERSION")),
+    };
+    let sw = match attributes.sw {
+        Some(sw) => quote!(#sw),
+        None => quote!(env!("CARGO_PKG_VERSION")),
+    };
+
+    let nicknames = attributes.nicknames.map(|a| a.elems.into_iter().collect::<Vec<_>>()).unwrap_or_default();
+    let default_names = attributes.default_names.map(|a| a.elems.into_iter().collect::<Vec<_>>()).unwrap_or_default();
+
+    let room_hint = attributes.room_hint.map(|room_hint| {
+        quote! {
+            fn get_room_hint(&self) -> Option<String> {
+                Some(#room_hint.to_string())
+            }
+        }
+    });
+
+    let will_report_state = attributes.will_report_state.map(|b| b.value).unwrap_or(false);
+    let is_online = match attributes.is_online {
+        Some(method) => quote!(self.#method()),
+        None => quote!(true),
+    };
+    let disconnect = attributes.disconnect.map(|method| quote!(self.#method();));
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::homelander::traits::GoogleHomeDevice for #ident #ty_generics #where_clause {
+            fn get_device_info(&self) -> ::homelander::traits::DeviceInfo {
+                ::homelander::traits::DeviceInfo {
+                    manufacturer: #manufacturer.to_string(),
+                    model: #model.to_string(),
+                    hw: #hw.to_string(),
+                    sw: #sw.to_string(),
+                }
+            }
+
+            #room_hint
+
+            fn will_report_state(&self) -> bool {
+                #will_report_state
+            }
+
+            fn get_device_name(&self) -> ::homelander::traits::DeviceName {
+                ::homelander::traits::DeviceName {
+                    name: #name.to_string(),
+                    default_names: vec![#(#default_names.to_string()),*],
+                    nicknames: vec![#(#nicknames.to_string()),*],
+                }
+            }
+
+            fn is_online(&self) -> bool {
+                #is_online
+            }
+
+            fn disconnect(&mut self) {
+                #disconnect
+            }
+        }
+    })
+}
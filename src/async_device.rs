@@ -0,0 +1,210 @@
+use crate::device::{command_output, is_command_only, offline_query_device_state, query_device_state, reject_query_only, sync_device, unsupported};
+use crate::device_trait::Trait;
+use crate::device_type::DeviceType;
+use crate::execute_error::ExecuteError;
+use crate::fulfillment::response::execute::{OnOffCommandState, TraitCommandState};
+use crate::traits::on_off::AsyncOnOff;
+use crate::traits::{Challenge, Language};
+use crate::{fulfillment, CommandOutput, CommandType, ErrorCode, GoogleHomeDevice};
+use std::error::Error;
+use std::fmt;
+use std::fmt::Debug;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use tracing::{instrument, trace};
+
+/// A Google Home device with async traits.
+///
+/// Works like [Device](crate::Device), but the registered traits are awaited.
+/// Async devices are only handled by [Homelander::handle_request_async](crate::Homelander::handle_request_async).
+///
+/// Only [AsyncOnOff] is available as an async trait so far, all other commands are rejected with `notSupported`.
+#[derive(Debug)]
+pub struct AsyncDevice<T: GoogleHomeDevice + Debug + Send + ?Sized + Sync + 'static> {
+    pub(crate) id: String,
+    device_type: DeviceType,
+    device_traits: AsyncDeviceTraits,
+    traits: Vec<Trait>,
+    inner: Arc<Mutex<T>>,
+}
+
+impl<T: GoogleHomeDevice + Send + Debug + Sync + 'static> AsyncDevice<T> {
+    pub(crate) fn unsize(self) -> AsyncDevice<dyn crate::DeviceTraits> {
+        let Self {
+            id,
+            device_type,
+            device_traits,
+            traits,
+            inner,
+        } = self;
+        AsyncDevice {
+            id,
+            device_type,
+            device_traits,
+            traits,
+            inner,
+        }
+    }
+
+    /// Create a new async device
+    pub fn new(device: T, device_type: DeviceType, id: String) -> Self {
+        Self {
+            id,
+            device_type,
+            device_traits: AsyncDeviceTraits::default(),
+            traits: Vec::new(),
+            inner: Arc::new(Mutex::new(device)),
+        }
+    }
+}
+
+impl<T: GoogleHomeDevice + Send + Sync + Debug + ?Sized + 'static> AsyncDevice<T> {
+    /// The traits registered on the device, in the order they were registered
+    pub fn registered_traits(&self) -> &[Trait] {
+        &self.traits
+    }
+
+    /// Add a trait to the registered traits, unless it is registered already
+    fn push_trait(&mut self, device_trait: Trait) {
+        if !self.traits.contains(&device_trait) {
+            self.traits.push(device_trait);
+        }
+    }
+
+    pub(crate) async fn disconnect(&mut self) {
+        self.inner.lock().await.disconnect();
+    }
+
+    pub(crate) async fn set_custom_data(&mut self, custom_data: Option<serde_json::Value>) {
+        self.inner.lock().await.set_custom_data(custom_data);
+    }
+
+    pub(crate) async fn set_request_id(&mut self, request_id: Option<&str>) {
+        self.inner.lock().await.set_request_id(request_id);
+    }
+
+    pub(crate) async fn set_challenge(&mut self, challenge: Option<Challenge>) {
+        self.inner.lock().await.set_challenge(challenge);
+    }
+
+    pub(crate) async fn get_localized_error(&self, error_code: &str, language: &Language) -> Option<String> {
+        self.inner.lock().await.get_localized_error(error_code, language)
+    }
+
+    /// Execute the QUERY intent
+    #[instrument(skip(self), fields(device_id = %self.id))]
+    pub(crate) async fn query(&self) -> fulfillment::response::query::QueryDeviceState {
+        trace!("Running QUERY for device {}", self.id);
+
+        // Don't touch the traits of a device which can't be reached
+        if !self.inner.lock().await.is_online() {
+            return offline_query_device_state();
+        }
+
+        query_device_state(self.query_get_states().await)
+    }
+
+    /// Collect the states for all traits supported by the device
+    #[instrument(skip(self))]
+    async fn query_get_states(&self) -> Result<fulfillment::response::query::TraitsQueryDeviceState, ExecuteError> {
+        let mut states = fulfillment::response::query::TraitsQueryDeviceState::default();
+
+        if let Some(d) = &self.device_traits.on_off {
+            let d = d.lock().await;
+            if is_command_only(d.is_command_only().await)? {
+                // The device has no other traits which could be queried
+                return Err(ErrorCode::NotSupported.into());
+            }
+
+            states.on = Some(d.is_on().await?);
+        }
+
+        Ok(states)
+    }
+
+    /// Execute the SYNC intent
+    #[instrument(skip(self), fields(device_id = %self.id))]
+    pub(crate) async fn sync(&self) -> Result<fulfillment::response::sync::Device, Box<dyn Error>> {
+        trace!("Running SYNC for device {}", self.id);
+        let attributes = self.sync_set_attributes().await?;
+        Ok(sync_device(&self.id, &self.device_type, &self.traits, &*self.inner.lock().await, attributes))
+    }
+
+    /// Collect all attributes for all traits supported by the device
+    #[instrument(skip(self))]
+    async fn sync_set_attributes(&self) -> Result<fulfillment::response::sync::SyncAttributes, Box<dyn Error>> {
+        let mut attributes = fulfillment::response::sync::SyncAttributes::default();
+
+        if let Some(d) = &self.device_traits.on_off {
+            let d = d.lock().await;
+            attributes.command_only_on_off = d.is_command_only().await?;
+            attributes.query_only_on_off = d.is_query_only().await?;
+        }
+
+        Ok(attributes)
+    }
+
+    /// Execute a command on the device
+    #[instrument(skip(self), fields(device_id = %self.id))]
+    pub(crate) async fn execute(&mut self, command: CommandType) -> CommandOutput {
+        trace!("Running EXECUTE for device {}", self.id);
+        let result = self.execute_inner(command).await;
+        command_output(self.id.clone(), result)
+    }
+
+    /// Execute the EXECUTE intent
+    #[instrument(skip(self))]
+    async fn execute_inner(&mut self, command: CommandType) -> Result<Option<TraitCommandState>, ExecuteError> {
+        let mut state = None;
+
+        match command {
+            CommandType::OnOff { on } => {
+                let device = match &mut self.device_traits.on_off {
+                    Some(x) => x,
+                    None => return Err(unsupported()),
+                };
+
+                let mut device = device.lock().await;
+                reject_query_only(device.is_query_only().await)?;
+                device.set_on(on).await?;
+
+                // Command-only devices can't report their state
+                if !is_command_only(device.is_command_only().await)? {
+                    state = Some(TraitCommandState::OnOff(OnOffCommandState { on: device.is_on().await? }));
+                }
+            }
+            CommandType::Unknown { .. } => return Err(ErrorCode::CommandDispatchFailed.into()),
+            // There is no async variant of the other traits
+            _ => return Err(ErrorCode::NotSupported.into()),
+        }
+        Ok(state)
+    }
+
+    /// Register the [AsyncOnOff] trait
+    pub fn set_on_off(&mut self)
+    where
+        T: AsyncOnOff + Sized,
+    {
+        self.device_traits.on_off = Some(self.inner.clone());
+        self.push_trait(Trait::OnOff);
+    }
+
+    /// Deregister the [AsyncOnOff] trait
+    pub fn unset_on_off(&mut self) {
+        self.device_traits.on_off = None;
+        self.traits.retain(|t| *t != Trait::OnOff);
+    }
+}
+
+/// Contains all supported async device traits.
+/// If the [Option] is empty, then the trait is not registered for the [AsyncDevice]
+#[derive(Default)]
+struct AsyncDeviceTraits {
+    on_off: Option<Arc<Mutex<dyn AsyncOnOff + Send + Sync>>>,
+}
+
+impl fmt::Debug for AsyncDeviceTraits {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "AsyncDeviceTraits {{ .. }}")
+    }
+}
@@ -1,13 +1,17 @@
 use crate::device_trait::Trait;
 use crate::device_type::DeviceType;
 use crate::execute_error::ExecuteError;
-use crate::fulfillment::response::execute::CommandState;
+use crate::fulfillment::response::execute::{
+    CommandState, LockUnlockCommandState, NetworkControlCommandState, OnOffCommandState, OpenCloseCommandState, TraitCommandState, VolumeCommandState,
+};
 use crate::traits::app_selector::AppSelector;
 use crate::traits::arm_disarm::AvailableArmLevels;
 use crate::traits::camera_stream::CameraStream;
 use crate::traits::channel::Channel;
-use crate::traits::cook::{Cook, CookingConfig};
-use crate::traits::dispense::Dispense;
+use crate::traits::color_setting::ColorCommand;
+use crate::traits::cook::{validate_cooking_config, Cook, CookingConfig};
+use crate::traits::dispense;
+use crate::traits::dispense::{Dispense, DispenseError};
 use crate::traits::dock::Dock;
 use crate::traits::energy_storage::EnergyStorage;
 use crate::traits::fan_speed::FanSpeed;
@@ -31,18 +35,25 @@ use crate::traits::software_update::SoftwareUpdate;
 use crate::traits::start_stop::StartStop;
 use crate::traits::status_report::StatusReport;
 use crate::traits::temperature_control::TemperatureControl;
-use crate::traits::temperature_setting::TemperatureSetting;
+use crate::traits::temperature_setting::{TemperatureSetting, ThermostatMode};
 use crate::traits::timer::Timer;
 use crate::traits::toggles::Toggles;
 use crate::traits::transport_control::TransportControl;
 use crate::traits::volume::Volume;
-use crate::traits::ObjectDetection;
-use crate::{fulfillment, ArmDisarm, Brightness, ColorSetting, CommandOutput, CommandStatus, CommandType, GoogleHomeDevice, SerializableError};
-use std::cell::RefCell;
+use crate::traits::{Challenge, CombinedDeviceError, DeviceError, Language, ObjectDetection};
+use crate::{
+    fulfillment, ArmDisarm, Brightness, ColorSetting, CommandOutput, CommandStatus, CommandType, ErrorCode, GoogleHomeDevice, SerializableError,
+    ValidationError,
+};
 use std::error::Error;
 use std::fmt;
 use std::fmt::Debug;
-use std::rc::Rc;
+use std::sync::atomic::AtomicBool;
+#[cfg(feature = "catch-panics")]
+use std::sync::atomic::Ordering;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+#[cfg(feature = "catch-panics")]
+use tracing::error;
 use tracing::{instrument, trace};
 
 /// A Google Home device with its traits
@@ -52,7 +63,10 @@ pub struct Device<T: GoogleHomeDevice + Debug + Send + ?Sized + Sync + 'static>
     device_type: DeviceType,
     device_traits: DeviceTraits,
     traits: Vec<Trait>,
-    inner: Rc<RefCell<T>>,
+    inner: Arc<Mutex<T>>,
+    /// Whether a callback into the device panicked, see [catch_panic].
+    /// Shared, so it can be checked while the device is borrowed mutably during EXECUTE
+    panicked: Arc<AtomicBool>,
 }
 
 impl<T: GoogleHomeDevice + Send + Debug + Sync + 'static> Device<T> {
@@ -63,6 +77,7 @@ impl<T: GoogleHomeDevice + Send + Debug + Sync + 'static> Device<T> {
             device_traits,
             traits,
             inner,
+            panicked,
         } = self;
         Device {
             id,
@@ -70,6 +85,7 @@ impl<T: GoogleHomeDevice + Send + Debug + Sync + 'static> Device<T> {
             device_traits,
             traits,
             inner,
+            panicked,
         }
     }
 
@@ -80,476 +96,622 @@ impl<T: GoogleHomeDevice + Send + Debug + Sync + 'static> Device<T> {
             device_type,
             device_traits: DeviceTraits::default(),
             traits: Vec::new(),
-            inner: Rc::new(RefCell::new(device)),
+            inner: Arc::new(Mutex::new(device)),
+            panicked: Arc::new(AtomicBool::new(false)),
         }
     }
 }
 
 impl<T: GoogleHomeDevice + Send + Sync + Debug + ?Sized + 'static> Device<T> {
+    /// The traits registered on the device, in the order they were registered
+    pub fn registered_traits(&self) -> &[Trait] {
+        &self.traits
+    }
+
+    /// Add a trait to the registered traits, unless it is registered already
+    fn push_trait(&mut self, device_trait: Trait) {
+        if !self.traits.contains(&device_trait) {
+            self.traits.push(device_trait);
+        }
+    }
+
+    /// Access the device state, e.g. to update it outside of an intent
+    pub fn with_inner<R, F: FnOnce(&mut T) -> R>(&self, f: F) -> R {
+        f(&mut self.inner.lock_ignore_poison())
+    }
+
+    /// Check the registered traits against the [DeviceType] of the device.
+    /// Google rejects a SYNC in which a device advertises a trait that doesn't fit its type, e.g. [Trait::ColorSetting] on an [DeviceType::Outlet].
+    /// Like [Self::validate], this is not checked when the device is added, call it in your tests.
+    pub fn validate_trait_compatibility(&self) -> Vec<ValidationError> {
+        self.traits
+            .iter()
+            .filter(|device_trait| self.device_type.is_incompatible_with(device_trait))
+            .map(|device_trait| ValidationError::new(device_trait.clone(), format!("not supported by device type {:?}", self.device_type)))
+            .collect()
+    }
+
+    /// Check the registered traits against requirements of Google which can't be expressed in their signatures.
+    /// A device violating them produces SYNC or QUERY responses which Google silently rejects.
+    /// Call this in your tests, or when adding devices at startup.
+    pub fn validate(&self) -> Vec<ValidationError> {
+        let mut errors = Vec::new();
+
+        if let Some(d) = &self.device_traits.fan_speed {
+            let d = d.lock_ignore_poison();
+            match (d.get_available_fan_speeds(), d.is_support_fan_speed_percent()) {
+                (Ok(None), Ok(None | Some(false))) => errors
+                    .push(ValidationError::new(Trait::FanSpeed, "at least one of get_available_fan_speeds and is_support_fan_speed_percent must be supported")),
+                (Err(e), _) | (_, Err(e)) => errors.push(ValidationError::new(Trait::FanSpeed, e)),
+                _ => {}
+            }
+        }
+
+        if let Some(d) = &self.device_traits.modes {
+            match d.lock_ignore_poison().get_available_modes() {
+                Ok(modes) => errors.extend(
+                    modes
+                        .iter()
+                        .filter(|mode| mode.settings.len() < 2)
+                        .map(|mode| ValidationError::new(Trait::Modes, format!("mode '{}' must have at least 2 settings", mode.name))),
+                ),
+                Err(e) => errors.push(ValidationError::new(Trait::Modes, e)),
+            }
+        }
+
+        if let Some(d) = &self.device_traits.volume {
+            let d = d.lock_ignore_poison();
+            match (d.is_command_only_volume(), d.get_current_volume()) {
+                (Ok(None | Some(false)), Ok(None)) => {
+                    errors.push(ValidationError::new(Trait::Volume, "get_current_volume must be supported unless the device is command only"))
+                }
+                (Err(e), _) | (_, Err(e)) => errors.push(ValidationError::new(Trait::Volume, e)),
+                _ => {}
+            }
+        }
+
+        errors
+    }
+
+    // A panic in one of the callbacks below marks the device as broken, the next intent reports it
+
     pub(crate) fn disconnect(&mut self) {
-        self.inner.borrow_mut().disconnect();
+        let _ = catch_panic(&self.panicked, || self.inner.lock_ignore_poison().disconnect());
+    }
+
+    pub(crate) fn set_custom_data(&mut self, custom_data: Option<serde_json::Value>) {
+        let _ = catch_panic(&self.panicked, || self.inner.lock_ignore_poison().set_custom_data(custom_data));
+    }
+
+    pub(crate) fn set_request_id(&mut self, request_id: Option<&str>) {
+        let _ = catch_panic(&self.panicked, || self.inner.lock_ignore_poison().set_request_id(request_id));
+    }
+
+    pub(crate) fn set_challenge(&mut self, challenge: Option<Challenge>) {
+        let _ = catch_panic(&self.panicked, || self.inner.lock_ignore_poison().set_challenge(challenge));
+    }
+
+    pub(crate) fn get_localized_error(&self, error_code: &str, language: &Language) -> Option<String> {
+        catch_panic(&self.panicked, || self.inner.lock_ignore_poison().get_localized_error(error_code, language))
+            .ok()
+            .flatten()
     }
 
     /// Execute the QUERY intent
-    #[instrument]
+    #[instrument(skip(self), fields(device_id = %self.id))]
     pub(crate) fn query(&self) -> fulfillment::response::query::QueryDeviceState {
         trace!("Running QUERY for device {}", self.id);
 
-        let states = self.query_get_states();
-        let states = match states {
-            Ok(s) => s,
-            Err(e) => {
-                return fulfillment::response::query::QueryDeviceState {
-                    required: fulfillment::response::query::RequiredQueryDeviceState {
-                        status: fulfillment::response::query::QueryStatus::Error,
-                        on: false,
-                        online: self.inner.borrow().is_online(),
-                        error_code: Some(e.to_string()),
-                    },
-                    traits: None,
-                }
+        let states = catch_panic(&self.panicked, || {
+            // Don't touch the traits of a device which can't be reached
+            if !self.inner.lock_ignore_poison().is_online() {
+                return Ok(None);
             }
-        };
 
-        if !self.inner.borrow().is_online() {
-            return fulfillment::response::query::QueryDeviceState {
-                required: fulfillment::response::query::RequiredQueryDeviceState {
-                    status: fulfillment::response::query::QueryStatus::Offline,
-                    on: true,
-                    online: false,
-                    error_code: None,
-                },
-                traits: None,
-            };
-        }
+            self.query_get_states().map(Some)
+        })
+        .unwrap_or_else(|e| Err(e.into()));
 
-        fulfillment::response::query::QueryDeviceState {
-            required: fulfillment::response::query::RequiredQueryDeviceState {
-                status: fulfillment::response::query::QueryStatus::Success,
-                online: true,
-                on: true,
-                error_code: None,
-            },
-            traits: Some(states),
+        match states {
+            Ok(None) => offline_query_device_state(),
+            Ok(Some(states)) => query_device_state(Ok(states)),
+            Err(e) => query_device_state(Err(e)),
         }
     }
 
     /// Collect the states for all traits supported by the device
-    #[instrument]
-    fn query_get_states(&self) -> Result<fulfillment::response::query::TraitsQueryDeviceState, Box<dyn Error>> {
+    #[instrument(skip(self))]
+    fn query_get_states(&self) -> Result<fulfillment::response::query::TraitsQueryDeviceState, ExecuteError> {
         let mut states = fulfillment::response::query::TraitsQueryDeviceState::default();
+        // Whether the state of a trait was left out because the trait is command-only
+        let mut command_only = false;
 
         if let Some(d) = &self.device_traits.app_selector {
-            states.current_application = Some(d.borrow().get_current_application()?);
+            states.current_application = Some(d.lock_ignore_poison().get_current_application()?);
         }
 
         if let Some(d) = &self.device_traits.arm_disarm {
-            states.is_armed = Some(d.borrow().is_armed()?);
-            states.current_arm_level = Some(d.borrow().current_arm_level()?);
-            states.exit_allowance = Some(d.borrow().exit_allowance()?);
+            let d = d.lock_ignore_poison();
+            states.is_armed = Some(d.is_armed()?);
+            states.current_arm_level = Some(d.current_arm_level()?);
+            states.exit_allowance = Some(d.exit_allowance()?);
         }
 
         if let Some(d) = &self.device_traits.brightness {
-            states.brightness = Some(d.borrow().get_brightness()?);
+            let d = d.lock_ignore_poison();
+            if is_command_only(d.is_command_only_brightness().map(Some))? {
+                command_only = true;
+            } else {
+                states.brightness = Some(d.get_brightness()?);
+            }
         }
 
         // TODO CameraStream
         // TODO Channel
 
         if let Some(d) = &self.device_traits.color_setting {
-            states.color = Some(d.borrow().get_color()?);
+            let d = d.lock_ignore_poison();
+            if is_command_only(d.is_command_only_color_setting().map(Some))? {
+                command_only = true;
+            } else {
+                states.color = Some(d.get_color()?);
+            }
         }
 
         if let Some(d) = &self.device_traits.cook {
-            states.current_cooking_mode = Some(d.borrow().get_current_cooking_mode()?);
-            states.current_food_preset = d.borrow().get_current_food_preset()?;
-            states.current_food_unit = d.borrow().get_current_food_unit()?;
+            let d = d.lock_ignore_poison();
+            states.current_cooking_mode = Some(d.get_current_cooking_mode()?);
+            states.current_food_preset = d.get_current_food_preset()?;
+            states.current_food_quantity = d.get_current_food_quantity()?;
+            states.current_food_unit = d.get_current_food_unit()?;
         }
 
         if let Some(d) = &self.device_traits.dispense {
-            states.dispense_items = Some(d.borrow().get_dispense_items_state()?);
+            states.dispense_items = Some(d.lock_ignore_poison().get_dispense_items_state()?);
         }
 
         if let Some(d) = &self.device_traits.dock {
-            states.is_docked = Some(d.borrow().is_docked()?);
+            states.is_docked = Some(d.lock_ignore_poison().is_docked()?);
         }
 
         if let Some(d) = &self.device_traits.energy_storage {
-            states.descriptive_capacity_remaining = Some(d.borrow().get_descriptive_capacity_remaining()?);
-            states.capacity_remaining = d.borrow().get_capacity_remaining()?;
-            states.capacity_until_full = d.borrow().get_capacity_until_full()?;
-            states.is_charging = d.borrow().is_charging()?;
-            states.is_plugged_in = d.borrow().is_plugged_in()?;
+            let d = d.lock_ignore_poison();
+            states.descriptive_capacity_remaining = Some(d.get_descriptive_capacity_remaining()?);
+            states.capacity_remaining = d.get_capacity_remaining()?;
+            states.capacity_until_full = d.get_capacity_until_full()?;
+            states.is_charging = d.is_charging()?;
+            states.is_plugged_in = d.is_plugged_in()?;
         }
 
         if let Some(d) = &self.device_traits.fan_speed {
-            states.current_fan_speed_setting = d.borrow().get_current_fan_speed_setting()?;
-            states.current_fan_speed_percent = d.borrow().get_current_fan_speed_percent()?;
+            let d = d.lock_ignore_poison();
+            if is_command_only(d.is_command_only_fan_speed())? {
+                command_only = true;
+            } else {
+                states.current_fan_speed_setting = d.get_current_fan_speed_setting()?;
+                states.current_fan_speed_percent = d.get_current_fan_speed_percent()?;
+            }
         }
 
         if let Some(d) = &self.device_traits.fill {
-            states.is_filled = Some(d.borrow().is_filled()?);
-            states.current_fill_level = d.borrow().get_current_fill_level()?;
-            states.current_fill_percent = d.borrow().get_current_fill_percent()?;
+            let d = d.lock_ignore_poison();
+            states.is_filled = Some(d.is_filled()?);
+            states.current_fill_level = d.get_current_fill_level()?;
+            states.current_fill_percent = d.get_current_fill_percent()?;
         }
 
         if let Some(d) = &self.device_traits.humidity_setting {
-            states.humidity_setpoint_percent = Some(d.borrow().get_current_humidity_set_point_range()?);
-            states.humidity_ambient_percent = Some(d.borrow().get_current_humidity_ambient_percent()?);
+            let d = d.lock_ignore_poison();
+            if is_command_only(d.is_command_only_humidity_settings())? {
+                command_only = true;
+            } else {
+                states.humidity_setpoint_percent = Some(d.get_current_humidity_setpoint_percent()?);
+                states.humidity_ambient_percent = Some(d.get_current_humidity_ambient_percent()?);
+            }
         }
 
         if let Some(d) = &self.device_traits.input_selector {
-            states.current_input = Some(d.borrow().get_current_input()?);
+            let d = d.lock_ignore_poison();
+            if is_command_only(d.is_command_only_input_selector())? {
+                command_only = true;
+            } else {
+                states.current_input = Some(d.get_current_input()?);
+            }
         }
 
         if let Some(d) = &self.device_traits.light_effects {
-            states.active_light_effect = d.borrow().get_active_light_effect()?;
-            states.light_effect_end_unix_timestamp_sec = d.borrow().get_light_efccect_end_unix_timestamp_sec()?;
+            let d = d.lock_ignore_poison();
+            states.active_light_effect = d.get_active_light_effect()?;
+            states.light_effect_end_unix_timestamp_sec = d.get_light_efccect_end_unix_timestamp_sec()?;
         }
 
         if let Some(d) = &self.device_traits.lock_unlock {
-            states.is_locked = Some(d.borrow().is_locked()?);
-            states.is_jammed = Some(d.borrow().is_jammed()?);
+            let d = d.lock_ignore_poison();
+            states.is_locked = Some(d.is_locked()?);
+            states.is_jammed = Some(d.is_jammed()?);
         }
 
         if let Some(d) = &self.device_traits.media_state {
-            states.activity_state = d.borrow().get_activity_state()?;
-            states.playback_state = d.borrow().get_playback_state()?;
+            let d = d.lock_ignore_poison();
+            states.activity_state = d.get_activity_state()?;
+            states.playback_state = d.get_playback_state()?;
         }
 
         if let Some(d) = &self.device_traits.modes {
-            states.current_mode_setting = Some(d.borrow().get_current_mode_settings()?);
+            let d = d.lock_ignore_poison();
+            if is_command_only(d.is_command_only_modes())? {
+                command_only = true;
+            } else {
+                states.current_mode_setting = Some(d.get_current_mode_settings()?);
+            }
         }
 
         if let Some(d) = &self.device_traits.network_control {
-            states.network_enabled = Some(d.borrow().is_network_enabled()?);
-            states.network_settings = Some(d.borrow().get_network_settings()?);
-            states.guest_network_enabled = Some(d.borrow().is_guest_network_enabled()?);
-            states.guest_network_settings = Some(d.borrow().get_guest_network_settings()?);
-            states.num_connected_devices = Some(d.borrow().get_num_connected_devices()?);
-            states.network_usage_mb = Some(d.borrow().get_network_usage_mb()?);
-            states.network_usage_unlimited = Some(d.borrow().is_network_usage_unlimited()?);
-            states.last_network_download_speed_test = Some(d.borrow().get_last_network_download_speed_test()?);
-            states.last_network_upload_speed_test = Some(d.borrow().get_last_network_upload_speed_test()?);
-            states.network_speed_test_in_progress = d.borrow().is_network_speed_test_in_progress()?;
-            states.network_profiles_state = Some(d.borrow().get_network_profiles_state()?);
+            let d = d.lock_ignore_poison();
+            states.network_enabled = Some(d.is_network_enabled()?);
+            states.network_settings = Some(d.get_network_settings()?);
+            states.guest_network_enabled = Some(d.is_guest_network_enabled()?);
+            states.guest_network_settings = Some(d.get_guest_network_settings()?);
+            states.num_connected_devices = Some(d.get_num_connected_devices()?);
+            states.network_usage_mb = Some(d.get_network_usage_mb()?);
+
+            // Google ignores the limit if usage is unlimited, so don't report it at all
+            let network_usage_unlimited = d.is_network_usage_unlimited()?;
+            states.network_usage_unlimited = Some(network_usage_unlimited);
+            if !network_usage_unlimited {
+                states.network_usage_limit_mb = Some(d.get_network_usage_limit_mb()?);
+            }
+
+            states.last_network_download_speed_test = Some(d.get_last_network_download_speed_test()?);
+            states.last_network_upload_speed_test = Some(d.get_last_network_upload_speed_test()?);
+            states.network_speed_test_in_progress = d.is_network_speed_test_in_progress()?;
+            states.network_profiles_state = Some(d.get_network_profiles_state()?);
         }
 
         if let Some(d) = &self.device_traits.on_off {
-            states.on = Some(d.borrow().is_on()?);
+            let d = d.lock_ignore_poison();
+            if is_command_only(d.is_command_only())? {
+                command_only = true;
+            } else {
+                states.on = Some(d.is_on()?);
+            }
         }
 
         if let Some(d) = &self.device_traits.open_close {
-            states.open_percent = d.borrow().get_open_percent()?;
-            states.open_state = d.borrow().get_open_state()?;
+            let d = d.lock_ignore_poison();
+            if is_command_only(d.is_command_only_open_close())? {
+                command_only = true;
+            } else {
+                states.open_percent = d.get_open_percent()?;
+                states.open_state = d.get_open_state()?;
+            }
         }
 
         if let Some(d) = &self.device_traits.rotation {
-            states.rotation_degrees = Some(d.borrow().get_rotation_degrees()?);
-            states.rotation_percent = Some(d.borrow().get_rotation_percent()?);
+            let d = d.lock_ignore_poison();
+            if is_command_only(d.is_command_only_rotation())? {
+                command_only = true;
+            } else {
+                states.rotation_degrees = Some(d.get_rotation_degrees()?);
+                states.rotation_percent = Some(d.get_rotation_percent()?);
+            }
         }
 
         if let Some(d) = &self.device_traits.run_cycle {
-            states.current_run_cycle = Some(d.borrow().get_current_run_cycle()?);
-            states.current_total_remaining_time = Some(d.borrow().get_current_total_remaining_time()?);
-            states.current_cycle_remaining_time = Some(d.borrow().get_current_cycle_remaining_time()?);
+            let d = d.lock_ignore_poison();
+            states.current_run_cycle = Some(d.get_current_run_cycle()?);
+            states.current_total_remaining_time = Some(d.get_current_total_remaining_time()?);
+            states.current_cycle_remaining_time = Some(d.get_current_cycle_remaining_time()?);
         }
 
         if let Some(d) = &self.device_traits.sensor_state {
-            states.current_sensor_state_data = Some(d.borrow().get_current_sensor_states()?);
+            states.current_sensor_state_data = Some(d.lock_ignore_poison().get_current_sensor_states()?);
         }
 
         if let Some(d) = &self.device_traits.software_update {
-            states.last_software_update_unix_timestamp_sec = Some(d.borrow().get_last_software_update_unix_timestamp_sec()?);
+            states.last_software_update_unix_timestamp_sec = Some(d.lock_ignore_poison().get_last_software_update_unix_timestamp_sec()?);
         }
 
         if let Some(d) = &self.device_traits.start_stop {
-            states.is_running = Some(d.borrow().is_running()?);
-            states.is_paused = d.borrow().is_paused()?;
-            states.active_zones = d.borrow().get_active_zones()?;
+            let d = d.lock_ignore_poison();
+            states.is_running = Some(d.is_running()?);
+            states.is_paused = d.is_paused()?;
+            states.active_zones = d.get_active_zones()?;
         }
 
         if let Some(d) = &self.device_traits.status_report {
-            states.current_status_report = Some(d.borrow().get_current_status_report()?);
+            states.current_status_report = Some(d.lock_ignore_poison().get_current_status_report()?);
         }
 
         if let Some(d) = &self.device_traits.temperature_control {
-            states.temperature_setpoint_celsius = Some(d.borrow().get_temperature_setpoint_celsius()?);
-            states.temperature_ambient_celsius = Some(d.borrow().get_temperatuer_ambient_celsius()?);
+            let d = d.lock_ignore_poison();
+            if is_command_only(d.is_command_only_temperature_control())? {
+                command_only = true;
+            } else {
+                states.temperature_setpoint_celsius = Some(d.get_temperature_setpoint_celsius()?);
+                states.temperature_ambient_celsius = Some(d.get_temperatuer_ambient_celsius()?);
+            }
         }
 
         if let Some(d) = &self.device_traits.temperature_setting {
-            states.active_thermostat_mode = Some(d.borrow().get_active_thermostat_mode()?);
-            states.target_temp_reached_estimate_unix_timestamp_sec = d.borrow().get_target_temp_reached_estimate_unix_timestamp_sec()?;
-            states.thermostat_humidity_ambient = d.borrow().get_thermostat_humidity_ambient()?;
-            states.thermostat_mode = Some(d.borrow().get_thermostat_mode()?);
+            let d = d.lock_ignore_poison();
+            if is_command_only(d.is_command_only_temperature_setting())? {
+                command_only = true;
+            } else {
+                states.active_thermostat_mode = Some(d.get_active_thermostat_mode()?);
+                states.target_temp_reached_estimate_unix_timestamp_sec = d.get_target_temp_reached_estimate_unix_timestamp_sec()?;
+                states.thermostat_humidity_ambient = d.get_thermostat_humidity_ambient()?;
+                states.thermostat_mode = Some(d.get_thermostat_mode()?);
+            }
         }
 
         if let Some(d) = &self.device_traits.timer {
-            // The API requires this to be -1 if there is no timer set
-            // Because we want idiomatic Rust, it's wrapped in an Option
-            // for if no timer is set
-            states.timer_remaining_sec = Some(d.borrow().get_timer_remaining_sec()?.unwrap_or(-1));
-            states.timer_paused = d.borrow().is_timer_paused()?;
+            let d = d.lock_ignore_poison();
+            if is_command_only(d.is_command_only_timer())? {
+                command_only = true;
+            } else {
+                // The API requires this to be -1 if there is no timer set
+                // Because we want idiomatic Rust, it's wrapped in an Option
+                // for if no timer is set
+                states.timer_remaining_sec = Some(d.get_timer_remaining_sec()?.unwrap_or(-1));
+                states.timer_paused = d.is_timer_paused()?;
+            }
         }
 
         if let Some(d) = &self.device_traits.volume {
-            states.current_volume = d.borrow().get_current_volume()?;
-            states.is_muted = d.borrow().is_muted()?
+            let d = d.lock_ignore_poison();
+            if is_command_only(d.is_command_only_volume())? {
+                command_only = true;
+            } else {
+                states.current_volume = d.get_current_volume()?;
+                states.is_muted = d.is_muted()?
+            }
         }
 
         if let Some(d) = &self.device_traits.toggles {
-            states.current_toggle_settings = Some(d.borrow().get_current_toggle_settings()?);
+            let d = d.lock_ignore_poison();
+            if is_command_only(d.is_command_only_toggles())? {
+                command_only = true;
+            } else {
+                states.current_toggle_settings = Some(d.get_current_toggle_settings()?);
+            }
+        }
+
+        // None of the traits of the device can be queried
+        if command_only && states == fulfillment::response::query::TraitsQueryDeviceState::default() {
+            return Err(ErrorCode::NotSupported.into());
         }
 
         Ok(states)
     }
 
     /// Execute the SYNC intent
-    #[instrument]
+    #[instrument(skip(self), fields(device_id = %self.id))]
     pub(crate) fn sync(&self) -> Result<fulfillment::response::sync::Device, Box<dyn Error>> {
         trace!("Running SYNC for device {}", self.id);
-        let name = self.inner.borrow().get_device_name();
-        let info = self.inner.borrow().get_device_info();
-
-        Ok(fulfillment::response::sync::Device {
-            id: self.id.clone(),
-            device_type: self.device_type.as_device_type_string(),
-            traits: self.traits.clone(),
-            name: fulfillment::response::sync::DeviceName {
-                name: name.name,
-                default_names: name.default_names,
-                nicknames: name.nicknames,
-            },
-            will_report_state: self.inner.borrow().will_report_state(),
-            room_hint: self.inner.borrow().get_room_hint(),
-            device_info: fulfillment::response::sync::DeviceInfo {
-                manufacturer: info.manufacturer,
-                model: info.model,
-                hw_version: info.hw,
-                sw_version: info.sw,
-            },
-            attributes: self.sync_set_attributes()?,
+        catch_panic(&self.panicked, || {
+            let attributes = self.sync_set_attributes()?;
+            Ok(sync_device(&self.id, &self.device_type, &self.traits, &*self.inner.lock_ignore_poison(), attributes))
         })
+        .unwrap_or_else(|e| Err(e.into()))
     }
 
     /// Collect all attributes for all traits supported by the device
-    #[instrument]
+    #[instrument(skip(self))]
     fn sync_set_attributes(&self) -> Result<fulfillment::response::sync::SyncAttributes, Box<dyn Error>> {
         let mut attributes = fulfillment::response::sync::SyncAttributes::default();
 
         if let Some(d) = &self.device_traits.app_selector {
-            attributes.available_applications = Some(d.borrow().get_available_applications()?);
+            attributes.available_applications = Some(d.lock_ignore_poison().get_available_applications()?);
         }
 
         if let Some(d) = &self.device_traits.arm_disarm {
-            attributes.available_arm_levels = Some(AvailableArmLevels {
-                levels: d.borrow().get_available_arm_levels()?,
-                ordered: d.borrow().is_ordered()?,
-            });
+            let d = d.lock_ignore_poison();
+            let levels = d.get_available_arm_levels()?;
+            let ordered = d.is_ordered()?;
+            attributes.available_arm_levels = Some(AvailableArmLevels { levels, ordered });
         }
 
         if let Some(d) = &self.device_traits.brightness {
-            attributes.command_only_brightness = Some(d.borrow().is_command_only_brightness()?);
+            attributes.command_only_brightness = Some(d.lock_ignore_poison().is_command_only_brightness()?);
         }
 
         if let Some(d) = &self.device_traits.camera_stream {
-            attributes.camera_stream_supported_protocols = Some(d.borrow().get_supported_camera_stream_protocols()?);
-            attributes.camera_stream_need_auth_token = Some(d.borrow().need_auth_token()?);
+            let d = d.lock_ignore_poison();
+            attributes.camera_stream_supported_protocols = Some(d.get_supported_camera_stream_protocols()?);
+            attributes.camera_stream_need_auth_token = Some(d.need_auth_token()?);
         }
 
         if let Some(d) = &self.device_traits.channel {
-            attributes.available_channels = Some(d.borrow().get_available_channels()?);
-            attributes.command_only_channels = d.borrow().is_command_only_channels()?;
+            let d = d.lock_ignore_poison();
+            attributes.available_channels = Some(d.get_available_channels()?);
+            attributes.command_only_channels = d.is_command_only_channels()?;
         }
 
         if let Some(d) = &self.device_traits.color_setting {
-            attributes.command_only_color_setting = Some(d.borrow().is_command_only_color_setting()?);
-            let support = d.borrow().get_color_model_support()?;
+            let d = d.lock_ignore_poison();
+            attributes.command_only_color_setting = Some(d.is_command_only_color_setting()?);
+            let support = d.get_color_model_support()?;
             attributes.color_model = support.color_model;
             attributes.color_temperature_range = support.color_temperature_range;
         }
 
         if let Some(d) = &self.device_traits.cook {
-            attributes.supported_cooking_modes = Some(d.borrow().get_supported_cooking_modes()?);
-            attributes.food_presets = Some(d.borrow().get_food_presets()?);
+            let d = d.lock_ignore_poison();
+            attributes.supported_cooking_modes = Some(d.get_supported_cooking_modes()?);
+            attributes.food_presets = Some(d.get_food_presets()?);
         }
 
         if let Some(d) = &self.device_traits.dispense {
-            attributes.supported_dispense_items = Some(d.borrow().get_supported_dispense_items()?);
-            attributes.supported_dispense_presets = Some(d.borrow().get_supported_dispense_presets()?);
+            let d = d.lock_ignore_poison();
+            attributes.supported_dispense_items = Some(d.get_supported_dispense_items()?);
+            attributes.supported_dispense_presets = Some(d.get_supported_dispense_presets()?);
         }
 
         if let Some(d) = &self.device_traits.energy_storage {
-            attributes.query_only_energy_storage = Some(d.borrow().is_query_only()?);
-            attributes.energy_storage_distance_unit_for_ux = Some(d.borrow().get_distance_unit_for_ux()?);
-            attributes.is_rechargeable = Some(d.borrow().is_rechargable()?);
+            let d = d.lock_ignore_poison();
+            attributes.query_only_energy_storage = Some(d.is_query_only()?);
+            attributes.energy_storage_distance_unit_for_ux = Some(d.get_distance_unit_for_ux()?);
+            attributes.is_rechargeable = Some(d.is_rechargable()?);
         }
 
         if let Some(d) = &self.device_traits.fan_speed {
-            attributes.reversible = d.borrow().is_reversable()?;
-            attributes.command_only_fan_speed = d.borrow().is_command_only_fan_speed()?;
-            attributes.available_fan_speeds = d.borrow().get_available_fan_speeds()?;
-            attributes.supports_fan_speed_percent = d.borrow().is_support_fan_speed_percent()?;
+            let d = d.lock_ignore_poison();
+            attributes.reversible = d.is_reversable()?;
+            attributes.command_only_fan_speed = d.is_command_only_fan_speed()?;
+            attributes.available_fan_speeds = d.get_available_fan_speeds()?;
+            attributes.supports_fan_speed_percent = d.is_support_fan_speed_percent()?;
         }
 
         if let Some(d) = &self.device_traits.fill {
-            attributes.available_fill_levels = Some(d.borrow().get_available_fill_levels()?);
+            attributes.available_fill_levels = Some(d.lock_ignore_poison().get_available_fill_levels()?);
         }
 
         if let Some(d) = &self.device_traits.humidity_setting {
-            attributes.humidity_set_point_range = d.borrow().get_humidity_set_point_range_minmax()?;
-            attributes.command_only_humidity_setting = d.borrow().is_command_only_humidity_settings()?;
-            attributes.query_only_humidity_setting = d.borrow().is_query_only_humidity_setting()?;
+            let d = d.lock_ignore_poison();
+            attributes.humidity_set_point_range = d.get_humidity_set_point_range_minmax()?;
+            attributes.command_only_humidity_setting = d.is_command_only_humidity_settings()?;
+            attributes.query_only_humidity_setting = d.is_query_only_humidity_setting()?;
         }
 
         if let Some(d) = &self.device_traits.input_selector {
-            attributes.available_inputs = Some(d.borrow().get_available_inputs()?);
-            attributes.command_only_input_selector = d.borrow().is_command_only_input_selector()?;
-            attributes.ordered_inputs = d.borrow().has_ordered_inputs()?;
+            let d = d.lock_ignore_poison();
+            attributes.available_inputs = Some(d.get_available_inputs()?);
+            attributes.command_only_input_selector = d.is_command_only_input_selector()?;
+            attributes.ordered_inputs = d.has_ordered_inputs()?;
         }
 
         if let Some(d) = &self.device_traits.light_effects {
-            attributes.default_color_loop_duration = d.borrow().get_default_color_loop_duration()?;
-            attributes.default_sleep_duration = d.borrow().get_default_sleep_duration()?;
-            attributes.default_wake_duration = d.borrow().get_default_wake_duration()?;
-            attributes.supported_effects = Some(d.borrow().get_supported_effects()?);
+            let d = d.lock_ignore_poison();
+            attributes.default_color_loop_duration = d.get_default_color_loop_duration()?;
+            attributes.default_sleep_duration = d.get_default_sleep_duration()?;
+            attributes.default_wake_duration = d.get_default_wake_duration()?;
+            attributes.supported_effects = Some(d.get_supported_effects()?);
         }
 
         if let Some(d) = &self.device_traits.media_state {
-            attributes.support_activity_state = d.borrow().does_support_activity_state()?;
-            attributes.support_playback_state = d.borrow().does_support_playback_state()?;
+            let d = d.lock_ignore_poison();
+            attributes.support_activity_state = d.does_support_activity_state()?;
+            attributes.support_playback_state = d.does_support_playback_state()?;
         }
 
         if let Some(d) = &self.device_traits.modes {
-            attributes.available_modes = Some(d.borrow().get_available_modes()?);
-            attributes.command_only_modes = d.borrow().is_command_only_modes()?;
-            attributes.query_only_modes = d.borrow().is_query_only_modes()?;
+            let d = d.lock_ignore_poison();
+            attributes.available_modes = Some(d.get_available_modes()?);
+            attributes.command_only_modes = d.is_command_only_modes()?;
+            attributes.query_only_modes = d.is_query_only_modes()?;
         }
 
         if let Some(d) = &self.device_traits.network_control {
-            attributes.network_profiles = d.borrow().get_network_profiles()?;
-            attributes.supports_enabling_guest_network = d.borrow().supports_disabling_guest_network()?;
-            attributes.supports_disabling_guest_network = d.borrow().supports_disabling_guest_network()?;
-            attributes.supports_getting_guest_network_password = d.borrow().supports_getting_guest_network_password()?;
-            attributes.supports_enabling_network_profile = d.borrow().supports_enabling_network_profile()?;
-            attributes.supports_disabling_network_profile = d.borrow().supports_disabling_network_profile()?;
-            attributes.supports_network_download_speed_test = d.borrow().supports_network_download_speed_test()?;
-            attributes.supports_network_upload_speed_test = d.borrow().supports_network_upload_speed_test()?;
+            let d = d.lock_ignore_poison();
+            attributes.network_profiles = d.get_network_profiles()?;
+            attributes.supports_enabling_guest_network = d.supports_enabling_guest_network()?;
+            attributes.supports_disabling_guest_network = d.supports_disabling_guest_network()?;
+            attributes.supports_getting_guest_network_password = d.supports_getting_guest_network_password()?;
+            attributes.supports_enabling_network_profile = d.supports_enabling_network_profile()?;
+            attributes.supports_disabling_network_profile = d.supports_disabling_network_profile()?;
+            attributes.supports_network_download_speed_test = d.supports_network_download_speed_test()?;
+            attributes.supports_network_upload_speed_test = d.supports_network_upload_speed_test()?;
         }
 
         if let Some(d) = &self.device_traits.on_off {
-            attributes.command_only_on_off = d.borrow().is_command_only()?;
-            attributes.query_only_on_off = d.borrow().is_query_only()?;
+            let d = d.lock_ignore_poison();
+            attributes.command_only_on_off = d.is_command_only()?;
+            attributes.query_only_on_off = d.is_query_only()?;
         }
 
         if let Some(d) = &self.device_traits.open_close {
-            attributes.discrete_only_open_close = d.borrow().is_discrete_only_open_close()?;
-            attributes.open_direction = d.borrow().get_supported_opening_directions()?;
-            attributes.command_only_open_close = d.borrow().is_command_only_open_close()?;
-            attributes.query_only_open_close = d.borrow().is_query_only_open_close()?;
+            let d = d.lock_ignore_poison();
+            attributes.discrete_only_open_close = d.is_discrete_only_open_close()?;
+            attributes.open_direction = d.get_supported_opening_directions()?;
+            attributes.command_only_open_close = d.is_command_only_open_close()?;
+            attributes.query_only_open_close = d.is_query_only_open_close()?;
         }
 
         if let Some(d) = &self.device_traits.rotation {
-            attributes.supports_degrees = Some(d.borrow().supports_degrees()?);
-            attributes.supports_percent = Some(d.borrow().supports_percent()?);
-            attributes.rotation_degrees_range = Some(d.borrow().get_rotation_degree_range()?);
-            attributes.supports_continuous_rotation = d.borrow().supports_continuous_rotation()?;
-            attributes.command_only_rotation = d.borrow().is_command_only_rotation()?;
+            let d = d.lock_ignore_poison();
+            attributes.supports_degrees = Some(d.supports_degrees()?);
+            attributes.supports_percent = Some(d.supports_percent()?);
+            attributes.rotation_degrees_range = Some(d.get_rotation_degree_range()?);
+            attributes.supports_continuous_rotation = d.supports_continuous_rotation()?;
+            attributes.command_only_rotation = d.is_command_only_rotation()?;
         }
 
         if let Some(d) = &self.device_traits.scene {
-            attributes.scene_reversible = d.borrow().is_reversible()?;
+            attributes.scene_reversible = d.lock_ignore_poison().is_reversible()?;
         }
 
         if let Some(d) = &self.device_traits.sensor_state {
-            attributes.sensor_states_supported = Some(d.borrow().get_supported_sensor_states()?);
+            attributes.sensor_states_supported = Some(d.lock_ignore_poison().get_supported_sensor_states()?);
         }
 
         if let Some(d) = &self.device_traits.start_stop {
-            attributes.pausable = d.borrow().is_pausable()?;
-            attributes.available_zones = d.borrow().get_available_zones()?;
+            let d = d.lock_ignore_poison();
+            attributes.pausable = d.is_pausable()?;
+            attributes.available_zones = d.get_available_zones()?;
         }
 
         if let Some(d) = &self.device_traits.temperature_control {
-            attributes.temperature_range = Some(d.borrow().get_temperature_range()?);
-            attributes.temperature_step_celsius = d.borrow().get_temperature_step_celsius()?;
-            attributes.temperature_unit_for_ux = Some(d.borrow().get_temperature_unit_for_ux()?);
-            attributes.command_only_temperature_control = d.borrow().is_command_only_temperature_control()?;
-            attributes.query_only_temperature_control = d.borrow().is_query_only_temperature_control()?;
+            let d = d.lock_ignore_poison();
+            attributes.temperature_range = Some(d.get_temperature_range()?);
+            attributes.temperature_step_celsius = d.get_temperature_step_celsius()?;
+            attributes.temperature_unit_for_ux = Some(d.get_temperature_unit_for_ux()?);
+            attributes.command_only_temperature_control = d.is_command_only_temperature_control()?;
+            attributes.query_only_temperature_control = d.is_query_only_temperature_control()?;
         }
 
         if let Some(d) = &self.device_traits.temperature_setting {
-            attributes.available_thermostat_modes = Some(d.borrow().get_available_thermostat_modes()?);
-            attributes.thermostat_temperature_range = d.borrow().get_thermostat_temperature_range()?;
-            attributes.thermostat_temperature_unit = Some(d.borrow().get_thermostat_temperature_unit()?);
-            attributes.buffer_range_celsius = d.borrow().get_buffer_range_celsius()?;
-            attributes.command_only_temperature_setting = d.borrow().is_command_only_temperature_setting()?;
-            attributes.query_only_temperature_setting = d.borrow().is_query_only_temperature_setting()?;
+            let d = d.lock_ignore_poison();
+            attributes.available_thermostat_modes = Some(d.get_available_thermostat_modes()?);
+            attributes.thermostat_temperature_range = d.get_thermostat_temperature_range()?;
+            attributes.thermostat_temperature_unit = Some(d.get_thermostat_temperature_unit()?);
+            attributes.buffer_range_celsius = d.get_buffer_range_celsius()?;
+            attributes.command_only_temperature_setting = d.is_command_only_temperature_setting()?;
+            attributes.query_only_temperature_setting = d.is_query_only_temperature_setting()?;
         }
 
         if let Some(d) = &self.device_traits.timer {
-            attributes.max_timer_limit_sec = Some(d.borrow().get_max_timer_limit_sec()?);
-            attributes.command_only_timer = d.borrow().is_command_only_timer()?;
+            let d = d.lock_ignore_poison();
+            attributes.max_timer_limit_sec = Some(d.get_max_timer_limit_sec()?);
+            attributes.command_only_timer = d.is_command_only_timer()?;
         }
 
         if let Some(d) = &self.device_traits.toggles {
-            attributes.available_toggles = Some(d.borrow().get_available_toggles()?);
-            attributes.command_only_toggles = d.borrow().is_command_only_toggles()?;
-            attributes.query_only_toggles = d.borrow().is_query_only_toggles()?;
+            let d = d.lock_ignore_poison();
+            attributes.available_toggles = Some(d.get_available_toggles()?);
+            attributes.command_only_toggles = d.is_command_only_toggles()?;
+            attributes.query_only_toggles = d.is_query_only_toggles()?;
         }
 
         if let Some(d) = &self.device_traits.transport_control {
-            attributes.transport_control_supported_commands = Some(d.borrow().get_supported_control_commands()?);
+            attributes.transport_control_supported_commands = Some(d.lock_ignore_poison().get_supported_control_commands()?);
         }
 
         if let Some(d) = &self.device_traits.volume {
-            attributes.volume_max_level = Some(d.borrow().get_volume_max_level()?);
-            attributes.volume_can_mute_and_unmute = Some(d.borrow().can_mute_and_unmute()?);
-            attributes.volume_default_percentage = d.borrow().get_volume_default_percentage()?;
-            attributes.level_step_size = d.borrow().get_level_step_size()?;
-            attributes.command_only_volume = d.borrow().is_command_only_volume()?;
+            let d = d.lock_ignore_poison();
+            attributes.volume_max_level = Some(d.get_volume_max_level()?);
+            attributes.volume_can_mute_and_unmute = Some(d.can_mute_and_unmute()?);
+            attributes.volume_default_percentage = d.get_volume_default_percentage()?;
+            attributes.level_step_size = d.get_level_step_size()?;
+            attributes.command_only_volume = d.is_command_only_volume()?;
         }
 
         Ok(attributes)
     }
 
     /// Execute the EXECUTE intent. Handles the error handling, delegates to [Self::execute_inner]
-    #[instrument]
+    #[instrument(skip(self), fields(device_id = %self.id))]
     pub(crate) fn execute(&mut self, command: CommandType) -> CommandOutput {
         trace!("Running EXECUTE for device {}", self.id);
-        match self.execute_inner(command) {
-            Ok(state) => CommandOutput {
-                id: self.id.clone(),
-                status: CommandStatus::Success,
-                state: Some(state),
-                error: None,
-                debug_string: None,
-            },
-            Err(e) => match e {
-                ExecuteError::Serializable(e) => CommandOutput {
-                    id: self.id.clone(),
-                    status: CommandStatus::Error,
-                    state: None,
-                    error: Some(SerializableError(e)),
-                    debug_string: None,
-                },
-                ExecuteError::Server(e) => CommandOutput {
-                    // TODO: maybe print the error?
-                    id: self.id.clone(),
-                    status: CommandStatus::Offline,
-                    state: None,
-                    error: None,
-                    debug_string: Some(e.to_string()),
-                },
-            },
-        }
+        let panicked = self.panicked.clone();
+        let result = catch_panic(&panicked, || self.execute_inner(command)).unwrap_or_else(|e| Err(e.into()));
+        command_output(self.id.clone(), result)
     }
 
     /// Execute the EXECUTE intent
-    #[instrument]
-    fn execute_inner(&mut self, command: CommandType) -> Result<CommandState, ExecuteError> {
-        let mut state = CommandState::default();
+    #[instrument(skip(self))]
+    fn execute_inner(&mut self, command: CommandType) -> Result<Option<TraitCommandState>, ExecuteError> {
+        let mut state = None;
 
         match command {
             CommandType::AppInstall {
@@ -558,15 +720,15 @@ impl<T: GoogleHomeDevice + Send + Sync + Debug + ?Sized + 'static> Device<T> {
             } => {
                 let device = match &mut self.device_traits.app_selector {
                     Some(x) => x,
-                    None => panic!("Unsupported"),
+                    None => return Err(unsupported()),
                 };
 
                 if let Some(key) = new_application {
-                    device.borrow_mut().app_install_key(key)?;
+                    device.lock_ignore_poison().app_install_key(key)?;
                 }
 
                 if let Some(name) = new_application_name {
-                    device.borrow_mut().app_install_name(name)?;
+                    device.lock_ignore_poison().app_install_name(name)?;
                 }
             }
             CommandType::AppSearch {
@@ -575,15 +737,15 @@ impl<T: GoogleHomeDevice + Send + Sync + Debug + ?Sized + 'static> Device<T> {
             } => {
                 let device = match &mut self.device_traits.app_selector {
                     Some(x) => x,
-                    None => panic!("Unsupported"),
+                    None => return Err(unsupported()),
                 };
 
                 if let Some(key) = new_application {
-                    device.borrow_mut().app_search_key(key)?;
+                    device.lock_ignore_poison().app_search_key(key)?;
                 }
 
                 if let Some(name) = new_application_name {
-                    device.borrow_mut().app_search_name(name)?;
+                    device.lock_ignore_poison().app_search_name(name)?;
                 }
             }
             CommandType::AppSelect {
@@ -592,42 +754,47 @@ impl<T: GoogleHomeDevice + Send + Sync + Debug + ?Sized + 'static> Device<T> {
             } => {
                 let device = match &mut self.device_traits.app_selector {
                     Some(x) => x,
-                    None => panic!("Unsupported"),
+                    None => return Err(unsupported()),
                 };
 
                 if let Some(key) = new_application {
-                    device.borrow_mut().app_select_key(key)?;
+                    device.lock_ignore_poison().app_select_key(key)?;
                 }
 
                 if let Some(name) = new_application_name {
-                    device.borrow_mut().app_select_name(name)?;
+                    device.lock_ignore_poison().app_select_name(name)?;
                 }
             }
             CommandType::ArmDisarm { arm, cancel, arm_level, .. } => {
                 let device = match &mut self.device_traits.arm_disarm {
                     Some(x) => x,
-                    None => panic!("Unsupported"),
+                    None => return Err(unsupported()),
                 };
 
                 if let Some(cancel) = cancel {
                     if cancel {
-                        device.borrow_mut().cancel_arm()?;
+                        device.lock_ignore_poison().cancel_arm()?;
                     }
                 } else {
                     if let Some(level) = arm_level {
-                        device.borrow_mut().arm_with_level(arm, level)?;
+                        device.lock_ignore_poison().arm_with_level(arm, level)?;
                     } else {
-                        device.borrow_mut().arm(arm)?;
+                        device.lock_ignore_poison().arm(arm)?;
                     }
                 }
             }
             CommandType::BrightnessAbsolute { brightness } => {
                 let device = match &mut self.device_traits.brightness {
                     Some(x) => x,
-                    None => panic!("Unsupported"),
+                    None => return Err(unsupported()),
                 };
 
-                device.borrow_mut().set_brightness_absolute(brightness)?;
+                // Brightness is a normalized percentage
+                if !(0..=100).contains(&brightness) {
+                    return Err(CombinedDeviceError::DeviceError(DeviceError::ValueOutOfRange).into());
+                }
+
+                device.lock_ignore_poison().set_brightness_absolute(brightness)?;
             }
             CommandType::BrightnessRelative {
                 brightness_relative_percent,
@@ -635,15 +802,15 @@ impl<T: GoogleHomeDevice + Send + Sync + Debug + ?Sized + 'static> Device<T> {
             } => {
                 let device = match &mut self.device_traits.brightness {
                     Some(x) => x,
-                    None => panic!("Unsupported"),
+                    None => return Err(unsupported()),
                 };
 
                 if let Some(brightness_relative_percent) = brightness_relative_percent {
-                    device.borrow_mut().set_brightness_relative_percent(brightness_relative_percent)?;
+                    device.lock_ignore_poison().set_brightness_relative_percent(brightness_relative_percent)?;
                 }
 
                 if let Some(brightness_relative_weight) = brightness_relative_weight {
-                    device.borrow_mut().set_brightness_relative_weight(brightness_relative_weight)?;
+                    device.lock_ignore_poison().set_brightness_relative_weight(brightness_relative_weight)?;
                 }
             }
             CommandType::GetCameraStream {
@@ -652,10 +819,12 @@ impl<T: GoogleHomeDevice + Send + Sync + Debug + ?Sized + 'static> Device<T> {
             } => {
                 let device = match &mut self.device_traits.camera_stream {
                     Some(x) => x,
-                    None => panic!("Unsupported"),
+                    None => return Err(unsupported()),
                 };
 
-                device.borrow_mut().get_camera_stream(stream_to_chromecast, supported_stream_protocols)?;
+                device
+                    .lock_ignore_poison()
+                    .get_camera_stream(stream_to_chromecast, supported_stream_protocols)?;
             }
             CommandType::SelectChannel {
                 channel_code,
@@ -664,38 +833,47 @@ impl<T: GoogleHomeDevice + Send + Sync + Debug + ?Sized + 'static> Device<T> {
             } => {
                 let device = match &mut self.device_traits.channel {
                     Some(x) => x,
-                    None => panic!("Unsupported"),
+                    None => return Err(unsupported()),
                 };
 
                 if let Some(code) = channel_code {
-                    device.borrow_mut().select_channel_by_id(code, channel_name, channel_number)?;
+                    device.lock_ignore_poison().select_channel_by_id(code, channel_name, channel_number)?;
                 } else if let Some(number) = channel_number {
-                    device.borrow_mut().select_channel_by_number(number)?;
+                    device.lock_ignore_poison().select_channel_by_number(number)?;
                 }
             }
             CommandType::RelativeChannel { relative_channel_change } => {
                 let device = match &mut self.device_traits.channel {
                     Some(x) => x,
-                    None => panic!("Unsupported"),
+                    None => return Err(unsupported()),
                 };
 
-                device.borrow_mut().select_channel_relative(relative_channel_change)?;
+                device.lock_ignore_poison().select_channel_relative(relative_channel_change)?;
             }
             CommandType::ReturnChannel => {
                 let device = match &mut self.device_traits.channel {
                     Some(x) => x,
-                    None => panic!("Unsupported"),
+                    None => return Err(unsupported()),
                 };
 
-                device.borrow_mut().return_to_last_channel()?;
+                device.lock_ignore_poison().return_to_last_channel()?;
             }
             CommandType::ColorAbsolute { color } => {
                 let device = match &mut self.device_traits.color_setting {
                     Some(x) => x,
-                    None => panic!("Unsupported"),
+                    None => return Err(unsupported()),
                 };
 
-                device.borrow_mut().set_color(color)?;
+                // The color has to match the color model advertised in SYNC
+                let support = device.lock_ignore_poison().get_color_model_support()?;
+                if !support.supports(&color) {
+                    return Err(CombinedDeviceError::DeviceError(DeviceError::ValueOutOfRange).into());
+                }
+
+                match color {
+                    ColorCommand::Name(name) => device.lock_ignore_poison().set_color_by_name(name)?,
+                    color => device.lock_ignore_poison().set_color(color)?,
+                }
             }
             CommandType::Cook {
                 start,
@@ -706,18 +884,22 @@ impl<T: GoogleHomeDevice + Send + Sync + Debug + ?Sized + 'static> Device<T> {
             } => {
                 let device = match &mut self.device_traits.cook {
                     Some(x) => x,
-                    None => panic!("Unsupported"),
+                    None => return Err(unsupported()),
                 };
 
                 if start {
-                    device.borrow_mut().start(CookingConfig {
+                    let config = CookingConfig {
                         cooking_mode,
                         food_preset,
                         quantity,
                         unit,
-                    })?;
+                    };
+
+                    let mut device = device.lock_ignore_poison();
+                    validate_cooking_config(&*device, &config)?;
+                    device.start(config)?;
                 } else {
-                    device.borrow_mut().stop()?;
+                    device.lock_ignore_poison().stop()?;
                 }
             }
             CommandType::Dispense {
@@ -728,48 +910,63 @@ impl<T: GoogleHomeDevice + Send + Sync + Debug + ?Sized + 'static> Device<T> {
             } => {
                 let device = match &mut self.device_traits.dispense {
                     Some(x) => x,
-                    None => panic!("Unsupported"),
-                };
-
-                if let Some(item) = item {
-                    // Unwraps are safe, specified in Google spec.
-                    // https://developers.google.com/assistant/smarthome/traits/dispense#device-commands
-                    let unit = unit.unwrap();
-                    let amount = amount.unwrap();
-
-                    device.borrow_mut().dispense_amount(item, amount, unit)?;
-                } else if let Some(preset_name) = preset_name {
-                    device.borrow_mut().dispense_preset(preset_name)?;
-                } else {
-                    device.borrow_mut().dispense_default()?;
+                    None => return Err(unsupported()),
+                };
+
+                // `amount` and `unit` are required together with `item`, and can't be dispensed without one.
+                // https://developers.google.com/assistant/smarthome/traits/dispense#device-commands
+                match (item, amount, unit) {
+                    (Some(item), Some(amount), Some(unit)) => device.lock_ignore_poison().dispense_amount(item, amount, unit)?,
+                    (Some(_), _, _) => return Err(ErrorCode::ProtocolError.into()),
+                    (None, Some(_), _) | (None, _, Some(_)) => return Err(DispenseError::Error(dispense::DeviceError::DispenseNotSupported).into()),
+                    (None, None, None) => {
+                        if let Some(preset_name) = preset_name {
+                            device.lock_ignore_poison().dispense_preset(preset_name)?;
+                        } else {
+                            device.lock_ignore_poison().dispense_default()?;
+                        }
+                    }
                 }
             }
             CommandType::Dock => {
                 let device = match &mut self.device_traits.dock {
                     Some(x) => x,
-                    None => panic!("Unsupported"),
+                    None => return Err(unsupported()),
                 };
 
-                device.borrow_mut().dock()?;
+                device.lock_ignore_poison().dock()?;
             }
             CommandType::Charge { charge } => {
                 let device = match &mut self.device_traits.energy_storage {
                     Some(x) => x,
-                    None => panic!("Unsupported"),
+                    None => return Err(unsupported()),
                 };
 
-                device.borrow_mut().charge(charge)?;
+                device.lock_ignore_poison().charge(charge)?;
             }
             CommandType::SetFanSpeed { fan_speed, fan_speed_percent } => {
                 let device = match &mut self.device_traits.fan_speed {
                     Some(x) => x,
-                    None => panic!("Unsupported"),
+                    None => return Err(unsupported()),
                 };
 
+                // The named speed takes precedence over the percentage, see CommandType::SetFanSpeed
                 if let Some(fan_speed) = fan_speed {
-                    device.borrow_mut().set_fan_speed_setting(fan_speed)?;
+                    // Only the speeds advertised in SYNC may be set
+                    let available_fan_speeds = device.lock_ignore_poison().get_available_fan_speeds()?;
+                    match available_fan_speeds {
+                        Some(available) if available.speeds.iter().any(|speed| speed.speed_name == fan_speed) => {}
+                        Some(_) => return Err(CombinedDeviceError::DeviceError(DeviceError::ValueOutOfRange).into()),
+                        None => return Err(CombinedDeviceError::DeviceError(DeviceError::FunctionNotSupported).into()),
+                    }
+
+                    device.lock_ignore_poison().set_fan_speed_setting(fan_speed)?;
                 } else if let Some(fan_speed_percent) = fan_speed_percent {
-                    device.borrow_mut().set_fan_speed_percent(fan_speed_percent)?;
+                    if !(0.0..=100.0).contains(&fan_speed_percent) {
+                        return Err(CombinedDeviceError::DeviceError(DeviceError::ValueOutOfRange).into());
+                    }
+
+                    device.lock_ignore_poison().set_fan_speed_percent(fan_speed_percent)?;
                 }
             }
             CommandType::SetFanSpeedRelative {
@@ -778,22 +975,22 @@ impl<T: GoogleHomeDevice + Send + Sync + Debug + ?Sized + 'static> Device<T> {
             } => {
                 let device = match &mut self.device_traits.fan_speed {
                     Some(x) => x,
-                    None => panic!("Unsupported"),
+                    None => return Err(unsupported()),
                 };
 
                 if let Some(weight) = fan_speed_relative_weight {
-                    device.borrow_mut().set_fan_speed_relative_weight(weight)?;
+                    device.lock_ignore_poison().set_fan_speed_relative_weight(weight)?;
                 } else if let Some(percent) = fan_speed_relative_percent {
-                    device.borrow_mut().set_fan_speed_relative_percent(percent)?;
+                    device.lock_ignore_poison().set_fan_speed_relative_percent(percent)?;
                 }
             }
             CommandType::Reverse => {
                 let device = match &mut self.device_traits.fan_speed {
                     Some(x) => x,
-                    None => panic!("Unsupported"),
+                    None => return Err(unsupported()),
                 };
 
-                device.borrow_mut().set_fan_reverse()?;
+                device.lock_ignore_poison().set_fan_reverse()?;
             }
             CommandType::Fill {
                 fill,
@@ -802,125 +999,156 @@ impl<T: GoogleHomeDevice + Send + Sync + Debug + ?Sized + 'static> Device<T> {
             } => {
                 let device = match &mut self.device_traits.fill {
                     Some(x) => x,
-                    None => panic!("Unsupported"),
+                    None => return Err(unsupported()),
                 };
 
-                if let Some(fill_level) = fill_level {
-                    device.borrow_mut().fill_to_level(fill_level)?;
-                } else if let Some(fill_percent) = fill_percent {
-                    device.borrow_mut().fill_to_percent(fill_percent)?;
-                } else {
-                    device.borrow_mut().fill(fill)?;
+                // Draining ignores the level, the level name takes precedence over the percentage, see CommandType::Fill
+                match (fill, fill_level, fill_percent) {
+                    (false, _, _) => device.lock_ignore_poison().fill(false)?,
+                    (true, Some(fill_level), _) => device.lock_ignore_poison().fill_to_level(fill_level)?,
+                    (true, None, Some(fill_percent)) => device.lock_ignore_poison().fill_to_percent(fill_percent)?,
+                    (true, None, None) => device.lock_ignore_poison().fill(true)?,
+                }
+            }
+            CommandType::SetHumidity { humidity } => {
+                let device = match &mut self.device_traits.humidity_setting {
+                    Some(x) => x,
+                    None => return Err(unsupported()),
+                };
+
+                reject_query_only(device.lock_ignore_poison().is_query_only_humidity_setting())?;
+                device.lock_ignore_poison().set_humidity(humidity)?;
+            }
+            CommandType::HumidityRelative {
+                humidity_relative_percent,
+                humidity_relative_weight,
+            } => {
+                let device = match &mut self.device_traits.humidity_setting {
+                    Some(x) => x,
+                    None => return Err(unsupported()),
+                };
+
+                reject_query_only(device.lock_ignore_poison().is_query_only_humidity_setting())?;
+                if let Some(percent) = humidity_relative_percent {
+                    device.lock_ignore_poison().set_humidity_relative_percent(percent)?;
+                } else if let Some(weight) = humidity_relative_weight {
+                    device.lock_ignore_poison().set_humidity_relative_weight(weight)?;
                 }
             }
             CommandType::SetInput { new_input } => {
                 let device = match &mut self.device_traits.input_selector {
                     Some(x) => x,
-                    None => panic!("Unsupported"),
+                    None => return Err(unsupported()),
                 };
 
-                device.borrow_mut().set_input(new_input)?;
+                device.lock_ignore_poison().set_input(new_input)?;
             }
             CommandType::NextInput => {
                 let device = match &mut self.device_traits.input_selector {
                     Some(x) => x,
-                    None => panic!("Unsupported"),
+                    None => return Err(unsupported()),
                 };
 
-                device.borrow_mut().set_next_input()?;
+                device.lock_ignore_poison().set_next_input()?;
             }
             CommandType::PreviousInput => {
                 let device = match &mut self.device_traits.input_selector {
                     Some(x) => x,
-                    None => panic!("Unsupported"),
+                    None => return Err(unsupported()),
                 };
 
-                device.borrow_mut().set_previous_input()?;
+                device.lock_ignore_poison().set_previous_input()?;
             }
             CommandType::ColorLoop { duration } => {
                 let device = match &mut self.device_traits.light_effects {
                     Some(x) => x,
-                    None => panic!("Unsupported"),
+                    None => return Err(unsupported()),
                 };
 
-                device.borrow_mut().set_color_loop(duration)?;
+                device.lock_ignore_poison().set_color_loop(duration)?;
             }
             CommandType::Sleep { duration } => {
                 let device = match &mut self.device_traits.light_effects {
                     Some(x) => x,
-                    None => panic!("Unsupported"),
+                    None => return Err(unsupported()),
                 };
 
-                device.borrow_mut().set_sleep(duration)?;
+                device.lock_ignore_poison().set_sleep(duration)?;
             }
             CommandType::StopEffect => {
                 let device = match &mut self.device_traits.light_effects {
                     Some(x) => x,
-                    None => panic!("Unsupported"),
+                    None => return Err(unsupported()),
                 };
 
-                device.borrow_mut().stop_effect()?;
+                device.lock_ignore_poison().stop_effect()?;
             }
             CommandType::Wake { duration } => {
                 let device = match &mut self.device_traits.light_effects {
                     Some(x) => x,
-                    None => panic!("Unsupported"),
+                    None => return Err(unsupported()),
                 };
 
-                device.borrow_mut().set_wake(duration)?;
+                device.lock_ignore_poison().set_wake(duration)?;
             }
             CommandType::Locate { silence, lang } => {
                 let device = match &mut self.device_traits.locator {
                     Some(x) => x,
-                    None => panic!("Unsupported"),
+                    None => return Err(unsupported()),
                 };
 
-                device.borrow_mut().locate(Some(silence), Some(lang))?;
+                device.lock_ignore_poison().locate(silence, lang)?;
             }
             CommandType::LockUnlock { lock, .. } => {
                 let device = match &mut self.device_traits.lock_unlock {
                     Some(x) => x,
-                    None => panic!("Unsupported"),
+                    None => return Err(unsupported()),
                 };
 
-                device.borrow_mut().set_locked(lock)?;
+                device.lock_ignore_poison().set_locked(lock)?;
 
-                state.lock = Some(device.borrow().is_locked()?);
+                state = Some(TraitCommandState::LockUnlock(LockUnlockCommandState {
+                    is_locked: device.lock_ignore_poison().is_locked()?,
+                }));
             }
             CommandType::SetModes { update_mode_settings } => {
                 let device = match &mut self.device_traits.modes {
                     Some(x) => x,
-                    None => panic!("Unsupported"),
+                    None => return Err(unsupported()),
                 };
 
+                reject_query_only(device.lock_ignore_poison().is_query_only_modes())?;
                 for (mode_name, setting_name) in update_mode_settings {
-                    device.borrow_mut().update_mode(mode_name, setting_name)?;
+                    device.lock_ignore_poison().update_mode(mode_name, setting_name)?;
                 }
             }
             CommandType::EnableDisableGuestNetwork { enable } => {
                 let device = match &mut self.device_traits.network_control {
                     Some(x) => x,
-                    None => panic!("Unsupported"),
+                    None => return Err(unsupported()),
                 };
 
-                device.borrow_mut().set_guest_network_enabled(enable)?;
+                device.lock_ignore_poison().set_guest_network_enabled(enable)?;
             }
             CommandType::EnableDisableNetworkProfile { enable, profile } => {
                 let device = match &mut self.device_traits.network_control {
                     Some(x) => x,
-                    None => panic!("Unsupported"),
+                    None => return Err(unsupported()),
                 };
 
-                device.borrow_mut().set_network_profile_enabled(profile, enable)?;
+                device.lock_ignore_poison().set_network_profile_enabled(profile, enable)?;
             }
             CommandType::GetGuestNetworkPassword => {
                 let device = match &mut self.device_traits.network_control {
                     Some(x) => x,
-                    None => panic!("Unsupported"),
+                    None => return Err(unsupported()),
                 };
 
-                let password = device.borrow_mut().get_guest_network_password()?;
-                state.guest_network_password = Some(password)
+                let password = device.lock_ignore_poison().get_guest_network_password()?;
+                state = Some(TraitCommandState::NetworkControl(NetworkControlCommandState {
+                    guest_network_password: Some(password),
+                    ..Default::default()
+                }));
             }
             CommandType::TestNetworkSpeed {
                 test_upload_speed,
@@ -929,26 +1157,58 @@ impl<T: GoogleHomeDevice + Send + Sync + Debug + ?Sized + 'static> Device<T> {
             } => {
                 let device = match &mut self.device_traits.network_control {
                     Some(x) => x,
-                    None => panic!("Unsupported"),
+                    None => return Err(unsupported()),
                 };
 
-                device.borrow_mut().test_network_speed(test_download_speed, test_upload_speed)?;
+                let mut device = device.lock_ignore_poison();
+                device.test_network_speed(test_download_speed, test_upload_speed)?;
+
+                // The test may have finished already, report the latest results.
+                // The test was started successfully, so results which are not available (yet) are left out instead of failing the command
+                let mut network_state = NetworkControlCommandState::default();
+                if test_download_speed {
+                    network_state.last_network_download_speed_test = device.get_last_network_download_speed_test().ok();
+                }
+                if test_upload_speed {
+                    network_state.last_network_upload_speed_test = device.get_last_network_upload_speed_test().ok();
+                }
+                network_state.network_speed_test_in_progress = device.is_network_speed_test_in_progress().ok().flatten();
+                state = Some(TraitCommandState::NetworkControl(network_state));
             }
             CommandType::OnOff { on } => {
                 let device = match &mut self.device_traits.on_off {
                     Some(x) => x,
-                    None => panic!("Unsupported"),
+                    None => return Err(unsupported()),
                 };
 
-                device.borrow_mut().set_on(on)?;
+                reject_query_only(device.lock_ignore_poison().is_query_only())?;
+                device.lock_ignore_poison().set_on(on)?;
+
+                // Command-only devices can't report their state
+                if !is_command_only(device.lock_ignore_poison().is_command_only())? {
+                    state = Some(TraitCommandState::OnOff(OnOffCommandState {
+                        on: device.lock_ignore_poison().is_on()?,
+                    }));
+                }
             }
             CommandType::OpenClose { open_percent, open_direction } => {
                 let device = match &mut self.device_traits.open_close {
                     Some(x) => x,
-                    None => panic!("Unsupported"),
+                    None => return Err(unsupported()),
                 };
 
-                device.borrow_mut().set_open(open_percent, open_direction)?;
+                reject_query_only(device.lock_ignore_poison().is_query_only_open_close())?;
+                // Discrete devices can only be fully opened or fully closed
+                if device.lock_ignore_poison().is_discrete_only_open_close()?.unwrap_or(false) && open_percent != 0.0 && open_percent != 100.0 {
+                    return Err(CombinedDeviceError::DeviceError(DeviceError::ValueOutOfRange).into());
+                }
+
+                device.lock_ignore_poison().set_open(open_percent, open_direction)?;
+                let device = device.lock_ignore_poison();
+                state = Some(TraitCommandState::OpenClose(OpenCloseCommandState {
+                    open_percent: device.get_open_percent()?,
+                    open_state: device.get_open_state()?,
+                }));
             }
             CommandType::OpenCloseRelative {
                 open_relative_percent,
@@ -956,18 +1216,24 @@ impl<T: GoogleHomeDevice + Send + Sync + Debug + ?Sized + 'static> Device<T> {
             } => {
                 let device = match &mut self.device_traits.open_close {
                     Some(x) => x,
-                    None => panic!("Unsupported"),
+                    None => return Err(unsupported()),
                 };
 
-                device.borrow_mut().set_open_relative(open_relative_percent, open_direction)?;
+                reject_query_only(device.lock_ignore_poison().is_query_only_open_close())?;
+                device.lock_ignore_poison().set_open_relative(open_relative_percent, open_direction)?;
+                let device = device.lock_ignore_poison();
+                state = Some(TraitCommandState::OpenClose(OpenCloseCommandState {
+                    open_percent: device.get_open_percent()?,
+                    open_state: device.get_open_state()?,
+                }));
             }
             CommandType::Reboot => {
                 let device = match &mut self.device_traits.reboot {
                     Some(x) => x,
-                    None => panic!("Unsupported"),
+                    None => return Err(unsupported()),
                 };
 
-                device.borrow_mut().reboot()?;
+                device.lock_ignore_poison().reboot()?;
             }
             CommandType::RotationAbsolute {
                 rotation_degrees,
@@ -975,70 +1241,78 @@ impl<T: GoogleHomeDevice + Send + Sync + Debug + ?Sized + 'static> Device<T> {
             } => {
                 let device = match &mut self.device_traits.rotation {
                     Some(x) => x,
-                    None => panic!("Unsupported"),
+                    None => return Err(unsupported()),
                 };
 
+                // Degrees take precedence over the percentage, see CommandType::RotationAbsolute
                 if let Some(deg) = rotation_degrees {
-                    device.borrow_mut().set_rotation_degrees(deg)?;
+                    device.lock_ignore_poison().set_rotation_degrees(deg)?;
                 } else if let Some(per) = rotation_percent {
-                    device.borrow_mut().set_rotation_percent(per)?;
+                    device.lock_ignore_poison().set_rotation_percent(per)?;
                 }
             }
             CommandType::ActivateScene { deactivate } => {
                 let device = match &mut self.device_traits.scene {
                     Some(x) => x,
-                    None => panic!("Unsupported"),
+                    None => return Err(unsupported()),
                 };
 
                 if deactivate {
-                    device.borrow_mut().deactivate()?;
+                    // Only reversible scenes can be deactivated
+                    if !device.lock_ignore_poison().is_reversible()?.unwrap_or(false) {
+                        return Err(ErrorCode::NotSupported.into());
+                    }
+
+                    device.lock_ignore_poison().deactivate()?;
                 } else {
-                    device.borrow_mut().activate()?;
+                    device.lock_ignore_poison().activate()?;
                 }
             }
             CommandType::SoftwareUpdate => {
                 let device = match &mut self.device_traits.software_update {
                     Some(x) => x,
-                    None => panic!("Unsupported"),
+                    None => return Err(unsupported()),
                 };
 
-                device.borrow_mut().perform_update()?;
+                device.lock_ignore_poison().perform_update()?;
             }
             CommandType::StartStop { start, zone, multiple_zones } => {
                 let device = match &mut self.device_traits.start_stop {
                     Some(x) => x,
-                    None => panic!("Unsupported"),
+                    None => return Err(unsupported()),
                 };
 
                 let zones = if let Some(zone) = zone { Some(vec![zone]) } else { multiple_zones };
 
-                device.borrow_mut().start_stop(start, zones)?;
+                device.lock_ignore_poison().start_stop(start, zones)?;
             }
             CommandType::PauseUnpause { pause } => {
                 let device = match &mut self.device_traits.start_stop {
                     Some(x) => x,
-                    None => panic!("Unsupported"),
+                    None => return Err(unsupported()),
                 };
 
-                device.borrow_mut().pause_unpause(pause)?;
+                device.lock_ignore_poison().pause_unpause(pause)?;
             }
             CommandType::SetTemperature { temperature } => {
                 let device = match &mut self.device_traits.temperature_control {
                     Some(x) => x,
-                    None => panic!("Unsupported"),
+                    None => return Err(unsupported()),
                 };
 
-                device.borrow_mut().set_temperature(temperature)?;
+                reject_query_only(device.lock_ignore_poison().is_query_only_temperature_control())?;
+                device.lock_ignore_poison().set_temperature(temperature)?;
             }
             CommandType::ThermostatTemperatureSetpoint {
                 thermostat_temperature_setpoint,
             } => {
                 let device = match &mut self.device_traits.temperature_setting {
                     Some(x) => x,
-                    None => panic!("Unsupported"),
+                    None => return Err(unsupported()),
                 };
 
-                device.borrow_mut().set_temperature_setpoint(thermostat_temperature_setpoint)?
+                reject_query_only(device.lock_ignore_poison().is_query_only_temperature_setting())?;
+                device.lock_ignore_poison().set_temperature_setpoint(thermostat_temperature_setpoint)?
             }
             CommandType::ThermostatTemperatureSetRange {
                 thermostat_temperature_setpoint_high,
@@ -1046,20 +1320,39 @@ impl<T: GoogleHomeDevice + Send + Sync + Debug + ?Sized + 'static> Device<T> {
             } => {
                 let device = match &mut self.device_traits.temperature_setting {
                     Some(x) => x,
-                    None => panic!("Unsupported"),
+                    None => return Err(unsupported()),
                 };
 
+                reject_query_only(device.lock_ignore_poison().is_query_only_temperature_setting())?;
+                if !device
+                    .lock_ignore_poison()
+                    .get_available_thermostat_modes()?
+                    .contains(&ThermostatMode::Heatcool)
+                {
+                    return Err(ErrorCode::NotSupported.into());
+                }
+
+                if thermostat_temperature_setpoint_low > thermostat_temperature_setpoint_high {
+                    return Err(CombinedDeviceError::DeviceError(DeviceError::ValueOutOfRange).into());
+                }
+
+                let buffer_range = device.lock_ignore_poison().get_buffer_range_celsius()?.unwrap_or(2.0);
+                if thermostat_temperature_setpoint_high - thermostat_temperature_setpoint_low < buffer_range {
+                    return Err(CombinedDeviceError::DeviceError(DeviceError::RangeTooClose).into());
+                }
+
                 device
-                    .borrow_mut()
+                    .lock_ignore_poison()
                     .set_temperature_set_range(thermostat_temperature_setpoint_high, thermostat_temperature_setpoint_low)?;
             }
             CommandType::ThermostatSetMode { thermostat_mode } => {
                 let device = match &mut self.device_traits.temperature_setting {
                     Some(x) => x,
-                    None => panic!("Unsupported"),
+                    None => return Err(unsupported()),
                 };
 
-                device.borrow_mut().set_thermostat_mode(thermostat_mode)?;
+                reject_query_only(device.lock_ignore_poison().is_query_only_temperature_setting())?;
+                device.lock_ignore_poison().set_thermostat_mode(thermostat_mode)?;
             }
             CommandType::TemperatureRelative {
                 thermostat_temperature_relative_degree,
@@ -1067,138 +1360,140 @@ impl<T: GoogleHomeDevice + Send + Sync + Debug + ?Sized + 'static> Device<T> {
             } => {
                 let device = match &mut self.device_traits.temperature_setting {
                     Some(x) => x,
-                    None => panic!("Unsupported"),
+                    None => return Err(unsupported()),
                 };
 
+                reject_query_only(device.lock_ignore_poison().is_query_only_temperature_setting())?;
                 if let Some(t) = thermostat_temperature_relative_degree {
-                    device.borrow_mut().set_temperature_relative_degree(t)?;
+                    device.lock_ignore_poison().set_temperature_relative_degree(t)?;
                 }
 
                 if let Some(w) = thermostat_temperature_relative_weight {
-                    device.borrow_mut().set_temperature_relative_weight(w)?;
+                    device.lock_ignore_poison().set_temperature_relative_weight(w)?;
                 }
             }
             CommandType::TimerStart { timer_time_sec } => {
                 let device = match &mut self.device_traits.timer {
                     Some(x) => x,
-                    None => panic!("Unsupported"),
+                    None => return Err(unsupported()),
                 };
 
-                device.borrow_mut().start_timer(timer_time_sec)?;
+                device.lock_ignore_poison().start_timer(timer_time_sec)?;
             }
             CommandType::TimerAdjust { timer_time_sec } => {
                 let device = match &mut self.device_traits.timer {
                     Some(x) => x,
-                    None => panic!("Unsupported"),
+                    None => return Err(unsupported()),
                 };
 
-                device.borrow_mut().adjust_timer(timer_time_sec)?;
+                device.lock_ignore_poison().adjust_timer(timer_time_sec)?;
             }
             CommandType::TimerPause => {
                 let device = match &mut self.device_traits.timer {
                     Some(x) => x,
-                    None => panic!("Unsupported"),
+                    None => return Err(unsupported()),
                 };
 
-                device.borrow_mut().pause_timer()?;
+                device.lock_ignore_poison().pause_timer()?;
             }
             CommandType::TimerResume => {
                 let device = match &mut self.device_traits.timer {
                     Some(x) => x,
-                    None => panic!("Unsupported"),
+                    None => return Err(unsupported()),
                 };
 
-                device.borrow_mut().resume_timer()?;
+                device.lock_ignore_poison().resume_timer()?;
             }
             CommandType::TimerCancel => {
                 let device = match &mut self.device_traits.timer {
                     Some(x) => x,
-                    None => panic!("Unsupported"),
+                    None => return Err(unsupported()),
                 };
 
-                device.borrow_mut().cancel_timer()?;
+                device.lock_ignore_poison().cancel_timer()?;
             }
             CommandType::SetToggles { update_toggle_settings } => {
                 let device = match &mut self.device_traits.toggles {
                     Some(x) => x,
-                    None => panic!("Unsupported"),
+                    None => return Err(unsupported()),
                 };
 
+                reject_query_only(device.lock_ignore_poison().is_query_only_toggles())?;
                 for (k, v) in update_toggle_settings {
-                    device.borrow_mut().set_toggle(k, v)?;
+                    device.lock_ignore_poison().set_toggle(k, v)?;
                 }
             }
             CommandType::MediaStop => {
                 let device = match &mut self.device_traits.transport_control {
                     Some(x) => x,
-                    None => panic!("Unsupported"),
+                    None => return Err(unsupported()),
                 };
 
-                device.borrow_mut().media_stop()?;
+                device.lock_ignore_poison().media_stop()?;
             }
             CommandType::MediaNext => {
                 let device = match &mut self.device_traits.transport_control {
                     Some(x) => x,
-                    None => panic!("Unsupported"),
+                    None => return Err(unsupported()),
                 };
 
-                device.borrow_mut().media_next()?;
+                device.lock_ignore_poison().media_next()?;
             }
             CommandType::MediaPrevious => {
                 let device = match &mut self.device_traits.transport_control {
                     Some(x) => x,
-                    None => panic!("Unsupported"),
+                    None => return Err(unsupported()),
                 };
 
-                device.borrow_mut().media_previous()?;
+                device.lock_ignore_poison().media_previous()?;
             }
             CommandType::MediaPause => {
                 let device = match &mut self.device_traits.transport_control {
                     Some(x) => x,
-                    None => panic!("Unsupported"),
+                    None => return Err(unsupported()),
                 };
 
-                device.borrow_mut().media_pause()?;
+                device.lock_ignore_poison().media_pause()?;
             }
             CommandType::MediaResume => {
                 let device = match &mut self.device_traits.transport_control {
                     Some(x) => x,
-                    None => panic!("Unsupported"),
+                    None => return Err(unsupported()),
                 };
 
-                device.borrow_mut().media_resume()?;
+                device.lock_ignore_poison().media_resume()?;
             }
             CommandType::MediaSeekRelative { relative_position_ms } => {
                 let device = match &mut self.device_traits.transport_control {
                     Some(x) => x,
-                    None => panic!("Unsupported"),
+                    None => return Err(unsupported()),
                 };
 
-                device.borrow_mut().media_seek_relative(relative_position_ms)?;
+                device.lock_ignore_poison().media_seek_relative(relative_position_ms)?;
             }
             CommandType::MediaSeekToPosition { abs_position_ms } => {
                 let device = match &mut self.device_traits.transport_control {
                     Some(x) => x,
-                    None => panic!("Unsupported"),
+                    None => return Err(unsupported()),
                 };
 
-                device.borrow_mut().media_seek_to_position(abs_position_ms)?;
+                device.lock_ignore_poison().media_seek_to_position(abs_position_ms)?;
             }
             CommandType::MediaRepeatMode { is_on, is_single } => {
                 let device = match &mut self.device_traits.transport_control {
                     Some(x) => x,
-                    None => panic!("Unsupported"),
+                    None => return Err(unsupported()),
                 };
 
-                device.borrow_mut().media_repeat_mode(is_on, is_single.unwrap_or(false))?;
+                device.lock_ignore_poison().media_repeat_mode(is_on, is_single.unwrap_or(false))?;
             }
             CommandType::MediaShuffle => {
                 let device = match &mut self.device_traits.transport_control {
                     Some(x) => x,
-                    None => panic!("Unsupported"),
+                    None => return Err(unsupported()),
                 };
 
-                device.borrow_mut().media_shuffle()?;
+                device.lock_ignore_poison().media_shuffle()?;
             }
             CommandType::MediaClosedCaptioningOn {
                 closed_captioning_language,
@@ -1206,46 +1501,74 @@ impl<T: GoogleHomeDevice + Send + Sync + Debug + ?Sized + 'static> Device<T> {
             } => {
                 let device = match &mut self.device_traits.transport_control {
                     Some(x) => x,
-                    None => panic!("Unsupported"),
+                    None => return Err(unsupported()),
                 };
 
                 device
-                    .borrow_mut()
+                    .lock_ignore_poison()
                     .media_closed_captioning_on(closed_captioning_language, user_query_language)?;
             }
             CommandType::MediaClosedCaptioningOff => {
                 let device = match &mut self.device_traits.transport_control {
                     Some(x) => x,
-                    None => panic!("Unsupported"),
+                    None => return Err(unsupported()),
                 };
 
-                device.borrow_mut().media_closed_captioning_off()?;
+                device.lock_ignore_poison().media_closed_captioning_off()?;
             }
             CommandType::Mute { mute } => {
                 let device = match &mut self.device_traits.volume {
                     Some(x) => x,
-                    None => panic!("Unsupported"),
+                    None => return Err(unsupported()),
                 };
 
-                device.borrow_mut().mute(mute)?;
+                device.lock_ignore_poison().mute(mute)?;
+                state = Some(TraitCommandState::Volume(volume_state(&*device.lock_ignore_poison())?));
             }
             CommandType::SetVolume { volume_level } => {
                 let device = match &mut self.device_traits.volume {
                     Some(x) => x,
-                    None => panic!("Unsupported"),
+                    None => return Err(unsupported()),
                 };
 
-                device.borrow_mut().set_volume(volume_level)?;
+                let volume_max_level = device.lock_ignore_poison().get_volume_max_level()?;
+                if !(0..=volume_max_level).contains(&volume_level) {
+                    return Err(CombinedDeviceError::DeviceError(DeviceError::ValueOutOfRange).into());
+                }
+
+                device.lock_ignore_poison().set_volume(volume_level)?;
+                state = Some(TraitCommandState::Volume(volume_state(&*device.lock_ignore_poison())?));
             }
             CommandType::VolumeRelative { relative_steps } => {
                 let device = match &mut self.device_traits.volume {
                     Some(x) => x,
-                    None => panic!("Unsupported"),
+                    None => return Err(unsupported()),
+                };
+
+                let mut device = device.lock_ignore_poison();
+
+                // Changing the volume of a muted device unmutes it, like the volume buttons on a remote would
+                if device.is_muted()? == Some(true) {
+                    device.mute(false)?;
+                }
+
+                // Stop at the bounds of the volume range, rather than asking the device to go past them.
+                // Command only devices don't know their volume, they get the steps as requested.
+                let relative_steps = match device.get_current_volume()? {
+                    Some(current_volume) => {
+                        let volume_max_level = device.get_volume_max_level()?;
+                        (current_volume + relative_steps).clamp(0, volume_max_level) - current_volume
+                    }
+                    None => relative_steps,
                 };
 
-                device.borrow_mut().set_volume_relative(relative_steps)?;
+                if relative_steps != 0 {
+                    device.set_volume_relative(relative_steps)?;
+                }
+
+                state = Some(TraitCommandState::Volume(volume_state(&*device)?));
             }
-            _ => {}
+            CommandType::Unknown { .. } => return Err(ErrorCode::CommandDispatchFailed.into()),
         }
         Ok(state)
     }
@@ -1256,7 +1579,7 @@ impl<T: GoogleHomeDevice + Send + Sync + Debug + ?Sized + 'static> Device<T> {
         T: AppSelector + Sized,
     {
         self.device_traits.app_selector = Some(self.inner.clone());
-        self.traits.push(Trait::AppSelector);
+        self.push_trait(Trait::AppSelector);
     }
 
     /// Register the [ArmDisarm] trait
@@ -1265,7 +1588,7 @@ impl<T: GoogleHomeDevice + Send + Sync + Debug + ?Sized + 'static> Device<T> {
         T: ArmDisarm + Sized,
     {
         self.device_traits.arm_disarm = Some(self.inner.clone());
-        self.traits.push(Trait::ArmDisarm);
+        self.push_trait(Trait::ArmDisarm);
     }
 
     /// Register the [Brightness] trait
@@ -1274,7 +1597,7 @@ impl<T: GoogleHomeDevice + Send + Sync + Debug + ?Sized + 'static> Device<T> {
         T: Brightness + Sized,
     {
         self.device_traits.brightness = Some(self.inner.clone());
-        self.traits.push(Trait::Brightness);
+        self.push_trait(Trait::Brightness);
     }
 
     /// Register the [CameraStream] trait
@@ -1283,7 +1606,7 @@ impl<T: GoogleHomeDevice + Send + Sync + Debug + ?Sized + 'static> Device<T> {
         T: CameraStream + Sized,
     {
         self.device_traits.camera_stream = Some(self.inner.clone());
-        self.traits.push(Trait::CameraStream);
+        self.push_trait(Trait::CameraStream);
     }
 
     /// Register the [Channel] trait
@@ -1292,7 +1615,7 @@ impl<T: GoogleHomeDevice + Send + Sync + Debug + ?Sized + 'static> Device<T> {
         T: Channel + Sized,
     {
         self.device_traits.channel = Some(self.inner.clone());
-        self.traits.push(Trait::Channel);
+        self.push_trait(Trait::Channel);
     }
 
     /// Register the [ColorSetting] trait
@@ -1301,7 +1624,7 @@ impl<T: GoogleHomeDevice + Send + Sync + Debug + ?Sized + 'static> Device<T> {
         T: ColorSetting + Sized,
     {
         self.device_traits.color_setting = Some(self.inner.clone());
-        self.traits.push(Trait::ColorSetting);
+        self.push_trait(Trait::ColorSetting);
     }
 
     /// Register the [Cook] trait
@@ -1310,7 +1633,7 @@ impl<T: GoogleHomeDevice + Send + Sync + Debug + ?Sized + 'static> Device<T> {
         T: Cook + Sized,
     {
         self.device_traits.cook = Some(self.inner.clone());
-        self.traits.push(Trait::Cook);
+        self.push_trait(Trait::Cook);
     }
 
     /// Register the [Dispense] trait
@@ -1319,7 +1642,7 @@ impl<T: GoogleHomeDevice + Send + Sync + Debug + ?Sized + 'static> Device<T> {
         T: Dispense + Sized,
     {
         self.device_traits.dispense = Some(self.inner.clone());
-        self.traits.push(Trait::Dispense);
+        self.push_trait(Trait::Dispense);
     }
 
     /// Register the [Dock] trait
@@ -1328,7 +1651,7 @@ impl<T: GoogleHomeDevice + Send + Sync + Debug + ?Sized + 'static> Device<T> {
         T: Dock + Sized,
     {
         self.device_traits.dock = Some(self.inner.clone());
-        self.traits.push(Trait::Dock);
+        self.push_trait(Trait::Dock);
     }
 
     /// Register the [EnergyStorage] trait
@@ -1337,7 +1660,7 @@ impl<T: GoogleHomeDevice + Send + Sync + Debug + ?Sized + 'static> Device<T> {
         T: EnergyStorage + Sized,
     {
         self.device_traits.energy_storage = Some(self.inner.clone());
-        self.traits.push(Trait::EnergyStorage);
+        self.push_trait(Trait::EnergyStorage);
     }
 
     /// Register the [FanSpeed] trait
@@ -1346,7 +1669,25 @@ impl<T: GoogleHomeDevice + Send + Sync + Debug + ?Sized + 'static> Device<T> {
         T: FanSpeed + Sized,
     {
         self.device_traits.fan_speed = Some(self.inner.clone());
-        self.traits.push(Trait::FanSpeed);
+        self.push_trait(Trait::FanSpeed);
+    }
+
+    /// Register the [Fill] trait
+    pub fn set_fill(&mut self)
+    where
+        T: Fill + Sized,
+    {
+        self.device_traits.fill = Some(self.inner.clone());
+        self.push_trait(Trait::Fill);
+    }
+
+    /// Register the [HumiditySetting] trait
+    pub fn set_humidity_setting(&mut self)
+    where
+        T: HumiditySetting + Sized,
+    {
+        self.device_traits.humidity_setting = Some(self.inner.clone());
+        self.push_trait(Trait::HumiditySetting);
     }
 
     /// Register the [InputSelector] trait
@@ -1355,7 +1696,7 @@ impl<T: GoogleHomeDevice + Send + Sync + Debug + ?Sized + 'static> Device<T> {
         T: InputSelector + Sized,
     {
         self.device_traits.input_selector = Some(self.inner.clone());
-        self.traits.push(Trait::InputSelector);
+        self.push_trait(Trait::InputSelector);
     }
 
     /// Register the [LightEffects] trait
@@ -1364,7 +1705,7 @@ impl<T: GoogleHomeDevice + Send + Sync + Debug + ?Sized + 'static> Device<T> {
         T: LightEffects + Sized,
     {
         self.device_traits.light_effects = Some(self.inner.clone());
-        self.traits.push(Trait::LightEffects);
+        self.push_trait(Trait::LightEffects);
     }
 
     /// Register the [Locator] trait
@@ -1373,7 +1714,7 @@ impl<T: GoogleHomeDevice + Send + Sync + Debug + ?Sized + 'static> Device<T> {
         T: Locator + Sized,
     {
         self.device_traits.locator = Some(self.inner.clone());
-        self.traits.push(Trait::Locator);
+        self.push_trait(Trait::Locator);
     }
 
     /// Register the [LockUnlock] trait
@@ -1382,7 +1723,7 @@ impl<T: GoogleHomeDevice + Send + Sync + Debug + ?Sized + 'static> Device<T> {
         T: LockUnlock + Sized,
     {
         self.device_traits.lock_unlock = Some(self.inner.clone());
-        self.traits.push(Trait::LockUnlock);
+        self.push_trait(Trait::LockUnlock);
     }
 
     /// Register the [MediaState] trait
@@ -1391,7 +1732,7 @@ impl<T: GoogleHomeDevice + Send + Sync + Debug + ?Sized + 'static> Device<T> {
         T: MediaState + Sized,
     {
         self.device_traits.media_state = Some(self.inner.clone());
-        self.traits.push(Trait::MediaState);
+        self.push_trait(Trait::MediaState);
     }
 
     /// Register the [Modes] trait
@@ -1400,7 +1741,7 @@ impl<T: GoogleHomeDevice + Send + Sync + Debug + ?Sized + 'static> Device<T> {
         T: Modes + Sized,
     {
         self.device_traits.modes = Some(self.inner.clone());
-        self.traits.push(Trait::Modes);
+        self.push_trait(Trait::Modes);
     }
 
     /// Register the [NetworkControl] trait
@@ -1409,7 +1750,7 @@ impl<T: GoogleHomeDevice + Send + Sync + Debug + ?Sized + 'static> Device<T> {
         T: NetworkControl + Sized,
     {
         self.device_traits.network_control = Some(self.inner.clone());
-        self.traits.push(Trait::NetworkControl);
+        self.push_trait(Trait::NetworkControl);
     }
 
     /// Register the [OnOff] trait
@@ -1418,7 +1759,7 @@ impl<T: GoogleHomeDevice + Send + Sync + Debug + ?Sized + 'static> Device<T> {
         T: OnOff + Sized,
     {
         self.device_traits.on_off = Some(self.inner.clone());
-        self.traits.push(Trait::OnOff);
+        self.push_trait(Trait::OnOff);
     }
 
     /// Register the [OpenClose] trait
@@ -1427,7 +1768,7 @@ impl<T: GoogleHomeDevice + Send + Sync + Debug + ?Sized + 'static> Device<T> {
         T: OpenClose + Sized,
     {
         self.device_traits.open_close = Some(self.inner.clone());
-        self.traits.push(Trait::OpenClose);
+        self.push_trait(Trait::OpenClose);
     }
 
     /// Register the [Reboot] trait
@@ -1436,7 +1777,7 @@ impl<T: GoogleHomeDevice + Send + Sync + Debug + ?Sized + 'static> Device<T> {
         T: Reboot + Sized,
     {
         self.device_traits.reboot = Some(self.inner.clone());
-        self.traits.push(Trait::Reboot);
+        self.push_trait(Trait::Reboot);
     }
 
     /// Register the [Rotation] trait
@@ -1445,7 +1786,7 @@ impl<T: GoogleHomeDevice + Send + Sync + Debug + ?Sized + 'static> Device<T> {
         T: Rotation + Sized,
     {
         self.device_traits.rotation = Some(self.inner.clone());
-        self.traits.push(Trait::Rotation);
+        self.push_trait(Trait::Rotation);
     }
 
     /// Register the [RunCycle] trait
@@ -1454,7 +1795,7 @@ impl<T: GoogleHomeDevice + Send + Sync + Debug + ?Sized + 'static> Device<T> {
         T: RunCycle + Sized,
     {
         self.device_traits.run_cycle = Some(self.inner.clone());
-        self.traits.push(Trait::RunCycle);
+        self.push_trait(Trait::RunCycle);
     }
 
     /// Register the [Scene] trait
@@ -1463,7 +1804,7 @@ impl<T: GoogleHomeDevice + Send + Sync + Debug + ?Sized + 'static> Device<T> {
         T: Scene + Sized,
     {
         self.device_traits.scene = Some(self.inner.clone());
-        self.traits.push(Trait::Scene);
+        self.push_trait(Trait::Scene);
     }
 
     /// Register the [SensorState] trait
@@ -1472,7 +1813,7 @@ impl<T: GoogleHomeDevice + Send + Sync + Debug + ?Sized + 'static> Device<T> {
         T: SensorState + Sized,
     {
         self.device_traits.sensor_state = Some(self.inner.clone());
-        self.traits.push(Trait::SensorState);
+        self.push_trait(Trait::SensorState);
     }
 
     /// Register the [SoftwareUpdate] trait
@@ -1481,7 +1822,7 @@ impl<T: GoogleHomeDevice + Send + Sync + Debug + ?Sized + 'static> Device<T> {
         T: SoftwareUpdate + Sized,
     {
         self.device_traits.software_update = Some(self.inner.clone());
-        self.traits.push(Trait::SoftwareUpdate);
+        self.push_trait(Trait::SoftwareUpdate);
     }
 
     /// Register the [StartStop] trait
@@ -1490,7 +1831,7 @@ impl<T: GoogleHomeDevice + Send + Sync + Debug + ?Sized + 'static> Device<T> {
         T: StartStop + Sized,
     {
         self.device_traits.start_stop = Some(self.inner.clone());
-        self.traits.push(Trait::StartStop);
+        self.push_trait(Trait::StartStop);
     }
 
     /// Register the [StatusReport] trait
@@ -1499,7 +1840,7 @@ impl<T: GoogleHomeDevice + Send + Sync + Debug + ?Sized + 'static> Device<T> {
         T: StatusReport + Sized,
     {
         self.device_traits.status_report = Some(self.inner.clone());
-        self.traits.push(Trait::StatusReport);
+        self.push_trait(Trait::StatusReport);
     }
 
     /// Register the [TemperatureControl] trait
@@ -1508,7 +1849,7 @@ impl<T: GoogleHomeDevice + Send + Sync + Debug + ?Sized + 'static> Device<T> {
         T: TemperatureControl + Sized,
     {
         self.device_traits.temperature_control = Some(self.inner.clone());
-        self.traits.push(Trait::TemperatureControl);
+        self.push_trait(Trait::TemperatureControl);
     }
 
     /// Register the [TemperatureSetting] trait
@@ -1517,7 +1858,7 @@ impl<T: GoogleHomeDevice + Send + Sync + Debug + ?Sized + 'static> Device<T> {
         T: TemperatureSetting + Sized,
     {
         self.device_traits.temperature_setting = Some(self.inner.clone());
-        self.traits.push(Trait::TemperatureSetting);
+        self.push_trait(Trait::TemperatureSetting);
     }
 
     /// Register the [Timer] trait
@@ -1526,7 +1867,7 @@ impl<T: GoogleHomeDevice + Send + Sync + Debug + ?Sized + 'static> Device<T> {
         T: Timer + Sized,
     {
         self.device_traits.timer = Some(self.inner.clone());
-        self.traits.push(Trait::Timer);
+        self.push_trait(Trait::Timer);
     }
 
     /// Register the [Toggles] trait
@@ -1535,7 +1876,7 @@ impl<T: GoogleHomeDevice + Send + Sync + Debug + ?Sized + 'static> Device<T> {
         T: Toggles + Sized,
     {
         self.device_traits.toggles = Some(self.inner.clone());
-        self.traits.push(Trait::Toggles);
+        self.push_trait(Trait::Toggles);
     }
 
     /// Register the [TransportControl] trait
@@ -1544,7 +1885,7 @@ impl<T: GoogleHomeDevice + Send + Sync + Debug + ?Sized + 'static> Device<T> {
         T: TransportControl + Sized,
     {
         self.device_traits.transport_control = Some(self.inner.clone());
-        self.traits.push(Trait::TransportControl)
+        self.push_trait(Trait::TransportControl)
     }
 
     /// Register the [Volume] trait
@@ -1553,10 +1894,386 @@ impl<T: GoogleHomeDevice + Send + Sync + Debug + ?Sized + 'static> Device<T> {
         T: Volume + Sized,
     {
         self.device_traits.volume = Some(self.inner.clone());
-        self.traits.push(Trait::Volume);
+        self.push_trait(Trait::Volume);
+    }
+
+    /// Deregister the [AppSelector] trait
+    pub fn unset_app_selector(&mut self) {
+        self.device_traits.app_selector = None;
+        self.traits.retain(|t| *t != Trait::AppSelector);
+    }
+
+    /// Deregister the [ArmDisarm] trait
+    pub fn unset_arm_disarm(&mut self) {
+        self.device_traits.arm_disarm = None;
+        self.traits.retain(|t| *t != Trait::ArmDisarm);
+    }
+
+    /// Deregister the [Brightness] trait
+    pub fn unset_brightness(&mut self) {
+        self.device_traits.brightness = None;
+        self.traits.retain(|t| *t != Trait::Brightness);
+    }
+
+    /// Deregister the [CameraStream] trait
+    pub fn unset_camera_stream(&mut self) {
+        self.device_traits.camera_stream = None;
+        self.traits.retain(|t| *t != Trait::CameraStream);
+    }
+
+    /// Deregister the [Channel] trait
+    pub fn unset_channel(&mut self) {
+        self.device_traits.channel = None;
+        self.traits.retain(|t| *t != Trait::Channel);
+    }
+
+    /// Deregister the [ColorSetting] trait
+    pub fn unset_color_setting(&mut self) {
+        self.device_traits.color_setting = None;
+        self.traits.retain(|t| *t != Trait::ColorSetting);
+    }
+
+    /// Deregister the [Cook] trait
+    pub fn unset_cook(&mut self) {
+        self.device_traits.cook = None;
+        self.traits.retain(|t| *t != Trait::Cook);
+    }
+
+    /// Deregister the [Dispense] trait
+    pub fn unset_dispense(&mut self) {
+        self.device_traits.dispense = None;
+        self.traits.retain(|t| *t != Trait::Dispense);
+    }
+
+    /// Deregister the [Dock] trait
+    pub fn unset_dock(&mut self) {
+        self.device_traits.dock = None;
+        self.traits.retain(|t| *t != Trait::Dock);
+    }
+
+    /// Deregister the [EnergyStorage] trait
+    pub fn unset_energy_storage(&mut self) {
+        self.device_traits.energy_storage = None;
+        self.traits.retain(|t| *t != Trait::EnergyStorage);
+    }
+
+    /// Deregister the [FanSpeed] trait
+    pub fn unset_fan_speed(&mut self) {
+        self.device_traits.fan_speed = None;
+        self.traits.retain(|t| *t != Trait::FanSpeed);
+    }
+
+    /// Deregister the [Fill] trait
+    pub fn unset_fill(&mut self) {
+        self.device_traits.fill = None;
+        self.traits.retain(|t| *t != Trait::Fill);
+    }
+
+    /// Deregister the [HumiditySetting] trait
+    pub fn unset_humidity_setting(&mut self) {
+        self.device_traits.humidity_setting = None;
+        self.traits.retain(|t| *t != Trait::HumiditySetting);
+    }
+
+    /// Deregister the [InputSelector] trait
+    pub fn unset_input_selector(&mut self) {
+        self.device_traits.input_selector = None;
+        self.traits.retain(|t| *t != Trait::InputSelector);
+    }
+
+    /// Deregister the [LightEffects] trait
+    pub fn unset_light_effects(&mut self) {
+        self.device_traits.light_effects = None;
+        self.traits.retain(|t| *t != Trait::LightEffects);
+    }
+
+    /// Deregister the [Locator] trait
+    pub fn unset_locator(&mut self) {
+        self.device_traits.locator = None;
+        self.traits.retain(|t| *t != Trait::Locator);
+    }
+
+    /// Deregister the [LockUnlock] trait
+    pub fn unset_lock_unlock(&mut self) {
+        self.device_traits.lock_unlock = None;
+        self.traits.retain(|t| *t != Trait::LockUnlock);
+    }
+
+    /// Deregister the [MediaState] trait
+    pub fn unset_media_state(&mut self) {
+        self.device_traits.media_state = None;
+        self.traits.retain(|t| *t != Trait::MediaState);
+    }
+
+    /// Deregister the [Modes] trait
+    pub fn unset_modes(&mut self) {
+        self.device_traits.modes = None;
+        self.traits.retain(|t| *t != Trait::Modes);
+    }
+
+    /// Deregister the [NetworkControl] trait
+    pub fn unset_network_control(&mut self) {
+        self.device_traits.network_control = None;
+        self.traits.retain(|t| *t != Trait::NetworkControl);
+    }
+
+    /// Deregister the [OnOff] trait
+    pub fn unset_on_off(&mut self) {
+        self.device_traits.on_off = None;
+        self.traits.retain(|t| *t != Trait::OnOff);
+    }
+
+    /// Deregister the [OpenClose] trait
+    pub fn unset_open_close(&mut self) {
+        self.device_traits.open_close = None;
+        self.traits.retain(|t| *t != Trait::OpenClose);
+    }
+
+    /// Deregister the [Reboot] trait
+    pub fn unset_reboot(&mut self) {
+        self.device_traits.reboot = None;
+        self.traits.retain(|t| *t != Trait::Reboot);
+    }
+
+    /// Deregister the [Rotation] trait
+    pub fn unset_rotation(&mut self) {
+        self.device_traits.rotation = None;
+        self.traits.retain(|t| *t != Trait::Rotation);
+    }
+
+    /// Deregister the [RunCycle] trait
+    pub fn unset_run_cycle(&mut self) {
+        self.device_traits.run_cycle = None;
+        self.traits.retain(|t| *t != Trait::RunCycle);
+    }
+
+    /// Deregister the [Scene] trait
+    pub fn unset_scene(&mut self) {
+        self.device_traits.scene = None;
+        self.traits.retain(|t| *t != Trait::Scene);
+    }
+
+    /// Deregister the [SensorState] trait
+    pub fn unset_sensor_state(&mut self) {
+        self.device_traits.sensor_state = None;
+        self.traits.retain(|t| *t != Trait::SensorState);
+    }
+
+    /// Deregister the [SoftwareUpdate] trait
+    pub fn unset_software_update(&mut self) {
+        self.device_traits.software_update = None;
+        self.traits.retain(|t| *t != Trait::SoftwareUpdate);
+    }
+
+    /// Deregister the [StartStop] trait
+    pub fn unset_start_stop(&mut self) {
+        self.device_traits.start_stop = None;
+        self.traits.retain(|t| *t != Trait::StartStop);
+    }
+
+    /// Deregister the [StatusReport] trait
+    pub fn unset_status_report(&mut self) {
+        self.device_traits.status_report = None;
+        self.traits.retain(|t| *t != Trait::StatusReport);
+    }
+
+    /// Deregister the [TemperatureControl] trait
+    pub fn unset_temperature_control(&mut self) {
+        self.device_traits.temperature_control = None;
+        self.traits.retain(|t| *t != Trait::TemperatureControl);
+    }
+
+    /// Deregister the [TemperatureSetting] trait
+    pub fn unset_temperature_setting(&mut self) {
+        self.device_traits.temperature_setting = None;
+        self.traits.retain(|t| *t != Trait::TemperatureSetting);
+    }
+
+    /// Deregister the [Timer] trait
+    pub fn unset_timer(&mut self) {
+        self.device_traits.timer = None;
+        self.traits.retain(|t| *t != Trait::Timer);
+    }
+
+    /// Deregister the [Toggles] trait
+    pub fn unset_toggles(&mut self) {
+        self.device_traits.toggles = None;
+        self.traits.retain(|t| *t != Trait::Toggles);
+    }
+
+    /// Deregister the [TransportControl] trait
+    pub fn unset_transport_control(&mut self) {
+        self.device_traits.transport_control = None;
+        self.traits.retain(|t| *t != Trait::TransportControl);
+    }
+
+    /// Deregister the [Volume] trait
+    pub fn unset_volume(&mut self) {
+        self.device_traits.volume = None;
+        self.traits.retain(|t| *t != Trait::Volume);
     }
+}
 
-    // TODO rest of the traits
+/// Create the SYNC response for a single device
+pub(crate) fn sync_device<T: GoogleHomeDevice + ?Sized>(
+    id: &str,
+    device_type: &DeviceType,
+    traits: &[Trait],
+    device: &T,
+    attributes: fulfillment::response::sync::SyncAttributes,
+) -> fulfillment::response::sync::Device {
+    let name = device.get_device_name();
+    let info = device.get_device_info();
+
+    fulfillment::response::sync::Device {
+        id: id.to_string(),
+        device_type: device_type.as_device_type_string(),
+        traits: traits.to_vec(),
+        name: fulfillment::response::sync::DeviceName {
+            name: name.name,
+            default_names: name.default_names,
+            nicknames: name.nicknames,
+        },
+        will_report_state: device.will_report_state(),
+        notification_supported_by_agent: device.supports_notifications(),
+        room_hint: device.get_room_hint(),
+        structure_hint: device.get_structure_hint(),
+        device_info: fulfillment::response::sync::DeviceInfo {
+            manufacturer: info.manufacturer,
+            model: info.model,
+            hw_version: info.hw,
+            sw_version: info.sw,
+        },
+        attributes,
+        other_device_ids: device.get_other_device_ids(),
+        custom_data: device.get_custom_data(),
+        error_code: device.get_sync_error(),
+    }
+}
+
+/// The volume state reported after a Volume command
+fn volume_state(device: &(dyn Volume + Send + Sync)) -> Result<VolumeCommandState, CombinedDeviceError> {
+    Ok(VolumeCommandState {
+        current_volume: device.get_current_volume()?,
+        is_muted: device.is_muted()?,
+    })
+}
+
+/// The error for a command belonging to a trait which is not registered for the device
+pub(crate) fn unsupported() -> ExecuteError {
+    CombinedDeviceError::DeviceError(DeviceError::FunctionNotSupported).into()
+}
+
+/// Reject a command for a trait which the device reports as query-only, see e.g. [OnOff::is_query_only]
+pub(crate) fn reject_query_only<E>(query_only: Result<Option<bool>, E>) -> Result<(), ExecuteError>
+where
+    ExecuteError: From<E>,
+{
+    match query_only? {
+        Some(true) => Err(ErrorCode::NotSupported.into()),
+        _ => Ok(()),
+    }
+}
+
+/// Whether the state of a trait should be left out of QUERY because the device reports it as command-only,
+/// see e.g. [OnOff::is_command_only]
+pub(crate) fn is_command_only<E>(command_only: Result<Option<bool>, E>) -> Result<bool, ExecuteError>
+where
+    ExecuteError: From<E>,
+{
+    Ok(command_only?.unwrap_or(false))
+}
+
+/// Create the QUERY response for a single device which is offline
+pub(crate) fn offline_query_device_state() -> fulfillment::response::query::QueryDeviceState {
+    fulfillment::response::query::QueryDeviceState {
+        required: fulfillment::response::query::RequiredQueryDeviceState {
+            status: fulfillment::response::query::QueryStatus::Offline,
+            online: false,
+            error_code: None,
+        },
+        traits: None,
+    }
+}
+
+/// Create the QUERY response for a device which does not exist
+pub(crate) fn unknown_query_device_state() -> fulfillment::response::query::QueryDeviceState {
+    fulfillment::response::query::QueryDeviceState {
+        required: fulfillment::response::query::RequiredQueryDeviceState {
+            status: fulfillment::response::query::QueryStatus::Error,
+            online: false,
+            error_code: Some(ErrorCode::DeviceNotFound),
+        },
+        traits: None,
+    }
+}
+
+/// Create the EXECUTE output for a device which does not exist
+pub(crate) fn unknown_command_output(id: String) -> CommandOutput {
+    command_output(id, Err(ErrorCode::DeviceNotFound.into()))
+}
+
+/// Create the QUERY response for a single online device from the collected trait states
+pub(crate) fn query_device_state(
+    states: Result<fulfillment::response::query::TraitsQueryDeviceState, ExecuteError>,
+) -> fulfillment::response::query::QueryDeviceState {
+    let states = match states {
+        Ok(s) => s,
+        Err(e) => {
+            // Without an error code the device could not be reached, the error itself is meaningless to Google
+            let error_code = e.to_google_error().0.map(ErrorCode::from).unwrap_or(ErrorCode::DeviceOffline);
+
+            return fulfillment::response::query::QueryDeviceState {
+                required: fulfillment::response::query::RequiredQueryDeviceState {
+                    status: fulfillment::response::query::QueryStatus::Error,
+                    online: true,
+                    error_code: Some(error_code),
+                },
+                traits: None,
+            };
+        }
+    };
+
+    fulfillment::response::query::QueryDeviceState {
+        required: fulfillment::response::query::RequiredQueryDeviceState {
+            status: fulfillment::response::query::QueryStatus::Success,
+            online: true,
+            error_code: None,
+        },
+        traits: Some(states),
+    }
+}
+
+/// Create the EXECUTE output for a single device from the result of running a command
+pub(crate) fn command_output(id: String, result: Result<Option<TraitCommandState>, ExecuteError>) -> CommandOutput {
+    match result {
+        Ok(state) => CommandOutput {
+            id,
+            status: CommandStatus::Success,
+            // Don't send a states object if the command did not report any state
+            state: state.map(|traits| CommandState { online: true, traits }),
+            error: None,
+            error_string: None,
+            debug_string: None,
+            challenge_needed: None,
+        },
+        Err(e) => {
+            let (error_code, debug_string) = e.to_google_error();
+            CommandOutput {
+                id,
+                // Errors without an error code mean the device could not be reached
+                status: if error_code.is_some() { CommandStatus::Error } else { CommandStatus::Offline },
+                state: None,
+                error: error_code.map(|code| SerializableError(Box::new(ErrorCode::from(code)))),
+                error_string: None,
+                debug_string,
+                challenge_needed: match e {
+                    ExecuteError::Challenge(c) => Some(c),
+                    _ => None,
+                },
+            }
+        }
+    }
 }
 
 /// Contains all supported device traits.
@@ -1564,43 +2281,43 @@ impl<T: GoogleHomeDevice + Send + Sync + Debug + ?Sized + 'static> Device<T> {
 #[allow(unused)]
 #[derive(Default)]
 struct DeviceTraits {
-    app_selector: Option<Rc<RefCell<dyn AppSelector>>>,
-    arm_disarm: Option<Rc<RefCell<dyn ArmDisarm>>>,
-    brightness: Option<Rc<RefCell<dyn Brightness + Send + Sync>>>,
-    camera_stream: Option<Rc<RefCell<dyn CameraStream + Send + Sync>>>,
-    channel: Option<Rc<RefCell<dyn Channel + Send + Sync>>>,
-    color_setting: Option<Rc<RefCell<dyn ColorSetting + Send + Sync>>>,
-    cook: Option<Rc<RefCell<dyn Cook + Send + Sync>>>,
-    dispense: Option<Rc<RefCell<dyn Dispense + Send + Sync>>>,
-    dock: Option<Rc<RefCell<dyn Dock + Send + Sync>>>,
-    energy_storage: Option<Rc<RefCell<dyn EnergyStorage + Send + Sync>>>,
-    fan_speed: Option<Rc<RefCell<dyn FanSpeed + Send + Sync>>>,
-    fill: Option<Rc<RefCell<dyn Fill + Send + Sync>>>,
-    humidity_setting: Option<Rc<RefCell<dyn HumiditySetting + Send + Sync>>>,
-    input_selector: Option<Rc<RefCell<dyn InputSelector + Send + Sync>>>,
-    light_effects: Option<Rc<RefCell<dyn LightEffects + Send + Sync>>>,
-    locator: Option<Rc<RefCell<dyn Locator + Send + Sync>>>,
-    lock_unlock: Option<Rc<RefCell<dyn LockUnlock + Send + Sync>>>,
-    media_state: Option<Rc<RefCell<dyn MediaState + Send + Sync>>>,
-    modes: Option<Rc<RefCell<dyn Modes + Send + Sync>>>,
-    network_control: Option<Rc<RefCell<dyn NetworkControl + Send + Sync>>>,
-    object_detection: Option<Rc<RefCell<dyn ObjectDetection + Send + Sync>>>,
-    on_off: Option<Rc<RefCell<dyn OnOff + Send + Sync>>>,
-    open_close: Option<Rc<RefCell<dyn OpenClose + Send + Sync>>>,
-    reboot: Option<Rc<RefCell<dyn Reboot + Send + Sync>>>,
-    rotation: Option<Rc<RefCell<dyn Rotation + Send + Sync>>>,
-    run_cycle: Option<Rc<RefCell<dyn RunCycle + Send + Sync>>>,
-    sensor_state: Option<Rc<RefCell<dyn SensorState + Send + Sync>>>,
-    scene: Option<Rc<RefCell<dyn Scene + Send + Sync>>>,
-    software_update: Option<Rc<RefCell<dyn SoftwareUpdate + Send + Sync>>>,
-    start_stop: Option<Rc<RefCell<dyn StartStop + Send + Sync>>>,
-    status_report: Option<Rc<RefCell<dyn StatusReport + Send + Sync>>>,
-    temperature_control: Option<Rc<RefCell<dyn TemperatureControl + Send + Sync>>>,
-    temperature_setting: Option<Rc<RefCell<dyn TemperatureSetting + Send + Sync>>>,
-    timer: Option<Rc<RefCell<dyn Timer + Send + Sync>>>,
-    toggles: Option<Rc<RefCell<dyn Toggles + Send + Sync>>>,
-    transport_control: Option<Rc<RefCell<dyn TransportControl + Send + Sync>>>,
-    volume: Option<Rc<RefCell<dyn Volume + Send + Sync>>>,
+    app_selector: Option<Arc<Mutex<dyn AppSelector + Send + Sync>>>,
+    arm_disarm: Option<Arc<Mutex<dyn ArmDisarm + Send + Sync>>>,
+    brightness: Option<Arc<Mutex<dyn Brightness + Send + Sync>>>,
+    camera_stream: Option<Arc<Mutex<dyn CameraStream + Send + Sync>>>,
+    channel: Option<Arc<Mutex<dyn Channel + Send + Sync>>>,
+    color_setting: Option<Arc<Mutex<dyn ColorSetting + Send + Sync>>>,
+    cook: Option<Arc<Mutex<dyn Cook + Send + Sync>>>,
+    dispense: Option<Arc<Mutex<dyn Dispense + Send + Sync>>>,
+    dock: Option<Arc<Mutex<dyn Dock + Send + Sync>>>,
+    energy_storage: Option<Arc<Mutex<dyn EnergyStorage + Send + Sync>>>,
+    fan_speed: Option<Arc<Mutex<dyn FanSpeed + Send + Sync>>>,
+    fill: Option<Arc<Mutex<dyn Fill + Send + Sync>>>,
+    humidity_setting: Option<Arc<Mutex<dyn HumiditySetting + Send + Sync>>>,
+    input_selector: Option<Arc<Mutex<dyn InputSelector + Send + Sync>>>,
+    light_effects: Option<Arc<Mutex<dyn LightEffects + Send + Sync>>>,
+    locator: Option<Arc<Mutex<dyn Locator + Send + Sync>>>,
+    lock_unlock: Option<Arc<Mutex<dyn LockUnlock + Send + Sync>>>,
+    media_state: Option<Arc<Mutex<dyn MediaState + Send + Sync>>>,
+    modes: Option<Arc<Mutex<dyn Modes + Send + Sync>>>,
+    network_control: Option<Arc<Mutex<dyn NetworkControl + Send + Sync>>>,
+    object_detection: Option<Arc<Mutex<dyn ObjectDetection + Send + Sync>>>,
+    on_off: Option<Arc<Mutex<dyn OnOff + Send + Sync>>>,
+    open_close: Option<Arc<Mutex<dyn OpenClose + Send + Sync>>>,
+    reboot: Option<Arc<Mutex<dyn Reboot + Send + Sync>>>,
+    rotation: Option<Arc<Mutex<dyn Rotation + Send + Sync>>>,
+    run_cycle: Option<Arc<Mutex<dyn RunCycle + Send + Sync>>>,
+    sensor_state: Option<Arc<Mutex<dyn SensorState + Send + Sync>>>,
+    scene: Option<Arc<Mutex<dyn Scene + Send + Sync>>>,
+    software_update: Option<Arc<Mutex<dyn SoftwareUpdate + Send + Sync>>>,
+    start_stop: Option<Arc<Mutex<dyn StartStop + Send + Sync>>>,
+    status_report: Option<Arc<Mutex<dyn StatusReport + Send + Sync>>>,
+    temperature_control: Option<Arc<Mutex<dyn TemperatureControl + Send + Sync>>>,
+    temperature_setting: Option<Arc<Mutex<dyn TemperatureSetting + Send + Sync>>>,
+    timer: Option<Arc<Mutex<dyn Timer + Send + Sync>>>,
+    toggles: Option<Arc<Mutex<dyn Toggles + Send + Sync>>>,
+    transport_control: Option<Arc<Mutex<dyn TransportControl + Send + Sync>>>,
+    volume: Option<Arc<Mutex<dyn Volume + Send + Sync>>>,
 }
 
 impl fmt::Debug for DeviceTraits {
@@ -1608,3 +2325,42 @@ impl fmt::Debug for DeviceTraits {
         write!(f, "DeviceTraits {{ .. }}")
     }
 }
+
+/// Run a callback into the device.
+/// With the `catch-panics` feature, a panic in the callback is reported as [ErrorCode::HardError] instead of unwinding into the request handler.
+/// A panic may leave the device with its state partly updated, so `panicked` is set and the callbacks of the device are
+/// not run again: every later intent is answered with [ErrorCode::HardError], until the device is added again.
+/// The code around the callbacks must not panic itself: malformed commands are rejected with an error code instead,
+/// so that a panic always comes from the device and a bad request can't leave a healthy device broken.
+#[cfg(feature = "catch-panics")]
+fn catch_panic<R>(panicked: &AtomicBool, f: impl FnOnce() -> R) -> Result<R, ErrorCode> {
+    if panicked.load(Ordering::Acquire) {
+        return Err(ErrorCode::HardError);
+    }
+
+    // Unwind safety is upheld by never running a callback into the device again after a panic
+    std::panic::catch_unwind(std::panic::AssertUnwindSafe(f)).map_err(|_| {
+        error!("A device panicked while handling an intent, it is reported as broken until it is added again");
+        panicked.store(true, Ordering::Release);
+        ErrorCode::HardError
+    })
+}
+
+/// Run a callback into the device.
+/// Without the `catch-panics` feature, a panic in the callback unwinds into the request handler.
+#[cfg(not(feature = "catch-panics"))]
+fn catch_panic<R>(_panicked: &AtomicBool, f: impl FnOnce() -> R) -> Result<R, ErrorCode> {
+    Ok(f())
+}
+
+/// Lock a [Mutex], recovering the inner value if a previous holder panicked.
+/// A device which panicked is not called again during intents (see [catch_panic]), its state stays accessible through [Device::with_inner].
+trait LockIgnorePoison<T: ?Sized> {
+    fn lock_ignore_poison(&self) -> MutexGuard<'_, T>;
+}
+
+impl<T: ?Sized> LockIgnorePoison<T> for Mutex<T> {
+    fn lock_ignore_poison(&self) -> MutexGuard<'_, T> {
+        self.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
@@ -0,0 +1,430 @@
+use crate::device::Device;
+use crate::device_type::DeviceType;
+use crate::traits::app_selector::AppSelector;
+use crate::traits::arm_disarm::ArmDisarm;
+use crate::traits::brightness::Brightness;
+use crate::traits::camera_stream::CameraStream;
+use crate::traits::channel::Channel;
+use crate::traits::color_setting::ColorSetting;
+use crate::traits::cook::Cook;
+use crate::traits::dispense::Dispense;
+use crate::traits::dock::Dock;
+use crate::traits::energy_storage::EnergyStorage;
+use crate::traits::fan_speed::FanSpeed;
+use crate::traits::humidity_setting::HumiditySetting;
+use crate::traits::input_selector::InputSelector;
+use crate::traits::light_effects::LightEffects;
+use crate::traits::locator::Locator;
+use crate::traits::lock_unlock::LockUnlock;
+use crate::traits::media_state::MediaState;
+use crate::traits::modes::Modes;
+use crate::traits::network_control::NetworkControl;
+use crate::traits::on_off::OnOff;
+use crate::traits::open_close::OpenClose;
+use crate::traits::reboot::Reboot;
+use crate::traits::rotation::Rotation;
+use crate::traits::run_cycle::RunCycle;
+use crate::traits::scene::Scene;
+use crate::traits::sensor_state::SensorState;
+use crate::traits::software_update::SoftwareUpdate;
+use crate::traits::start_stop::StartStop;
+use crate::traits::status_report::StatusReport;
+use crate::traits::temperature_control::TemperatureControl;
+use crate::traits::temperature_setting::TemperatureSetting;
+use crate::traits::timer::Timer;
+use crate::traits::toggles::Toggles;
+use crate::traits::transport_control::TransportControl;
+use crate::traits::volume::Volume;
+use crate::GoogleHomeDevice;
+use std::fmt::Debug;
+
+/// Fluent construction of a [Device] with its traits.
+///
+/// ```
+/// use homelander::{DeviceBuilder, DeviceType, Homelander};
+/// use homelander::traits::{CombinedDeviceError, DeviceInfo, DeviceName, GoogleHomeDevice};
+/// use homelander::traits::on_off::OnOff;
+///
+/// #[derive(Debug)]
+/// struct MyDevice(bool);
+///
+/// impl GoogleHomeDevice for MyDevice {
+///     fn get_device_info(&self) -> DeviceInfo {
+///         DeviceInfo {
+///             model: "mydevice".to_string(),
+///             manufacturer: "mydevice company".to_string(),
+///             hw: "0.1.0".to_string(),
+///             sw: "0.1.0".to_string(),
+///         }
+///     }
+///
+///     fn will_report_state(&self) -> bool {
+///         false
+///     }
+///
+///     fn get_device_name(&self) -> DeviceName {
+///         DeviceName {
+///             name: "MyDevice".to_string(),
+///             default_names: Vec::new(),
+///             nicknames: Vec::new(),
+///         }
+///     }
+///
+///     fn is_online(&self) -> bool {
+///         true
+///     }
+///
+///     fn disconnect(&mut self) {}
+/// }
+///
+/// impl OnOff for MyDevice {
+///     fn is_on(&self) -> Result<bool, CombinedDeviceError> {
+///         Ok(self.0)
+///     }
+///
+///     fn set_on(&mut self, on: bool) -> Result<(), CombinedDeviceError> {
+///         self.0 = on;
+///         Ok(())
+///     }
+/// }
+///
+/// let device = DeviceBuilder::new(MyDevice(false), DeviceType::Outlet, "my_id".to_string())
+///     .on_off()
+///     .build();
+///
+/// let mut homelander = Homelander::new("my_user_id".to_string());
+/// homelander.add_device(device);
+/// ```
+#[derive(Debug)]
+pub struct DeviceBuilder<T: GoogleHomeDevice + Debug + Send + Sync + 'static> {
+    device: Device<T>,
+}
+
+impl<T: GoogleHomeDevice + Debug + Send + Sync + 'static> DeviceBuilder<T> {
+    /// Start building a device. See [Device::new]
+    pub fn new(device: T, device_type: DeviceType, id: String) -> Self {
+        Self {
+            device: Device::new(device, device_type, id),
+        }
+    }
+
+    /// Finish building the device
+    pub fn build(self) -> Device<T> {
+        self.device
+    }
+
+    /// Register the [AppSelector] trait
+    pub fn app_selector(mut self) -> Self
+    where
+        T: AppSelector,
+    {
+        self.device.set_app_selector();
+        self
+    }
+
+    /// Register the [ArmDisarm] trait
+    pub fn arm_disarm(mut self) -> Self
+    where
+        T: ArmDisarm,
+    {
+        self.device.set_arm_disarm();
+        self
+    }
+
+    /// Register the [Brightness] trait
+    pub fn brightness(mut self) -> Self
+    where
+        T: Brightness,
+    {
+        self.device.set_brightness();
+        self
+    }
+
+    /// Register the [CameraStream] trait
+    pub fn camera_stream(mut self) -> Self
+    where
+        T: CameraStream,
+    {
+        self.device.set_camera_stream();
+        self
+    }
+
+    /// Register the [Channel] trait
+    pub fn channel(mut self) -> Self
+    where
+        T: Channel,
+    {
+        self.device.set_channel();
+        self
+    }
+
+    /// Register the [ColorSetting] trait
+    pub fn color_setting(mut self) -> Self
+    where
+        T: ColorSetting,
+    {
+        self.device.set_color_setting();
+        self
+    }
+
+    /// Register the [Cook] trait
+    pub fn cook(mut self) -> Self
+    where
+        T: Cook,
+    {
+        self.device.set_cook();
+        self
+    }
+
+    /// Register the [Dispense] trait
+    pub fn dispense(mut self) -> Self
+    where
+        T: Dispense,
+    {
+        self.device.set_dispense();
+        self
+    }
+
+    /// Register the [Dock] trait
+    pub fn dock(mut self) -> Self
+    where
+        T: Dock,
+    {
+        self.device.set_dock();
+        self
+    }
+
+    /// Register the [EnergyStorage] trait
+    pub fn energy_storage(mut self) -> Self
+    where
+        T: EnergyStorage,
+    {
+        self.device.set_energy_storage();
+        self
+    }
+
+    /// Register the [FanSpeed] trait
+    pub fn fan_speed(mut self) -> Self
+    where
+        T: FanSpeed,
+    {
+        self.device.set_fan_speed();
+        self
+    }
+
+    /// Register the [HumiditySetting] trait
+    pub fn humidity_setting(mut self) -> Self
+    where
+        T: HumiditySetting,
+    {
+        self.device.set_humidity_setting();
+        self
+    }
+
+    /// Register the [InputSelector] trait
+    pub fn input_selector(mut self) -> Self
+    where
+        T: InputSelector,
+    {
+        self.device.set_input_selector();
+        self
+    }
+
+    /// Register the [LightEffects] trait
+    pub fn light_effects(mut self) -> Self
+    where
+        T: LightEffects,
+    {
+        self.device.set_light_effects();
+        self
+    }
+
+    /// Register the [Locator] trait
+    pub fn locator(mut self) -> Self
+    where
+        T: Locator,
+    {
+        self.device.set_locator();
+        self
+    }
+
+    /// Register the [LockUnlock] trait
+    pub fn lock_unlock(mut self) -> Self
+    where
+        T: LockUnlock,
+    {
+        self.device.set_lock_unlock();
+        self
+    }
+
+    /// Register the [MediaState] trait
+    pub fn media_state(mut self) -> Self
+    where
+        T: MediaState,
+    {
+        self.device.set_media_state();
+        self
+    }
+
+    /// Register the [Modes] trait
+    pub fn modes(mut self) -> Self
+    where
+        T: Modes,
+    {
+        self.device.set_modes();
+        self
+    }
+
+    /// Register the [NetworkControl] trait
+    pub fn network_control(mut self) -> Self
+    where
+        T: NetworkControl,
+    {
+        self.device.set_network_control();
+        self
+    }
+
+    /// Register the [OnOff] trait
+    pub fn on_off(mut self) -> Self
+    where
+        T: OnOff,
+    {
+        self.device.set_on_off();
+        self
+    }
+
+    /// Register the [OpenClose] trait
+    pub fn open_close(mut self) -> Self
+    where
+        T: OpenClose,
+    {
+        self.device.set_open_close();
+        self
+    }
+
+    /// Register the [Reboot] trait
+    pub fn reboot(mut self) -> Self
+    where
+        T: Reboot,
+    {
+        self.device.set_reboot();
+        self
+    }
+
+    /// Register the [Rotation] trait
+    pub fn rotation(mut self) -> Self
+    where
+        T: Rotation,
+    {
+        self.device.set_rotation();
+        self
+    }
+
+    /// Register the [RunCycle] trait
+    pub fn run_cycle(mut self) -> Self
+    where
+        T: RunCycle,
+    {
+        self.device.set_run_cycle();
+        self
+    }
+
+    /// Register the [Scene] trait
+    pub fn scene(mut self) -> Self
+    where
+        T: Scene,
+    {
+        self.device.set_scene();
+        self
+    }
+
+    /// Register the [SensorState] trait
+    pub fn sensor_state(mut self) -> Self
+    where
+        T: SensorState,
+    {
+        self.device.set_sensor_state();
+        self
+    }
+
+    /// Register the [SoftwareUpdate] trait
+    pub fn software_update(mut self) -> Self
+    where
+        T: SoftwareUpdate,
+    {
+        self.device.set_software_update();
+        self
+    }
+
+    /// Register the [StartStop] trait
+    pub fn start_stop(mut self) -> Self
+    where
+        T: StartStop,
+    {
+        self.device.set_start_stop();
+        self
+    }
+
+    /// Register the [StatusReport] trait
+    pub fn status_report(mut self) -> Self
+    where
+        T: StatusReport,
+    {
+        self.device.set_status_report();
+        self
+    }
+
+    /// Register the [TemperatureControl] trait
+    pub fn temperature_control(mut self) -> Self
+    where
+        T: TemperatureControl,
+    {
+        self.device.set_temperature_control();
+        self
+    }
+
+    /// Register the [TemperatureSetting] trait
+    pub fn temperature_setting(mut self) -> Self
+    where
+        T: TemperatureSetting,
+    {
+        self.device.set_temperature_setting();
+        self
+    }
+
+    /// Register the [Timer] trait
+    pub fn timer(mut self) -> Self
+    where
+        T: Timer,
+    {
+        self.device.set_timer();
+        self
+    }
+
+    /// Register the [Toggles] trait
+    pub fn toggles(mut self) -> Self
+    where
+        T: Toggles,
+    {
+        self.device.set_toggles();
+        self
+    }
+
+    /// Register the [TransportControl] trait
+    pub fn transport_control(mut self) -> Self
+    where
+        T: TransportControl,
+    {
+        self.device.set_transport_control();
+        self
+    }
+
+    /// Register the [Volume] trait
+    pub fn volume(mut self) -> Self
+    where
+        T: Volume,
+    {
+        self.device.set_volume();
+        self
+    }
+}
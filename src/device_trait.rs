@@ -1,80 +1,147 @@
-use serde::Serialize;
+use serde::{Serialize, Serializer};
+use std::fmt;
 
-#[non_exhaustive]
-#[derive(Debug, Clone, Eq, PartialEq, Serialize)]
-pub enum Trait {
-    #[serde(rename = "action.devices.traits.AppSelector")]
+/// Define [Trait] from a list of variants, named after the trait in Google's trait string
+macro_rules! traits {
+    ($($variant:ident,)*) => {
+        /// A Google Home device trait
+        #[non_exhaustive]
+        #[derive(Debug, Clone, Eq, PartialEq)]
+        pub enum Trait {
+            $($variant,)*
+        }
+
+        impl Trait {
+            /// The trait as sent to Google, e.g. `action.devices.traits.OnOff`
+            pub fn as_str(&self) -> &'static str {
+                match self {
+                    $(Self::$variant => concat!("action.devices.traits.", stringify!($variant)),)*
+                }
+            }
+
+            #[cfg(test)]
+            const ALL: &'static [Trait] = &[$(Self::$variant,)*];
+        }
+    };
+}
+
+traits! {
     AppSelector,
-    #[serde(rename = "action.devices.traits.ArmDisarm")]
     ArmDisarm,
-    #[serde(rename = "action.devices.traits.Brightness")]
     Brightness,
-    #[serde(rename = "action.devices.traits.CameraStream")]
     CameraStream,
-    #[serde(rename = "action.devices.traits.Channel")]
     Channel,
-    #[serde(rename = "action.devices.traits.ColorSetting")]
     ColorSetting,
-    #[serde(rename = "action.devices.traits.Cook")]
     Cook,
-    #[serde(rename = "action.devices.traits.Dispense")]
     Dispense,
-    #[serde(rename = "action.devices.traits.Dock")]
     Dock,
-    #[serde(rename = "action.devices.traits.EnergyStorage")]
     EnergyStorage,
-    #[serde(rename = "action.devices.traits.FanSpeed")]
     FanSpeed,
-    #[serde(rename = "action.devices.traits.Fill")]
     Fill,
-    #[serde(rename = "action.devices.traits.HumiditySetting")]
     HumiditySetting,
-    #[serde(rename = "action.devices.traits.InputSelector")]
     InputSelector,
-    #[serde(rename = "action.devices.traits.LightEffects")]
     LightEffects,
-    #[serde(rename = "action.devices.traits.Locator")]
     Locator,
-    #[serde(rename = "action.devices.traits.LockUnlock")]
     LockUnlock,
-    #[serde(rename = "action.devices.traits.MediaState")]
     MediaState,
-    #[serde(rename = "action.devices.traits.Modes")]
     Modes,
-    #[serde(rename = "action.devices.traits.NetworkControl")]
     NetworkControl,
-    #[serde(rename = "action.devices.traits.ObjectDetection")]
     ObjectDetection,
-    #[serde(rename = "action.devices.traits.OnOff")]
     OnOff,
-    #[serde(rename = "action.devices.traits.OpenClose")]
     OpenClose,
-    #[serde(rename = "action.devices.traits.Reboot")]
     Reboot,
-    #[serde(rename = "action.devices.traits.Rotation")]
     Rotation,
-    #[serde(rename = "action.devices.traits.RunCycle")]
     RunCycle,
-    #[serde(rename = "action.devices.traits.SensorState")]
     SensorState,
-    #[serde(rename = "action.devices.traits.Scene")]
     Scene,
-    #[serde(rename = "action.devices.traits.SoftwareUpdate")]
     SoftwareUpdate,
-    #[serde(rename = "action.devices.traits.StartStop")]
     StartStop,
-    #[serde(rename = "action.devices.traits.StatusReport")]
     StatusReport,
-    #[serde(rename = "action.devices.traits.TemperatureControl")]
     TemperatureControl,
-    #[serde(rename = "action.devices.traits.TemperatureSetting")]
     TemperatureSetting,
-    #[serde(rename = "action.devices.traits.Timer")]
     Timer,
-    #[serde(rename = "action.devices.traits.Toggles")]
     Toggles,
-    #[serde(rename = "action.devices.traits.TransportControl")]
     TransportControl,
-    #[serde(rename = "action.devices.traits.Volume")]
     Volume,
 }
+
+impl fmt::Display for Trait {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Serialize for Trait {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Trait;
+
+    #[test]
+    fn test_trait_strings() {
+        for t in Trait::ALL {
+            assert_eq!(serde_json::to_value(t).unwrap(), serde_json::json!(t.as_str()));
+            assert_eq!(t.to_string(), t.as_str());
+            assert_eq!(t.as_str(), format!("action.devices.traits.{t:?}"));
+        }
+
+        assert_eq!("action.devices.traits.OnOff", Trait::OnOff.as_str());
+        assert_eq!("action.devices.traits.TemperatureSetting", Trait::TemperatureSetting.to_string());
+    }
+
+    #[test]
+    fn test_google_trait_strings() {
+        // Spelled out, so a renamed variant can't silently change what is sent to Google
+        let traits = [
+            (Trait::AppSelector, "action.devices.traits.AppSelector"),
+            (Trait::ArmDisarm, "action.devices.traits.ArmDisarm"),
+            (Trait::Brightness, "action.devices.traits.Brightness"),
+            (Trait::CameraStream, "action.devices.traits.CameraStream"),
+            (Trait::Channel, "action.devices.traits.Channel"),
+            (Trait::ColorSetting, "action.devices.traits.ColorSetting"),
+            (Trait::Cook, "action.devices.traits.Cook"),
+            (Trait::Dispense, "action.devices.traits.Dispense"),
+            (Trait::Dock, "action.devices.traits.Dock"),
+            (Trait::EnergyStorage, "action.devices.traits.EnergyStorage"),
+            (Trait::FanSpeed, "action.devices.traits.FanSpeed"),
+            (Trait::Fill, "action.devices.traits.Fill"),
+            (Trait::HumiditySetting, "action.devices.traits.HumiditySetting"),
+            (Trait::InputSelector, "action.devices.traits.InputSelector"),
+            (Trait::LightEffects, "action.devices.traits.LightEffects"),
+            (Trait::Locator, "action.devices.traits.Locator"),
+            (Trait::LockUnlock, "action.devices.traits.LockUnlock"),
+            (Trait::MediaState, "action.devices.traits.MediaState"),
+            (Trait::Modes, "action.devices.traits.Modes"),
+            (Trait::NetworkControl, "action.devices.traits.NetworkControl"),
+            (Trait::ObjectDetection, "action.devices.traits.ObjectDetection"),
+            (Trait::OnOff, "action.devices.traits.OnOff"),
+            (Trait::OpenClose, "action.devices.traits.OpenClose"),
+            (Trait::Reboot, "action.devices.traits.Reboot"),
+            (Trait::Rotation, "action.devices.traits.Rotation"),
+            (Trait::RunCycle, "action.devices.traits.RunCycle"),
+            (Trait::SensorState, "action.devices.traits.SensorState"),
+            (Trait::Scene, "action.devices.traits.Scene"),
+            (Trait::SoftwareUpdate, "action.devices.traits.SoftwareUpdate"),
+            (Trait::StartStop, "action.devices.traits.StartStop"),
+            (Trait::StatusReport, "action.devices.traits.StatusReport"),
+            (Trait::TemperatureControl, "action.devices.traits.TemperatureControl"),
+            (Trait::TemperatureSetting, "action.devices.traits.TemperatureSetting"),
+            (Trait::Timer, "action.devices.traits.Timer"),
+            (Trait::Toggles, "action.devices.traits.Toggles"),
+            (Trait::TransportControl, "action.devices.traits.TransportControl"),
+            (Trait::Volume, "action.devices.traits.Volume"),
+        ];
+
+        assert_eq!(traits.len(), Trait::ALL.len());
+        for (t, expected) in traits {
+            assert_eq!(serde_json::to_value(&t).unwrap(), serde_json::json!(expected));
+        }
+    }
+}
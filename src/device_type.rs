@@ -1,3 +1,4 @@
+use crate::Trait;
 use convert_case::{Case, Casing};
 use serde::Serialize;
 use strum_macros::AsRefStr;
@@ -94,6 +95,38 @@ impl DeviceType {
         let cased = as_string.to_case(Case::ScreamingSnake);
         format!("{DEVICE_TYPE_PREFIX}{cased}")
     }
+
+    /// Whether Google rejects a device of this type advertising `device_trait`.
+    /// Only covers combinations which are clearly wrong, Google recommends more specific traits for most device types.
+    pub(crate) fn is_incompatible_with(&self, device_trait: &Trait) -> bool {
+        match (self, device_trait) {
+            // Scenes don't combine with other traits, see the Scene trait
+            (Self::Scene, device_trait) => *device_trait != Trait::Scene,
+            (_, Trait::Scene) => true,
+            // Devices which don't emit light can't change its color
+            (
+                Self::Outlet
+                | Self::Lock
+                | Self::Door
+                | Self::Garage
+                | Self::Gate
+                | Self::Window
+                | Self::Blinds
+                | Self::Curtain
+                | Self::Shutter
+                | Self::Awning
+                | Self::Valve
+                | Self::Sprinkler
+                | Self::Faucet
+                | Self::Thermostat
+                | Self::SmokeDetector
+                | Self::CarbonMonoxideDetector
+                | Self::SecuritySystem,
+                Trait::ColorSetting | Trait::LightEffects,
+            ) => true,
+            _ => false,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,166 @@
+use serde::{Serialize, Serializer};
+use std::error::Error;
+use std::fmt;
+
+/// Define [ErrorCode] from a list of `Variant => "code"` pairs
+macro_rules! error_codes {
+    ($($(#[$attr:meta])* $variant:ident => $code:literal,)*) => {
+        /// An error code as understood by Google.
+        ///
+        /// ## See also
+        /// <https://developers.google.com/assistant/smarthome/reference/errors-exceptions>
+        #[non_exhaustive]
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        pub enum ErrorCode {
+            $($(#[$attr])* $variant,)*
+            /// An error code not (yet) known to Homelander. The code is sent to Google as-is.
+            Other(String),
+        }
+
+        impl ErrorCode {
+            /// The error code as sent to Google
+            pub fn as_str(&self) -> &str {
+                match self {
+                    $(Self::$variant => $code,)*
+                    Self::Other(code) => code,
+                }
+            }
+
+            #[cfg(test)]
+            const KNOWN: &'static [ErrorCode] = &[$(Self::$variant,)*];
+        }
+
+        impl From<&str> for ErrorCode {
+            fn from(code: &str) -> Self {
+                match code {
+                    $($code => Self::$variant,)*
+                    code => Self::Other(code.to_string()),
+                }
+            }
+        }
+    };
+}
+
+error_codes! {
+    AlreadyArmed => "alreadyArmed",
+    AlreadyAtMax => "alreadyAtMax",
+    AlreadyAtMin => "alreadyAtMin",
+    AlreadyClosed => "alreadyClosed",
+    AlreadyDisarmed => "alreadyDisarmed",
+    AlreadyDocked => "alreadyDocked",
+    AlreadyInState => "alreadyInState",
+    AlreadyLocked => "alreadyLocked",
+    AlreadyOff => "alreadyOff",
+    AlreadyOn => "alreadyOn",
+    AlreadyOpen => "alreadyOpen",
+    AlreadyPaused => "alreadyPaused",
+    AlreadyStarted => "alreadyStarted",
+    AlreadyStopped => "alreadyStopped",
+    AlreadyUnlocked => "alreadyUnlocked",
+    AuthFailure => "authFailure",
+    /// Secondary user verification is required, see [ChallengeType](crate::traits::ChallengeType)
+    ChallengeNeeded => "challengeNeeded",
+    /// The command is not known to Homelander, see [CommandType::Unknown](crate::CommandType::Unknown)
+    CommandDispatchFailed => "commandDispatchFailed",
+    DeviceBusy => "deviceBusy",
+    DeviceJammingDetected => "deviceJammingDetected",
+    DeviceNotFound => "deviceNotFound",
+    DeviceNotReady => "deviceNotReady",
+    /// The device could not be reached
+    DeviceOffline => "deviceOffline",
+    FunctionNotSupported => "functionNotSupported",
+    HardError => "hardError",
+    /// The thermostat is in heat or cool mode, and can't be set to a range
+    InHeatOrCool => "inHeatOrCool",
+    InSoftwareUpdate => "inSoftwareUpdate",
+    LockedToRange => "lockedToRange",
+    LowBattery => "lowBattery",
+    MaxSettingReached => "maxSettingReached",
+    MaxSpeedReached => "maxSpeedReached",
+    MinSettingReached => "minSettingReached",
+    MinSpeedReached => "minSpeedReached",
+    NetworkProfileNotRecognized => "networkProfileNotRecognized",
+    NetworkSpeedTestInProgress => "networkSpeedTestInProgress",
+    NotSupported => "notSupported",
+    PinIncorrect => "pinIncorrect",
+    ProtocolError => "protocolError",
+    /// The high and low setpoint of a thermostat are closer together than its buffer range
+    RangeTooClose => "rangeTooClose",
+    RelinkRequired => "relinkRequired",
+    RemoteSetDisabled => "remoteSetDisabled",
+    SecurityRestriction => "securityRestriction",
+    TooManyFailedAttempts => "tooManyFailedAttempts",
+    TransientError => "transientError",
+    TurnedOff => "turnedOff",
+    UnknownError => "unknownError",
+    UserCancelled => "userCancelled",
+    /// The requested value is outside of the range supported by the device
+    ValueOutOfRange => "valueOutOfRange",
+}
+
+impl ErrorCode {
+    /// The error code for an error returned by a trait.
+    /// Trait errors display as their error code, though some start with an uppercase letter (e.g. `NetworkSpeedTestInProgress`),
+    /// while Google only accepts codes in lower camel case.
+    pub(crate) fn from_trait_error<E: ToString + ?Sized>(error: &E) -> Self {
+        let code = error.to_string();
+        let mut chars = code.chars();
+        match chars.next() {
+            Some(first) => Self::from(first.to_lowercase().chain(chars).collect::<String>()),
+            None => Self::from(code),
+        }
+    }
+}
+
+impl From<String> for ErrorCode {
+    fn from(code: String) -> Self {
+        Self::from(code.as_str())
+    }
+}
+
+impl fmt::Display for ErrorCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Error for ErrorCode {}
+
+impl Serialize for ErrorCode {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::ErrorCode;
+
+    #[test]
+    fn test_known_error_codes() {
+        for code in ErrorCode::KNOWN {
+            assert_eq!(serde_json::to_value(code).unwrap(), serde_json::json!(code.as_str()));
+            assert_eq!(&ErrorCode::from(code.as_str()), code);
+        }
+
+        assert_eq!("deviceOffline", ErrorCode::DeviceOffline.as_str());
+        assert_eq!("valueOutOfRange", ErrorCode::ValueOutOfRange.to_string());
+    }
+
+    #[test]
+    fn test_trait_error_code() {
+        assert_eq!(ErrorCode::NetworkSpeedTestInProgress, ErrorCode::from_trait_error("NetworkSpeedTestInProgress"));
+        assert_eq!(ErrorCode::ValueOutOfRange, ErrorCode::from_trait_error("valueOutOfRange"));
+        assert_eq!(ErrorCode::Other("deviceUnplugged".to_string()), ErrorCode::from_trait_error("DeviceUnplugged"));
+    }
+
+    #[test]
+    fn test_other_error_code() {
+        let code = ErrorCode::from("doorClosedTooLong");
+        assert_eq!(ErrorCode::Other("doorClosedTooLong".to_string()), code);
+        assert_eq!(serde_json::to_value(&code).unwrap(), serde_json::json!("doorClosedTooLong"));
+    }
+}
@@ -7,23 +7,33 @@ use crate::traits::input_selector::InputSelectorError;
 use crate::traits::lock_unlock::LockUnlockError;
 use crate::traits::network_control::NetworkControlError;
 use crate::traits::open_close::OpenCloseError;
-use crate::{CombinedDeviceError, ToStringError};
+use crate::traits::ChallengeType;
+use crate::{CombinedDeviceError, ErrorCode, ToStringError};
 use std::error::Error;
 
 #[derive(Debug)]
 pub enum ExecuteError {
     Serializable(Box<dyn ToStringError>),
     Server(Box<dyn Error>),
+    /// The command requires secondary user verification
+    Challenge(ChallengeType),
 }
 
-macro_rules! impl_execute_error {
-    ($ty:ty) => {
-        impl From<$ty> for ExecuteError {
-            fn from(t: $ty) -> Self {
-                Self::Serializable(Box::new(t))
-            }
+impl ExecuteError {
+    /// The `errorCode` and `debugString` to report this error to Google with, see [CombinedDeviceError::to_google_error].
+    pub(crate) fn to_google_error(&self) -> (Option<String>, Option<String>) {
+        match self {
+            Self::Serializable(e) => (Some(ErrorCode::from_trait_error(e).to_string()), None),
+            Self::Server(e) => (None, Some(e.to_string())),
+            Self::Challenge(c) => CombinedDeviceError::ChallengeNeeded(c.clone()).to_google_error(),
         }
-    };
+    }
+}
+
+impl From<ErrorCode> for ExecuteError {
+    fn from(code: ErrorCode) -> Self {
+        Self::Serializable(Box::new(code))
+    }
 }
 
 impl From<CombinedDeviceError> for ExecuteError {
@@ -32,16 +42,138 @@ impl From<CombinedDeviceError> for ExecuteError {
             CombinedDeviceError::Other(x) => Self::Server(Box::new(x)),
             CombinedDeviceError::DeviceError(e) => Self::Serializable(Box::new(e)),
             CombinedDeviceError::DeviceException(e) => Self::Serializable(Box::new(e)),
+            CombinedDeviceError::ChallengeNeeded(c) => Self::Challenge(c),
+        }
+    }
+}
+
+impl From<ArmDisarmError> for ExecuteError {
+    fn from(x: ArmDisarmError) -> Self {
+        match x {
+            ArmDisarmError::Other(e) => e.into(),
+            e => Self::Serializable(Box::new(e)),
+        }
+    }
+}
+
+impl From<CookError> for ExecuteError {
+    fn from(x: CookError) -> Self {
+        match x {
+            CookError::Other(e) => e.into(),
+            e => Self::Serializable(Box::new(e)),
+        }
+    }
+}
+
+impl From<DispenseError> for ExecuteError {
+    fn from(x: DispenseError) -> Self {
+        match x {
+            DispenseError::Error(e) => Self::Serializable(Box::new(e)),
+            DispenseError::Exception(e) => Self::Serializable(Box::new(e)),
+            DispenseError::Other(e) => e.into(),
+        }
+    }
+}
+
+impl From<EnergyStorageError> for ExecuteError {
+    fn from(x: EnergyStorageError) -> Self {
+        match x {
+            EnergyStorageError::Device(e) => Self::Serializable(Box::new(e)),
+            EnergyStorageError::Other(e) => e.into(),
         }
     }
 }
 
-impl_execute_error!(ArmDisarmError);
-impl_execute_error!(CookError);
-impl_execute_error!(DispenseError);
-impl_execute_error!(EnergyStorageError);
-impl_execute_error!(FanSpeedError);
-impl_execute_error!(InputSelectorError);
-impl_execute_error!(LockUnlockError);
-impl_execute_error!(NetworkControlError);
-impl_execute_error!(OpenCloseError);
+impl From<FanSpeedError> for ExecuteError {
+    fn from(x: FanSpeedError) -> Self {
+        match x {
+            FanSpeedError::Device(e) => Self::Serializable(Box::new(e)),
+            FanSpeedError::Other(e) => e.into(),
+        }
+    }
+}
+
+impl From<InputSelectorError> for ExecuteError {
+    fn from(x: InputSelectorError) -> Self {
+        match x {
+            InputSelectorError::Device(e) => Self::Serializable(Box::new(e)),
+            InputSelectorError::Other(e) => e.into(),
+        }
+    }
+}
+
+impl From<LockUnlockError> for ExecuteError {
+    fn from(x: LockUnlockError) -> Self {
+        match x {
+            LockUnlockError::Device(e) => Self::Serializable(Box::new(e)),
+            LockUnlockError::Other(e) => e.into(),
+        }
+    }
+}
+
+impl From<NetworkControlError> for ExecuteError {
+    fn from(x: NetworkControlError) -> Self {
+        match x {
+            NetworkControlError::Device(e) => Self::Serializable(Box::new(e)),
+            NetworkControlError::Other(e) => e.into(),
+        }
+    }
+}
+
+impl From<OpenCloseError> for ExecuteError {
+    fn from(x: OpenCloseError) -> Self {
+        match x {
+            OpenCloseError::Device(e) => Self::Serializable(Box::new(e)),
+            OpenCloseError::OpenClose(e) => e.into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::ExecuteError;
+    use crate::traits::dispense::{DeviceError, DeviceException, DispenseError};
+    use crate::traits::energy_storage::EnergyStorageError;
+    use crate::traits::fan_speed::{self, FanSpeedError};
+    use crate::traits::input_selector::InputSelectorError;
+    use crate::traits::lock_unlock::LockUnlockError;
+    use crate::traits::network_control::NetworkControlError;
+    use crate::{CombinedDeviceError, ErrorCode, SerializableError};
+
+    fn server_error() -> CombinedDeviceError {
+        CombinedDeviceError::Other(SerializableError(Box::new(ErrorCode::from("connectionRefused"))))
+    }
+
+    #[test]
+    fn test_dispense_device_error() {
+        let error = ExecuteError::from(DispenseError::Error(DeviceError::DispenseAmountAboveLimit));
+        assert!(matches!(error, ExecuteError::Serializable(e) if e.to_string() == "DispenseAmountAboveLimit"));
+
+        let error = ExecuteError::from(DispenseError::Exception(DeviceException::AmountRemainingLow));
+        assert!(matches!(error, ExecuteError::Serializable(e) if e.to_string() == "AmountRemainingLow"));
+    }
+
+    #[test]
+    fn test_dispense_server_error() {
+        assert!(is_server_error(DispenseError::Other(server_error()).into()));
+    }
+
+    fn is_server_error(error: ExecuteError) -> bool {
+        matches!(error, ExecuteError::Server(e) if e.to_string() == "connectionRefused")
+    }
+
+    #[test]
+    fn test_fan_speed_errors() {
+        let error = ExecuteError::from(FanSpeedError::Device(fan_speed::DeviceError::MaxSpeedReached));
+        assert!(matches!(error, ExecuteError::Serializable(e) if e.to_string() == "MaxSpeedReached"));
+        assert!(is_server_error(FanSpeedError::Other(server_error()).into()));
+    }
+
+    #[test]
+    fn test_server_errors() {
+        assert!(is_server_error(EnergyStorageError::Other(server_error()).into()));
+        assert!(is_server_error(InputSelectorError::Other(server_error()).into()));
+        assert!(is_server_error(LockUnlockError::Other(server_error()).into()));
+        assert!(is_server_error(NetworkControlError::Other(server_error()).into()));
+    }
+}
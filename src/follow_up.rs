@@ -0,0 +1,163 @@
+//! Follow-up responses for commands which complete after the EXECUTE response was sent,
+//! such as a network speed test or a lock which takes a while to engage.
+//!
+//! Google sends a `followUpToken` with such commands. Once the command completes,
+//! report the result to the HomeGraph API (`devices:reportStateAndNotification`) with a [FollowUpNotification].
+//!
+//! ## See also
+//! <https://developers.google.com/assistant/smarthome/develop/notifications#follow-up-response>
+
+use crate::{ErrorCode, ToStringError, Trait};
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// The outcome of a command reported in a follow-up response
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum FollowUpStatus {
+    Success,
+    Failure,
+}
+
+/// The result of a command which completed after the EXECUTE response.
+///
+/// ```
+/// # use homelander::follow_up::FollowUpResponse;
+/// let response = FollowUpResponse::success("token".to_string())
+///     .download_speed_mbps(123.45)
+///     .upload_speed_mbps(67.89);
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FollowUpResponse {
+    status: FollowUpStatus,
+    follow_up_token: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error_code: Option<ErrorCode>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    is_locked: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    is_armed: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    current_arm_level: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    download_speed_mbps: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    upload_speed_mbps: Option<f32>,
+}
+
+impl FollowUpResponse {
+    fn new(status: FollowUpStatus, follow_up_token: String, error_code: Option<ErrorCode>) -> Self {
+        Self {
+            status,
+            follow_up_token,
+            error_code,
+            is_locked: None,
+            is_armed: None,
+            current_arm_level: None,
+            download_speed_mbps: None,
+            upload_speed_mbps: None,
+        }
+    }
+
+    /// The command with `follow_up_token` completed successfully
+    pub fn success(follow_up_token: String) -> Self {
+        Self::new(FollowUpStatus::Success, follow_up_token, None)
+    }
+
+    /// The command with `follow_up_token` failed with `error`.
+    /// The error is reported with the same error code as it would have been in an EXECUTE response.
+    pub fn failure<E: ToStringError>(follow_up_token: String, error: E) -> Self {
+        Self::new(FollowUpStatus::Failure, follow_up_token, Some(ErrorCode::from_trait_error(&error)))
+    }
+
+    /// LockUnlock: Whether the device is locked after the command
+    pub fn is_locked(mut self, is_locked: bool) -> Self {
+        self.is_locked = Some(is_locked);
+        self
+    }
+
+    /// ArmDisarm: Whether the device is armed after the command
+    pub fn is_armed(mut self, is_armed: bool) -> Self {
+        self.is_armed = Some(is_armed);
+        self
+    }
+
+    /// ArmDisarm: The level the device is armed to after the command
+    pub fn current_arm_level(mut self, current_arm_level: String) -> Self {
+        self.current_arm_level = Some(current_arm_level);
+        self
+    }
+
+    /// NetworkControl: The measured download speed
+    pub fn download_speed_mbps(mut self, download_speed_mbps: f32) -> Self {
+        self.download_speed_mbps = Some(download_speed_mbps);
+        self
+    }
+
+    /// NetworkControl: The measured upload speed
+    pub fn upload_speed_mbps(mut self, upload_speed_mbps: f32) -> Self {
+        self.upload_speed_mbps = Some(upload_speed_mbps);
+        self
+    }
+}
+
+/// The request body for the HomeGraph `devices:reportStateAndNotification` API carrying a [FollowUpResponse]
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FollowUpNotification {
+    request_id: String,
+    agent_user_id: String,
+    payload: NotificationPayload,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+struct NotificationPayload {
+    devices: NotificationDevices,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+struct NotificationDevices {
+    notifications: HashMap<String, HashMap<String, TraitNotification>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct TraitNotification {
+    priority: i32,
+    follow_up_response: FollowUpResponse,
+}
+
+impl FollowUpNotification {
+    /// Create the notification for the command on the device with ID `device_id`, which belongs to `device_trait`.
+    /// `request_id` identifies the HomeGraph request and may be chosen freely.
+    pub fn new(request_id: String, agent_user_id: String, device_id: String, device_trait: Trait, response: FollowUpResponse) -> Self {
+        let notification = TraitNotification {
+            priority: 0,
+            follow_up_response: response,
+        };
+
+        Self {
+            request_id,
+            agent_user_id,
+            payload: NotificationPayload {
+                devices: NotificationDevices {
+                    notifications: HashMap::from([(device_id, HashMap::from([(trait_name(&device_trait), notification)]))]),
+                },
+            },
+        }
+    }
+}
+
+/// The name of a trait as used in notifications, e.g. `NetworkControl`
+fn trait_name(device_trait: &Trait) -> String {
+    let name = serde_json::to_value(device_trait)
+        .ok()
+        .and_then(|v| v.as_str().map(str::to_string))
+        .unwrap_or_default();
+
+    match name.strip_prefix("action.devices.traits.") {
+        Some(name) => name.to_string(),
+        None => name,
+    }
+}
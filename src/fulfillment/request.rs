@@ -1,9 +1,14 @@
-use serde::Deserialize;
+use serde::{Deserialize, Deserializer};
 
+/// Requests are parsed leniently, as Google adds fields to them over time:
+/// - Unknown fields are ignored.
+/// - Intents and commands without parameters may still have an (empty) `payload` or `params`.
+/// - Unknown commands are parsed as [CommandType::Unknown](execute::CommandType::Unknown).
 #[derive(Debug, PartialEq, Deserialize)]
 pub struct Request {
     #[serde(rename = "requestId")]
     pub request_id: String,
+    #[serde(deserialize_with = "deserialize_inputs")]
     pub inputs: Vec<Input>,
 }
 
@@ -16,9 +21,39 @@ pub enum Input {
     Query(query::Payload),
     #[serde(rename = "action.devices.SYNC")]
     Sync,
+    #[serde(rename = "action.devices.DISCONNECT")]
     Disconnect,
 }
 
+fn deserialize_inputs<'de, D>(deserializer: D) -> Result<Vec<Input>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Vec::<serde_json::Value>::deserialize(deserializer)?
+        .iter()
+        .map(|input| without_empty_content::<Input>(input, "payload").map_err(serde::de::Error::custom))
+        .collect()
+}
+
+/// Deserialize an adjacently tagged enum from `value`.
+/// Unit variants don't accept content, so if parsing fails and the `content` field is empty (`{}` or `null`), it is retried without it.
+fn without_empty_content<T: serde::de::DeserializeOwned>(value: &serde_json::Value, content: &str) -> Result<T, serde_json::Error> {
+    let error = match T::deserialize(value) {
+        Ok(t) => return Ok(t),
+        Err(e) => e,
+    };
+
+    let is_empty = |content: &serde_json::Value| content.is_null() || content.as_object().map(|x| x.is_empty()).unwrap_or(false);
+    match value.as_object() {
+        Some(object) if object.get(content).map(is_empty).unwrap_or(false) => {
+            let mut object = object.clone();
+            object.remove(content);
+            T::deserialize(serde_json::Value::Object(object)).map_err(|_| error)
+        }
+        _ => Err(error),
+    }
+}
+
 pub mod query {
     use serde::Deserialize;
 
@@ -39,24 +74,96 @@ pub mod execute {
     use crate::traits::cook::CookingMode;
     use crate::traits::open_close::OpenDirection;
     use crate::traits::temperature_setting::ThermostatMode;
-    use crate::traits::{Language, SizeUnit};
-    use serde::Deserialize;
+    use crate::traits::{Challenge, Language, SizeUnit};
+    use crate::Trait;
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer};
     use std::collections::HashMap;
 
     #[derive(Debug, PartialEq, Deserialize)]
     pub struct Execute {
         pub commands: Vec<Command>,
+        /// Data about the structure the devices are in, passed through as-is
+        #[serde(rename = "structureData", default)]
+        pub structure_data: Option<serde_json::Value>,
     }
 
     #[derive(Debug, PartialEq, Deserialize)]
     pub struct Command {
         pub devices: Vec<Device>,
-        pub execution: Vec<CommandType>,
+        pub execution: Vec<Execution>,
+    }
+
+    #[derive(Clone, Debug, PartialEq)]
+    pub struct Execution {
+        pub command: CommandType,
+        /// Secondary user verification, if a trait asked for it
+        pub challenge: Option<Challenge>,
+    }
+
+    impl<'de> Deserialize<'de> for Execution {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            /// An execution of a command modelled by [CommandType]
+            #[derive(Deserialize)]
+            struct Known {
+                #[serde(flatten)]
+                command: CommandType,
+                challenge: Option<Challenge>,
+            }
+
+            /// An execution of any command, used if Google sends a command Homelander does not know (yet)
+            #[derive(Deserialize)]
+            struct Unknown {
+                command: String,
+                #[serde(default)]
+                params: serde_json::Value,
+                challenge: Option<Challenge>,
+            }
+
+            let value = serde_json::Value::deserialize(deserializer)?;
+            let error = match super::without_empty_content::<Known>(&value, "params") {
+                Ok(known) => {
+                    return Ok(Self {
+                        command: known.command,
+                        challenge: known.challenge,
+                    })
+                }
+                Err(e) => e,
+            };
+
+            let unknown = Unknown::deserialize(&value).map_err(D::Error::custom)?;
+            // A known command with invalid params is still an error
+            if CommandType::is_known(&unknown.command) {
+                return Err(D::Error::custom(error));
+            }
+
+            Ok(Self {
+                command: CommandType::Unknown {
+                    command: unknown.command,
+                    params: unknown.params,
+                },
+                challenge: unknown.challenge,
+            })
+        }
     }
 
     #[derive(Debug, PartialEq, Eq, Deserialize)]
     pub struct Device {
         pub id: String,
+        /// The custom data provided by the device during SYNC
+        #[serde(rename = "customData")]
+        pub custom_data: Option<serde_json::Value>,
+    }
+
+    impl Device {
+        /// Deserialize the custom data into the type it was created from during SYNC.
+        /// Returns `None` if Google sent no custom data for this device.
+        pub fn custom_data_as<T: serde::de::DeserializeOwned>(&self) -> Option<Result<T, serde_json::Error>> {
+            self.custom_data.as_ref().map(|custom_data| T::deserialize(custom_data))
+        }
     }
 
     fn locate_default_lang() -> Language {
@@ -154,7 +261,7 @@ pub mod execute {
         #[serde(rename = "action.devices.commands.relativeChannel")]
         RelativeChannel {
             /// The number of channels to increase or decrease.
-            #[serde(rename = "relativeChannelChange	")]
+            #[serde(rename = "relativeChannelChange")]
             relative_channel_change: i32,
         },
         /// Return to the last/previous channel the user was on.
@@ -178,7 +285,7 @@ pub mod execute {
             #[serde(rename = "foodPreset")]
             food_preset: Option<String>,
             /// The quantity of the food requested by the user.
-            quantity: Option<i32>,
+            quantity: Option<f32>,
             /// The unit associated with the quantity, from supported_units attribute.
             unit: Option<SizeUnit>,
         },
@@ -205,6 +312,7 @@ pub mod execute {
             charge: bool,
         },
         /// Set speed.
+        /// If both `fan_speed` and `fan_speed_percent` are present, only `fan_speed` is applied.
         #[serde(rename = "action.devices.commands.SetFanSpeed")]
         SetFanSpeed {
             /// The requested speed settings of the fan.
@@ -229,6 +337,8 @@ pub mod execute {
         #[serde(rename = "action.devices.commands.Reverse")]
         Reverse,
         /// Fill or drain the device.
+        /// When draining, `fill_level` and `fill_percent` are ignored.
+        /// If both `fill_level` and `fill_percent` are present, only `fill_level` is applied.
         #[serde(rename = "action.devices.commands.Fill")]
         Fill {
             /// True to fill, false to drain.
@@ -285,7 +395,7 @@ pub mod execute {
         #[serde(rename = "action.devices.commands.StopEffect")]
         StopEffect,
         /// Gradually increase the device's brightness and, optionally, adjusts the color temperature over a duration of time.
-        #[serde(rename = "actin.devices.commands.Wake")]
+        #[serde(rename = "action.devices.commands.Wake")]
         Wake {
             /// Duration for the wake command, in seconds.
             duration: Option<i32>,
@@ -368,7 +478,7 @@ pub mod execute {
         #[serde(rename = "action.devices.commands.OpenCloseRelative")]
         OpenCloseRelative {
             /// The exact percentage to change open-close state. Ambigous relative commands will be converted to an exact percentage parameter (for example, "Open the blinds a little more" vs "Open the blinds by 5%").
-            #[serde(rename = "oopenRelativePercent")]
+            #[serde(rename = "openRelativePercent")]
             open_relative_percent: f32,
             /// Direction in which to open. Only present if device supports multiple directions, as indicated by the openDirection attribute, and a direction is specified by the user.
             #[serde(rename = "openDirection")]
@@ -378,6 +488,7 @@ pub mod execute {
         #[serde(rename = "action.devices.commands.Reboot")]
         Reboot,
         /// Set the absolute rotation of the device.
+        /// If both `rotation_degrees` and `rotation_percent` are present, only `rotation_degrees` is applied.
         #[serde(rename = "action.devices.commands.RotationAbsolute")]
         RotationAbsolute {
             /// An absolute value, in degrees, that specifies the final clockwise rotation of the device. Value must fall within rotationDegreesRange attribute.
@@ -483,7 +594,7 @@ pub mod execute {
             #[serde(rename = "updateToggleSettings")]
             update_toggle_settings: HashMap<String, bool>,
         },
-        /// Pause media playback.
+        /// Stop media playback.
         #[serde(rename = "action.devices.commands.mediaStop")]
         MediaStop,
         /// Skip to next media item.
@@ -513,7 +624,7 @@ pub mod execute {
             abs_position_ms: i32,
         },
         /// Set repeat playback mode.
-        #[serde(rename = "action.devices.commands.")]
+        #[serde(rename = "action.devices.commands.mediaRepeatMode")]
         MediaRepeatMode {
             /// True to turn on repeat mode, false to turn off repeat mode.
             #[serde(rename = "isOn")]
@@ -561,6 +672,105 @@ pub mod execute {
             #[serde(rename = "relativeSteps")]
             relative_steps: i32,
         },
+        /// A command Homelander does not know (yet). Executing it fails with `commandDispatchFailed`.
+        #[serde(skip_deserializing)]
+        Unknown {
+            /// The name of the command, e.g. `action.devices.commands.OnOff`
+            command: String,
+            /// The parameters of the command
+            params: serde_json::Value,
+        },
+    }
+
+    /// The error serde reports when deserializing [CommandType], capturing the command names it expected
+    #[derive(Debug)]
+    struct ExpectedCommands(&'static [&'static str]);
+
+    impl std::fmt::Display for ExpectedCommands {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "expected one of {:?}", self.0)
+        }
+    }
+
+    impl std::error::Error for ExpectedCommands {}
+
+    impl Error for ExpectedCommands {
+        fn custom<T: std::fmt::Display>(_msg: T) -> Self {
+            Self(&[])
+        }
+
+        fn unknown_variant(_variant: &str, expected: &'static [&'static str]) -> Self {
+            Self(expected)
+        }
+    }
+
+    impl CommandType {
+        /// Whether `command` is the name of a command modelled by [CommandType], e.g. `action.devices.commands.OnOff`
+        pub(crate) fn is_known(command: &str) -> bool {
+            // Deserializing a command without a name makes serde list the names of all commands
+            let deserializer = serde::de::value::MapDeserializer::<_, ExpectedCommands>::new(std::iter::once(("command", "")));
+            match CommandType::deserialize(deserializer) {
+                Err(ExpectedCommands(expected)) => expected.contains(&command),
+                Ok(_) => false,
+            }
+        }
+
+        /// The trait this command belongs to, `None` for [CommandType::Unknown]
+        pub fn device_trait(&self) -> Option<Trait> {
+            let device_trait = match self {
+                Self::AppInstall { .. } | Self::AppSearch { .. } | Self::AppSelect { .. } => Trait::AppSelector,
+                Self::ArmDisarm { .. } => Trait::ArmDisarm,
+                Self::BrightnessAbsolute { .. } | Self::BrightnessRelative { .. } => Trait::Brightness,
+                Self::GetCameraStream { .. } => Trait::CameraStream,
+                Self::SelectChannel { .. } | Self::RelativeChannel { .. } | Self::ReturnChannel => Trait::Channel,
+                Self::ColorAbsolute { .. } => Trait::ColorSetting,
+                Self::Cook { .. } => Trait::Cook,
+                Self::Dispense { .. } => Trait::Dispense,
+                Self::Dock => Trait::Dock,
+                Self::Charge { .. } => Trait::EnergyStorage,
+                Self::SetFanSpeed { .. } | Self::SetFanSpeedRelative { .. } | Self::Reverse => Trait::FanSpeed,
+                Self::Fill { .. } => Trait::Fill,
+                Self::SetHumidity { .. } | Self::HumidityRelative { .. } => Trait::HumiditySetting,
+                Self::SetInput { .. } | Self::NextInput | Self::PreviousInput => Trait::InputSelector,
+                Self::ColorLoop { .. } | Self::Sleep { .. } | Self::StopEffect | Self::Wake { .. } => Trait::LightEffects,
+                Self::Locate { .. } => Trait::Locator,
+                Self::LockUnlock { .. } => Trait::LockUnlock,
+                Self::SetModes { .. } => Trait::Modes,
+                Self::EnableDisableGuestNetwork { .. }
+                | Self::EnableDisableNetworkProfile { .. }
+                | Self::GetGuestNetworkPassword
+                | Self::TestNetworkSpeed { .. } => Trait::NetworkControl,
+                Self::OnOff { .. } => Trait::OnOff,
+                Self::OpenClose { .. } | Self::OpenCloseRelative { .. } => Trait::OpenClose,
+                Self::Reboot => Trait::Reboot,
+                Self::RotationAbsolute { .. } => Trait::Rotation,
+                Self::ActivateScene { .. } => Trait::Scene,
+                Self::SoftwareUpdate => Trait::SoftwareUpdate,
+                Self::StartStop { .. } | Self::PauseUnpause { .. } => Trait::StartStop,
+                Self::SetTemperature { .. } => Trait::TemperatureControl,
+                Self::ThermostatTemperatureSetpoint { .. }
+                | Self::ThermostatTemperatureSetRange { .. }
+                | Self::ThermostatSetMode { .. }
+                | Self::TemperatureRelative { .. } => Trait::TemperatureSetting,
+                Self::TimerStart { .. } | Self::TimerAdjust { .. } | Self::TimerPause | Self::TimerResume | Self::TimerCancel => Trait::Timer,
+                Self::SetToggles { .. } => Trait::Toggles,
+                Self::MediaStop
+                | Self::MediaNext
+                | Self::MediaPrevious
+                | Self::MediaPause
+                | Self::MediaResume
+                | Self::MediaSeekRelative { .. }
+                | Self::MediaSeekToPosition { .. }
+                | Self::MediaRepeatMode { .. }
+                | Self::MediaShuffle
+                | Self::MediaClosedCaptioningOn { .. }
+                | Self::MediaClosedCaptioningOff => Trait::TransportControl,
+                Self::Mute { .. } | Self::SetVolume { .. } | Self::VolumeRelative { .. } => Trait::Volume,
+                Self::Unknown { .. } => return None,
+            };
+
+            Some(device_trait)
+        }
     }
 }
 
@@ -571,7 +781,7 @@ mod test {
 
     #[test]
     fn test_execute_payload() {
-        use crate::fulfillment::request::execute::{Command, Device, Execute};
+        use crate::fulfillment::request::execute::{Command, Device, Execute, Execution};
 
         let payload = r#"
             {
@@ -615,9 +825,26 @@ mod test {
             request_id: "ff36a3cc-ec34-11e6-b1a0-64510650abcf".to_string(),
             inputs: vec![Input::Execute(Execute {
                 commands: vec![Command {
-                    devices: vec![Device { id: "123".to_string() }, Device { id: "456".to_string() }],
-                    execution: vec![OnOff { on: true }],
+                    devices: vec![
+                        Device {
+                            id: "123".to_string(),
+                            custom_data: Some(serde_json::json!({
+                                "fooValue": 74,
+                                "barValue": true,
+                                "bazValue": "sheepdip"
+                            })),
+                        },
+                        Device {
+                            id: "456".to_string(),
+                            custom_data: None,
+                        },
+                    ],
+                    execution: vec![Execution {
+                        command: OnOff { on: true },
+                        challenge: None,
+                    }],
                 }],
+                structure_data: None,
             })],
         };
 
@@ -625,4 +852,348 @@ mod test {
         let payload = deserialized.unwrap();
         assert_eq!(request, payload);
     }
+
+    #[test]
+    fn test_unknown_command() {
+        use crate::fulfillment::request::execute::Execution;
+
+        let execution = serde_json::from_str::<Execution>(r#"{ "command": "action.devices.commands.Levitate", "params": { "heightCm": 30 } }"#).unwrap();
+        assert_eq!(
+            execution.command,
+            crate::CommandType::Unknown {
+                command: "action.devices.commands.Levitate".to_string(),
+                params: serde_json::json!({ "heightCm": 30 }),
+            }
+        );
+
+        let execution = serde_json::from_str::<Execution>(r#"{ "command": "action.devices.commands.Reboot" }"#).unwrap();
+        assert_eq!(execution.command, crate::CommandType::Reboot);
+    }
+
+    #[test]
+    fn test_malformed_params() {
+        use crate::fulfillment::request::execute::Execution;
+
+        // Every parameter of Locate has a default, malformed params must not fall back to them
+        let execution = serde_json::from_str::<Execution>(r#"{ "command": "action.devices.commands.Locate", "params": { "silence": "yes" } }"#);
+        assert!(execution.is_err());
+
+        let execution = serde_json::from_str::<Execution>(r#"{ "command": "action.devices.commands.OnOff", "params": { "on": "yes" } }"#);
+        assert!(execution.is_err());
+
+        for params in ["{}", "null"] {
+            let execution = serde_json::from_str::<Execution>(&format!(r#"{{ "command": "action.devices.commands.Reboot", "params": {params} }}"#)).unwrap();
+            assert_eq!(execution.command, crate::CommandType::Reboot);
+        }
+    }
+
+    #[test]
+    fn test_command_names() {
+        use crate::fulfillment::request::execute::{CommandType, Execution};
+        use crate::traits::camera_stream::CameraStreamProtocol;
+        use crate::traits::color_setting::ColorCommand;
+        use crate::traits::cook::CookingMode;
+        use crate::traits::open_close::OpenDirection;
+        use crate::traits::temperature_setting::ThermostatMode;
+        use crate::traits::{Language, SizeUnit};
+        use serde_json::json;
+        use std::collections::HashMap;
+
+        let commands = [
+            (
+                json!({ "command": "action.devices.commands.appInstall", "params": { "newApplication": "youtube", "newApplicationName": "YouTube" } }),
+                CommandType::AppInstall {
+                    new_application: Some("youtube".to_string()),
+                    new_application_name: Some("YouTube".to_string()),
+                },
+            ),
+            (
+                json!({ "command": "action.devices.commands.appSearch", "params": { "newApplicationName": "YouTube" } }),
+                CommandType::AppSearch {
+                    new_application: None,
+                    new_application_name: Some("YouTube".to_string()),
+                },
+            ),
+            (
+                json!({ "command": "action.devices.commands.appSelect", "params": { "newApplication": "youtube" } }),
+                CommandType::AppSelect {
+                    new_application: Some("youtube".to_string()),
+                    new_application_name: None,
+                },
+            ),
+            (
+                json!({ "command": "action.devices.commands.ArmDisarm", "params": { "arm": true, "cancel": false, "armLevel": "L2", "followUpToken": "token" } }),
+                CommandType::ArmDisarm {
+                    follow_up_token: Some("token".to_string()),
+                    arm: true,
+                    cancel: Some(false),
+                    arm_level: Some("L2".to_string()),
+                },
+            ),
+            (
+                json!({ "command": "action.devices.commands.BrightnessAbsolute", "params": { "brightness": 65 } }),
+                CommandType::BrightnessAbsolute { brightness: 65 },
+            ),
+            (
+                json!({ "command": "action.devices.commands.BrightnessRelative", "params": { "brightnessRelativePercent": 10, "brightnessRelativeWeight": 2 } }),
+                CommandType::BrightnessRelative {
+                    brightness_relative_percent: Some(10),
+                    brightness_relative_weight: Some(2),
+                },
+            ),
+            (
+                json!({ "command": "action.devices.commands.GetCameraStream", "params": { "StreamToChromecast": true, "SupportedStreamProtocols": ["hls", "dash"] } }),
+                CommandType::GetCameraStream {
+                    stream_to_chromecast: true,
+                    supported_stream_protocols: vec![CameraStreamProtocol::Hls, CameraStreamProtocol::Dash],
+                },
+            ),
+            (
+                json!({ "command": "action.devices.commands.selectChannel", "params": { "channelCode": "cnn", "channelName": "CNN", "channelNumber": "200" } }),
+                CommandType::SelectChannel {
+                    channel_code: Some("cnn".to_string()),
+                    channel_name: Some("CNN".to_string()),
+                    channel_number: Some("200".to_string()),
+                },
+            ),
+            (
+                json!({ "command": "action.devices.commands.relativeChannel", "params": { "relativeChannelChange": -1 } }),
+                CommandType::RelativeChannel { relative_channel_change: -1 },
+            ),
+            (json!({ "command": "action.devices.commands.returnChannel" }), CommandType::ReturnChannel),
+            (
+                json!({ "command": "action.devices.commands.ColorAbsolute", "params": { "color": { "spectrumRGB": 16711935 } } }),
+                CommandType::ColorAbsolute {
+                    color: ColorCommand::SpectrumRgb(16711935),
+                },
+            ),
+            (
+                json!({ "command": "action.devices.commands.Cook", "params": { "start": true, "cookingMode": "BOIL", "foodPreset": "white_rice", "quantity": 2, "unit": "CUPS" } }),
+                CommandType::Cook {
+                    start: true,
+                    cooking_mode: Some(CookingMode::Boil),
+                    food_preset: Some("white_rice".to_string()),
+                    quantity: Some(2.0),
+                    unit: Some(SizeUnit::Cups),
+                },
+            ),
+            (
+                json!({ "command": "action.devices.commands.Dispense", "params": { "item": "water", "amount": 2, "unit": "CUPS" } }),
+                CommandType::Dispense {
+                    item: Some("water".to_string()),
+                    amount: Some(2),
+                    unit: Some(SizeUnit::Cups),
+                    preset_name: None,
+                },
+            ),
+            (json!({ "command": "action.devices.commands.Dock" }), CommandType::Dock),
+            (json!({ "command": "action.devices.commands.Charge", "params": { "charge": true } }), CommandType::Charge { charge: true }),
+            (
+                json!({ "command": "action.devices.commands.SetFanSpeed", "params": { "fanSpeed": "speed_low" } }),
+                CommandType::SetFanSpeed {
+                    fan_speed: Some("speed_low".to_string()),
+                    fan_speed_percent: None,
+                },
+            ),
+            (
+                json!({ "command": "action.devices.commands.SetFanSpeedRelative", "params": { "fanSpeedRelativeWeight": -1, "fanSpeedRelativePercent": 20 } }),
+                CommandType::SetFanSpeedRelative {
+                    fan_speed_relative_weight: Some(-1),
+                    fan_speed_relative_percent: Some(20.0),
+                },
+            ),
+            (json!({ "command": "action.devices.commands.Reverse" }), CommandType::Reverse),
+            (
+                json!({ "command": "action.devices.commands.Fill", "params": { "fill": true, "fillLevel": "half", "fillPercent": 50 } }),
+                CommandType::Fill {
+                    fill: true,
+                    fill_level: Some("half".to_string()),
+                    fill_percent: Some(50.0),
+                },
+            ),
+            (json!({ "command": "action.devices.commands.SetHumidity", "params": { "humidity": 45 } }), CommandType::SetHumidity { humidity: 45 }),
+            (
+                json!({ "command": "action.devices.commands.HumidityRelative", "params": { "humidityRelativePercent": 10, "humidityRelativeWeight": 1 } }),
+                CommandType::HumidityRelative {
+                    humidity_relative_percent: Some(10),
+                    humidity_relative_weight: Some(1),
+                },
+            ),
+            (
+                json!({ "command": "action.devices.commands.SetInput", "params": { "newInput": "hdmi_1" } }),
+                CommandType::SetInput {
+                    new_input: "hdmi_1".to_string(),
+                },
+            ),
+            (json!({ "command": "action.devices.commands.NextInput" }), CommandType::NextInput),
+            (json!({ "command": "action.devices.commands.PreviousInput" }), CommandType::PreviousInput),
+            (json!({ "command": "action.devices.commands.ColorLoop", "params": { "duration": 60 } }), CommandType::ColorLoop { duration: Some(60) }),
+            (json!({ "command": "action.devices.commands.Sleep", "params": { "duration": 1800 } }), CommandType::Sleep { duration: Some(1800) }),
+            (json!({ "command": "action.devices.commands.StopEffect" }), CommandType::StopEffect),
+            (json!({ "command": "action.devices.commands.Wake", "params": { "duration": 1800 } }), CommandType::Wake { duration: Some(1800) }),
+            (
+                json!({ "command": "action.devices.commands.Locate", "params": { "silence": true, "lang": "nl" } }),
+                CommandType::Locate {
+                    silence: true,
+                    lang: Language::Dutch,
+                },
+            ),
+            (
+                json!({ "command": "action.devices.commands.LockUnlock", "params": { "lock": true, "followUpToken": "token" } }),
+                CommandType::LockUnlock {
+                    lock: true,
+                    follow_up_token: "token".to_string(),
+                },
+            ),
+            (
+                json!({ "command": "action.devices.commands.SetModes", "params": { "updateModeSettings": { "load": "small" } } }),
+                CommandType::SetModes {
+                    update_mode_settings: HashMap::from([("load".to_string(), "small".to_string())]),
+                },
+            ),
+            (
+                json!({ "command": "action.devices.commands.EnableDisableGuestNetwork", "params": { "enable": true } }),
+                CommandType::EnableDisableGuestNetwork { enable: true },
+            ),
+            (
+                json!({ "command": "action.devices.commands.EnableDisableNetworkProfile", "params": { "profile": "kids", "enable": false } }),
+                CommandType::EnableDisableNetworkProfile {
+                    profile: "kids".to_string(),
+                    enable: false,
+                },
+            ),
+            (json!({ "command": "action.devices.commands.GetGuestNetworkPassword" }), CommandType::GetGuestNetworkPassword),
+            (
+                json!({ "command": "action.devices.commands.TestNetworkSpeed", "params": { "testDownloadSpeed": true, "testUploadSpeed": false, "followUpToken": "token" } }),
+                CommandType::TestNetworkSpeed {
+                    test_download_speed: true,
+                    test_upload_speed: false,
+                    follow_up_token: "token".to_string(),
+                },
+            ),
+            (json!({ "command": "action.devices.commands.OnOff", "params": { "on": true } }), CommandType::OnOff { on: true }),
+            (
+                json!({ "command": "action.devices.commands.OpenClose", "params": { "openPercent": 50, "openDirection": "UP" } }),
+                CommandType::OpenClose {
+                    open_percent: 50.0,
+                    open_direction: Some(OpenDirection::Up),
+                },
+            ),
+            (
+                json!({ "command": "action.devices.commands.OpenCloseRelative", "params": { "openRelativePercent": 20 } }),
+                CommandType::OpenCloseRelative {
+                    open_relative_percent: 20.0,
+                    open_direction: None,
+                },
+            ),
+            (json!({ "command": "action.devices.commands.Reboot" }), CommandType::Reboot),
+            (
+                json!({ "command": "action.devices.commands.RotationAbsolute", "params": { "rotationDegrees": 90 } }),
+                CommandType::RotationAbsolute {
+                    rotation_degrees: Some(90.0),
+                    rotation_percent: None,
+                },
+            ),
+            (
+                json!({ "command": "action.devices.commands.ActivateScene", "params": { "deactivate": false } }),
+                CommandType::ActivateScene { deactivate: false },
+            ),
+            (json!({ "command": "action.devices.commands.SoftwareUpdate" }), CommandType::SoftwareUpdate),
+            (
+                json!({ "command": "action.devices.commands.StartStop", "params": { "start": true, "multipleZones": ["kitchen", "hallway"] } }),
+                CommandType::StartStop {
+                    start: true,
+                    zone: None,
+                    multiple_zones: Some(vec!["kitchen".to_string(), "hallway".to_string()]),
+                },
+            ),
+            (json!({ "command": "action.devices.commands.PauseUnpause", "params": { "pause": true } }), CommandType::PauseUnpause { pause: true }),
+            (
+                json!({ "command": "action.devices.commands.SetTemperature", "params": { "temperature": 180 } }),
+                CommandType::SetTemperature { temperature: 180.0 },
+            ),
+            (
+                json!({ "command": "action.devices.commands.ThermostatTemperatureSetpoint", "params": { "thermostatTemperatureSetpoint": 22 } }),
+                CommandType::ThermostatTemperatureSetpoint {
+                    thermostat_temperature_setpoint: 22.0,
+                },
+            ),
+            (
+                json!({ "command": "action.devices.commands.ThermostatTemperatureSetRange", "params": { "thermostatTemperatureSetpointHigh": 26, "thermostatTemperatureSetpointLow": 20 } }),
+                CommandType::ThermostatTemperatureSetRange {
+                    thermostat_temperature_setpoint_high: 26.0,
+                    thermostat_temperature_setpoint_low: 20.0,
+                },
+            ),
+            (
+                json!({ "command": "action.devices.commands.ThermostatSetMode", "params": { "thermostatMode": "heatcool" } }),
+                CommandType::ThermostatSetMode {
+                    thermostat_mode: ThermostatMode::Heatcool,
+                },
+            ),
+            (
+                json!({ "command": "action.devices.commands.TemperatureRelative", "params": { "thermostatTemperatureRelativeDegree": -2, "thermostatTemperatureRelativeWeight": 1 } }),
+                CommandType::TemperatureRelative {
+                    thermostat_temperature_relative_degree: Some(-2.0),
+                    thermostat_temperature_relative_weight: Some(1.0),
+                },
+            ),
+            (json!({ "command": "action.devices.commands.TimerStart", "params": { "timerTimeSec": 300 } }), CommandType::TimerStart { timer_time_sec: 300 }),
+            (json!({ "command": "action.devices.commands.TimerAdjust", "params": { "timerTimeSec": -60 } }), CommandType::TimerAdjust { timer_time_sec: -60 }),
+            (json!({ "command": "action.devices.commands.TimerPause" }), CommandType::TimerPause),
+            (json!({ "command": "action.devices.commands.TimerResume" }), CommandType::TimerResume),
+            (json!({ "command": "action.devices.commands.TimerCancel" }), CommandType::TimerCancel),
+            (
+                json!({ "command": "action.devices.commands.SetToggles", "params": { "updateToggleSettings": { "sterilization": true } } }),
+                CommandType::SetToggles {
+                    update_toggle_settings: HashMap::from([("sterilization".to_string(), true)]),
+                },
+            ),
+            (json!({ "command": "action.devices.commands.mediaStop" }), CommandType::MediaStop),
+            (json!({ "command": "action.devices.commands.mediaNext" }), CommandType::MediaNext),
+            (json!({ "command": "action.devices.commands.mediaPrevious" }), CommandType::MediaPrevious),
+            (json!({ "command": "action.devices.commands.mediaPause" }), CommandType::MediaPause),
+            (json!({ "command": "action.devices.commands.mediaResume" }), CommandType::MediaResume),
+            (
+                json!({ "command": "action.devices.commands.mediaSeekRelative", "params": { "relativePositionMs": -30000 } }),
+                CommandType::MediaSeekRelative { relative_position_ms: -30000 },
+            ),
+            (
+                json!({ "command": "action.devices.commands.mediaSeekToPosition", "params": { "absPositionMs": 60000 } }),
+                CommandType::MediaSeekToPosition { abs_position_ms: 60000 },
+            ),
+            (
+                json!({ "command": "action.devices.commands.mediaRepeatMode", "params": { "isOn": true, "isSingle": true } }),
+                CommandType::MediaRepeatMode {
+                    is_on: true,
+                    is_single: Some(true),
+                },
+            ),
+            (json!({ "command": "action.devices.commands.mediaShuffle" }), CommandType::MediaShuffle),
+            (
+                json!({ "command": "action.devices.commands.mediaClosedCaptioningOn", "params": { "closedCaptioningLanguage": "en", "userQueryLanguage": "en" } }),
+                CommandType::MediaClosedCaptioningOn {
+                    closed_captioning_language: "en".to_string(),
+                    user_query_language: "en".to_string(),
+                },
+            ),
+            (json!({ "command": "action.devices.commands.mediaClosedCaptioningOff" }), CommandType::MediaClosedCaptioningOff),
+            (json!({ "command": "action.devices.commands.mute", "params": { "mute": true } }), CommandType::Mute { mute: true }),
+            (json!({ "command": "action.devices.commands.setVolume", "params": { "volumeLevel": 7 } }), CommandType::SetVolume { volume_level: 7 }),
+            (
+                json!({ "command": "action.devices.commands.volumeRelative", "params": { "relativeSteps": -2 } }),
+                CommandType::VolumeRelative { relative_steps: -2 },
+            ),
+        ];
+
+        for (execution, command) in commands {
+            let name = execution["command"].clone();
+            assert!(CommandType::is_known(name.as_str().unwrap()), "{name}");
+
+            let execution = serde_json::from_value::<Execution>(execution).unwrap();
+            assert_eq!(execution.command, command, "{name}");
+        }
+
+        assert!(!CommandType::is_known("action.devices.commands.Levitate"));
+    }
 }
@@ -1,4 +1,7 @@
-use serde::Serialize;
+use crate::error_code::ErrorCode;
+use convert_case::{Case, Casing};
+use serde::{Serialize, Serializer};
+use serde_json::{Map, Value};
 
 #[derive(Debug, PartialEq, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -8,15 +11,69 @@ pub struct Response {
 }
 
 #[derive(Debug, PartialEq, Serialize)]
+#[serde(untagged)]
 pub enum ResponsePayload {
     Sync(sync::Payload),
     Query(query::Payload),
     Execute(execute::Payload),
+    /// Google does not expect a payload for DISCONNECT, this is serialized as an empty object
+    #[serde(serialize_with = "serialize_empty_payload")]
     Disconnect,
+    Error(ErrorPayload),
+}
+
+/// The payload for a request which could not be handled at all, e.g. because it has no inputs
+#[derive(Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ErrorPayload {
+    pub error_code: ErrorCode,
+    pub debug_string: Option<String>,
+}
+
+impl Response {
+    /// Serialize the response as pretty-printed JSON, with the snake_case field names of Homelander instead of the camelCase names Google expects.
+    /// Google does not accept this form, it is only meant for logging and diagnostics.
+    pub fn to_debug_json(&self) -> serde_json::Result<String> {
+        let value = serde_json::to_value(self)?;
+        serde_json::to_string_pretty(&debug_keys(value))
+    }
+}
+
+/// Fields whose keys are data (e.g. device IDs or mode names) rather than field names. These keys are left intact by [Response::to_debug_json]
+const DATA_KEYED_FIELDS: &[&str] = &["devices", "currentModeSetting", "currentToggleSettings", "networkProfilesState"];
+
+/// Convert the keys of all objects in `value` to snake_case.
+fn debug_keys(value: Value) -> Value {
+    match value {
+        Value::Object(map) => Value::Object(
+            map.into_iter()
+                .map(|(key, value)| {
+                    let value = match (key.as_str(), value) {
+                        // Custom data is passed through from the device as-is
+                        ("customData", value) => value,
+                        (key, Value::Object(map)) if DATA_KEYED_FIELDS.contains(&key) => {
+                            Value::Object(map.into_iter().map(|(k, v)| (k, debug_keys(v))).collect::<Map<_, _>>())
+                        }
+                        (_, value) => debug_keys(value),
+                    };
+
+                    (key.to_case(Case::Snake), value)
+                })
+                .collect(),
+        ),
+        Value::Array(values) => Value::Array(values.into_iter().map(debug_keys).collect()),
+        value => value,
+    }
+}
+
+fn serialize_empty_payload<S: Serializer>(serializer: S) -> Result<S::Ok, S::Error> {
+    use serde::ser::SerializeMap;
+    serializer.serialize_map(Some(0))?.end()
 }
 
 pub mod sync {
     use crate::device_trait::Trait;
+    use crate::error_code::ErrorCode;
     use crate::traits::app_selector::AvailableApplication;
     use crate::traits::arm_disarm::AvailableArmLevels;
     use crate::traits::camera_stream::CameraStreamProtocol;
@@ -37,7 +94,7 @@ pub mod sync {
     use crate::traits::temperature_setting::ThermostatMode;
     use crate::traits::toggles::AvailableToggle;
     use crate::traits::transport_control::SupportedCommand;
-    use crate::traits::{TemperatureRange, TemperatureUnit};
+    use crate::traits::{OtherDeviceId, TemperatureRange, TemperatureUnit};
     use serde::Serialize;
 
     #[derive(Debug, PartialEq, Serialize)]
@@ -45,7 +102,7 @@ pub mod sync {
     pub struct Payload {
         pub agent_user_id: String,
         pub devices: Vec<Device>,
-        pub error_code: Option<String>,
+        pub error_code: Option<ErrorCode>,
         pub debug_string: Option<String>,
     }
 
@@ -58,100 +115,301 @@ pub mod sync {
         pub traits: Vec<Trait>,
         pub name: DeviceName,
         pub will_report_state: bool,
+        #[serde(skip_serializing_if = "std::ops::Not::not")]
+        pub notification_supported_by_agent: bool,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub room_hint: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub structure_hint: Option<String>,
         pub device_info: DeviceInfo,
         pub attributes: SyncAttributes,
+        #[serde(skip_serializing_if = "Vec::is_empty")]
+        pub other_device_ids: Vec<OtherDeviceId>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub custom_data: Option<serde_json::Value>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub error_code: Option<ErrorCode>,
     }
 
+    /// The attributes of all traits of a device. Attributes which are not set are left out of the response.
+    ///
+    /// Besides through its fields, attributes can be set with `with_*` methods:
+    ///
+    /// ```
+    /// # use homelander::fulfillment::response::sync::SyncAttributes;
+    /// let attributes = SyncAttributes::default()
+    ///     .with_command_only_on_off(false)
+    ///     .with_volume_max_level(100);
+    /// ```
     #[derive(Debug, PartialEq, Serialize, Default)]
     #[serde(rename_all = "camelCase")]
     pub struct SyncAttributes {
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub available_applications: Option<Vec<AvailableApplication>>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub available_arm_levels: Option<AvailableArmLevels>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub command_only_brightness: Option<bool>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub camera_stream_supported_protocols: Option<Vec<CameraStreamProtocol>>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub camera_stream_need_auth_token: Option<bool>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub available_channels: Option<Vec<AvailableChannel>>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub command_only_channels: Option<bool>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub command_only_color_setting: Option<bool>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub color_model: Option<ColorModel>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub color_temperature_range: Option<ColorTemperatureRange>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub supported_cooking_modes: Option<Vec<CookingMode>>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub food_presets: Option<Vec<FoodPreset>>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub supported_dispense_items: Option<Vec<DispenseItem>>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub supported_dispense_presets: Option<Vec<DispensePreset>>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub query_only_energy_storage: Option<bool>,
-        #[serde(rename = "energyStorageDistanceUnitForUX")]
+        #[serde(rename = "energyStorageDistanceUnitForUX", skip_serializing_if = "Option::is_none")]
         pub energy_storage_distance_unit_for_ux: Option<UxDistanceUnit>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub is_rechargeable: Option<bool>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub reversible: Option<bool>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub command_only_fan_speed: Option<bool>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub available_fan_speeds: Option<AvailableFanSpeeds>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub supports_fan_speed_percent: Option<bool>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub available_fill_levels: Option<AvailableFillLevels>,
+        #[serde(skip_serializing_if = "Option::is_none", rename = "humiditySetpointRange")]
         pub humidity_set_point_range: Option<HumiditySetPointRange>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub command_only_humidity_setting: Option<bool>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub query_only_humidity_setting: Option<bool>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub available_inputs: Option<Vec<AvailableInput>>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub command_only_input_selector: Option<bool>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub ordered_inputs: Option<bool>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub default_color_loop_duration: Option<i32>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub default_sleep_duration: Option<i32>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub default_wake_duration: Option<i32>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub supported_effects: Option<Vec<LightEffectType>>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub support_activity_state: Option<bool>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub support_playback_state: Option<bool>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub available_modes: Option<Vec<AvailableMode>>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub command_only_modes: Option<bool>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub query_only_modes: Option<bool>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub supports_enabling_guest_network: Option<bool>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub supports_disabling_guest_network: Option<bool>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub supports_getting_guest_network_password: Option<bool>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub network_profiles: Option<Vec<String>>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub supports_enabling_network_profile: Option<bool>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub supports_disabling_network_profile: Option<bool>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub supports_network_download_speed_test: Option<bool>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub supports_network_upload_speed_test: Option<bool>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub command_only_on_off: Option<bool>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub query_only_on_off: Option<bool>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub discrete_only_open_close: Option<bool>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub open_direction: Option<Vec<OpenDirection>>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub command_only_open_close: Option<bool>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub query_only_open_close: Option<bool>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub supports_degrees: Option<bool>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub supports_percent: Option<bool>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub rotation_degrees_range: Option<RotationDegreeRange>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub supports_continuous_rotation: Option<bool>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub command_only_rotation: Option<bool>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub scene_reversible: Option<bool>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub sensor_states_supported: Option<Vec<SupportedSensorState>>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub pausable: Option<bool>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub available_zones: Option<Vec<String>>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub temperature_range: Option<TemperatureRange>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub temperature_step_celsius: Option<f32>,
-        #[serde(rename = "temperatureUnitForUX")]
+        #[serde(rename = "temperatureUnitForUX", skip_serializing_if = "Option::is_none")]
         pub temperature_unit_for_ux: Option<TemperatureUnit>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub command_only_temperature_control: Option<bool>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub query_only_temperature_control: Option<bool>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub available_thermostat_modes: Option<Vec<ThermostatMode>>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub thermostat_temperature_range: Option<TemperatureRange>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub thermostat_temperature_unit: Option<TemperatureUnit>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub buffer_range_celsius: Option<f32>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub command_only_temperature_setting: Option<bool>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub query_only_temperature_setting: Option<bool>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub max_timer_limit_sec: Option<i32>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub command_only_timer: Option<bool>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub available_toggles: Option<Vec<AvailableToggle>>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub command_only_toggles: Option<bool>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub query_only_toggles: Option<bool>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub transport_control_supported_commands: Option<Vec<SupportedCommand>>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub volume_max_level: Option<i32>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub volume_can_mute_and_unmute: Option<bool>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub volume_default_percentage: Option<i32>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub level_step_size: Option<i32>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub command_only_volume: Option<bool>,
     }
 
+    /// Define a `with_*` method on [SyncAttributes] for every attribute
+    macro_rules! attribute_setters {
+        ($($method:ident => $field:ident: $ty:ty,)*) => {
+            impl SyncAttributes {
+                $(
+                    #[doc = concat!("Set the `", stringify!($field), "` attribute")]
+                    pub fn $method(mut self, $field: $ty) -> Self {
+                        self.$field = Some($field);
+                        self
+                    }
+                )*
+            }
+        };
+    }
+
+    attribute_setters! {
+        with_available_applications => available_applications: Vec<AvailableApplication>,
+        with_available_arm_levels => available_arm_levels: AvailableArmLevels,
+        with_command_only_brightness => command_only_brightness: bool,
+        with_camera_stream_supported_protocols => camera_stream_supported_protocols: Vec<CameraStreamProtocol>,
+        with_camera_stream_need_auth_token => camera_stream_need_auth_token: bool,
+        with_available_channels => available_channels: Vec<AvailableChannel>,
+        with_command_only_channels => command_only_channels: bool,
+        with_command_only_color_setting => command_only_color_setting: bool,
+        with_color_model => color_model: ColorModel,
+        with_color_temperature_range => color_temperature_range: ColorTemperatureRange,
+        with_supported_cooking_modes => supported_cooking_modes: Vec<CookingMode>,
+        with_food_presets => food_presets: Vec<FoodPreset>,
+        with_supported_dispense_items => supported_dispense_items: Vec<DispenseItem>,
+        with_supported_dispense_presets => supported_dispense_presets: Vec<DispensePreset>,
+        with_query_only_energy_storage => query_only_energy_storage: bool,
+        with_energy_storage_distance_unit_for_ux => energy_storage_distance_unit_for_ux: UxDistanceUnit,
+        with_is_rechargeable => is_rechargeable: bool,
+        with_reversible => reversible: bool,
+        with_command_only_fan_speed => command_only_fan_speed: bool,
+        with_available_fan_speeds => available_fan_speeds: AvailableFanSpeeds,
+        with_supports_fan_speed_percent => supports_fan_speed_percent: bool,
+        with_available_fill_levels => available_fill_levels: AvailableFillLevels,
+        with_humidity_set_point_range => humidity_set_point_range: HumiditySetPointRange,
+        with_command_only_humidity_setting => command_only_humidity_setting: bool,
+        with_query_only_humidity_setting => query_only_humidity_setting: bool,
+        with_available_inputs => available_inputs: Vec<AvailableInput>,
+        with_command_only_input_selector => command_only_input_selector: bool,
+        with_ordered_inputs => ordered_inputs: bool,
+        with_default_color_loop_duration => default_color_loop_duration: i32,
+        with_default_sleep_duration => default_sleep_duration: i32,
+        with_default_wake_duration => default_wake_duration: i32,
+        with_supported_effects => supported_effects: Vec<LightEffectType>,
+        with_support_activity_state => support_activity_state: bool,
+        with_support_playback_state => support_playback_state: bool,
+        with_available_modes => available_modes: Vec<AvailableMode>,
+        with_command_only_modes => command_only_modes: bool,
+        with_query_only_modes => query_only_modes: bool,
+        with_supports_enabling_guest_network => supports_enabling_guest_network: bool,
+        with_supports_disabling_guest_network => supports_disabling_guest_network: bool,
+        with_supports_getting_guest_network_password => supports_getting_guest_network_password: bool,
+        with_network_profiles => network_profiles: Vec<String>,
+        with_supports_enabling_network_profile => supports_enabling_network_profile: bool,
+        with_supports_disabling_network_profile => supports_disabling_network_profile: bool,
+        with_supports_network_download_speed_test => supports_network_download_speed_test: bool,
+        with_supports_network_upload_speed_test => supports_network_upload_speed_test: bool,
+        with_command_only_on_off => command_only_on_off: bool,
+        with_query_only_on_off => query_only_on_off: bool,
+        with_discrete_only_open_close => discrete_only_open_close: bool,
+        with_open_direction => open_direction: Vec<OpenDirection>,
+        with_command_only_open_close => command_only_open_close: bool,
+        with_query_only_open_close => query_only_open_close: bool,
+        with_supports_degrees => supports_degrees: bool,
+        with_supports_percent => supports_percent: bool,
+        with_rotation_degrees_range => rotation_degrees_range: RotationDegreeRange,
+        with_supports_continuous_rotation => supports_continuous_rotation: bool,
+        with_command_only_rotation => command_only_rotation: bool,
+        with_scene_reversible => scene_reversible: bool,
+        with_sensor_states_supported => sensor_states_supported: Vec<SupportedSensorState>,
+        with_pausable => pausable: bool,
+        with_available_zones => available_zones: Vec<String>,
+        with_temperature_range => temperature_range: TemperatureRange,
+        with_temperature_step_celsius => temperature_step_celsius: f32,
+        with_temperature_unit_for_ux => temperature_unit_for_ux: TemperatureUnit,
+        with_command_only_temperature_control => command_only_temperature_control: bool,
+        with_query_only_temperature_control => query_only_temperature_control: bool,
+        with_available_thermostat_modes => available_thermostat_modes: Vec<ThermostatMode>,
+        with_thermostat_temperature_range => thermostat_temperature_range: TemperatureRange,
+        with_thermostat_temperature_unit => thermostat_temperature_unit: TemperatureUnit,
+        with_buffer_range_celsius => buffer_range_celsius: f32,
+        with_command_only_temperature_setting => command_only_temperature_setting: bool,
+        with_query_only_temperature_setting => query_only_temperature_setting: bool,
+        with_max_timer_limit_sec => max_timer_limit_sec: i32,
+        with_command_only_timer => command_only_timer: bool,
+        with_available_toggles => available_toggles: Vec<AvailableToggle>,
+        with_command_only_toggles => command_only_toggles: bool,
+        with_query_only_toggles => query_only_toggles: bool,
+        with_transport_control_supported_commands => transport_control_supported_commands: Vec<SupportedCommand>,
+        with_volume_max_level => volume_max_level: i32,
+        with_volume_can_mute_and_unmute => volume_can_mute_and_unmute: bool,
+        with_volume_default_percentage => volume_default_percentage: i32,
+        with_level_step_size => level_step_size: i32,
+        with_command_only_volume => command_only_volume: bool,
+    }
+
     #[derive(Debug, PartialEq, Serialize)]
     #[serde(rename_all = "camelCase")]
     pub struct DeviceInfo {
@@ -171,6 +429,7 @@ pub mod sync {
 }
 
 pub mod query {
+    use crate::error_code::ErrorCode;
     use crate::traits::color_setting::Color;
     use crate::traits::cook::CookingMode;
     use crate::traits::dispense::DispenseItemState;
@@ -189,7 +448,7 @@ pub mod query {
 
     #[derive(Debug, PartialEq, Serialize)]
     pub struct Payload {
-        pub error_code: Option<String>,
+        pub error_code: Option<ErrorCode>,
         pub debug_string: Option<String>,
         pub devices: HashMap<String, QueryDeviceState>,
     }
@@ -215,94 +474,165 @@ pub mod query {
     #[derive(Debug, PartialEq, Serialize)]
     #[serde(rename_all = "camelCase")]
     pub struct RequiredQueryDeviceState {
-        pub on: bool,
         pub online: bool,
         pub status: QueryStatus,
-        pub error_code: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub error_code: Option<ErrorCode>,
     }
 
     #[derive(Debug, Default, PartialEq, Serialize)]
     #[serde(rename_all = "camelCase")]
     pub struct TraitsQueryDeviceState {
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub current_application: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub is_armed: Option<bool>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub current_arm_level: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub exit_allowance: Option<i32>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub brightness: Option<i32>,
         // TODO camerastream
         // TODO channel
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub color: Option<Color>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub current_cooking_mode: Option<CookingMode>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub current_food_preset: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub current_food_quantity: Option<f32>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub current_food_unit: Option<SizeUnit>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub dispense_items: Option<Vec<DispenseItemState>>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub is_docked: Option<bool>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub descriptive_capacity_remaining: Option<CapacityState>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub capacity_remaining: Option<Vec<CapacityValue>>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub capacity_until_full: Option<Vec<CapacityValue>>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub is_charging: Option<bool>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub is_plugged_in: Option<bool>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub current_fan_speed_setting: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub current_fan_speed_percent: Option<f32>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub is_filled: Option<bool>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub current_fill_level: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub current_fill_percent: Option<f32>,
+        /// The target humidity, a single percentage within the `humiditySetpointRange` attribute
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub humidity_setpoint_percent: Option<i32>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub humidity_ambient_percent: Option<i32>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub current_input: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub active_light_effect: Option<LightEffectType>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub light_effect_end_unix_timestamp_sec: Option<i64>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub is_locked: Option<bool>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub is_jammed: Option<bool>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub activity_state: Option<ActivityState>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub playback_state: Option<PlaybackState>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub current_mode_setting: Option<HashMap<String, String>>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub network_enabled: Option<bool>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub network_settings: Option<NetworkSettings>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub guest_network_enabled: Option<bool>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub guest_network_settings: Option<NetworkSettings>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub num_connected_devices: Option<i32>,
-        #[serde(rename = "networkUsageMB")]
+        #[serde(rename = "networkUsageMB", skip_serializing_if = "Option::is_none")]
         pub network_usage_mb: Option<f32>,
-        #[serde(rename = "networkUsageLimitMB")]
+        #[serde(rename = "networkUsageLimitMB", skip_serializing_if = "Option::is_none")]
         pub network_usage_limit_mb: Option<f32>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub network_usage_unlimited: Option<bool>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub last_network_download_speed_test: Option<DownloadSpeedTestResult>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub last_network_upload_speed_test: Option<UploadSpeedTestResult>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub network_speed_test_in_progress: Option<bool>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub network_profiles_state: Option<HashMap<String, NetworkProfileState>>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub on: Option<bool>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub open_percent: Option<f32>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub open_state: Option<Vec<OpenState>>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub rotation_degrees: Option<f32>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub rotation_percent: Option<f32>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub current_run_cycle: Option<Vec<CurrentRunCycle>>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub current_total_remaining_time: Option<i32>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub current_cycle_remaining_time: Option<i32>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub current_sensor_state_data: Option<Vec<CurrentSensorState>>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub last_software_update_unix_timestamp_sec: Option<i64>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub is_running: Option<bool>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub is_paused: Option<bool>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub active_zones: Option<Vec<String>>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub current_status_report: Option<Vec<CurrentStatusReport>>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub temperature_setpoint_celsius: Option<f32>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub temperature_ambient_celsius: Option<f32>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub active_thermostat_mode: Option<ThermostatMode>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub target_temp_reached_estimate_unix_timestamp_sec: Option<i64>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub thermostat_humidity_ambient: Option<f32>,
         #[serde(flatten)]
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub thermostat_mode: Option<QueryThermostatMode>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub timer_remaining_sec: Option<i32>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub timer_paused: Option<bool>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub current_toggle_settings: Option<HashMap<String, bool>>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub current_volume: Option<i32>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub is_muted: Option<bool>,
     }
 }
 
 pub mod execute {
-    use crate::serializable_error::SerializableError;
+    use crate::error_code::ErrorCode;
+    use crate::traits::network_control::{DownloadSpeedTestResult, UploadSpeedTestResult};
+    use crate::traits::open_close::OpenState;
+    use crate::traits::ChallengeType;
     use serde::Serialize;
 
     #[derive(Debug, PartialEq, Serialize)]
@@ -326,14 +656,85 @@ pub mod execute {
         pub ids: Vec<String>,
         pub status: CommandStatus,
         pub states: Option<CommandState>,
-        pub error_code: Option<SerializableError>,
+        pub error_code: Option<ErrorCode>,
+        /// A message describing the error in the language of the user
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub error_code_string: Option<String>,
         pub debug_string: Option<String>,
+        /// The secondary user verification required to execute the command, if `error_code` is `challengeNeeded`
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub challenge_needed: Option<ChallengeNeeded>,
     }
 
-    #[derive(Debug, Default, PartialEq, Serialize)]
+    #[derive(Debug, PartialEq, Serialize)]
+    pub struct ChallengeNeeded {
+        #[serde(rename = "type")]
+        pub challenge_type: ChallengeType,
+    }
+
+    /// The state of a device after executing a command
+    #[derive(Debug, PartialEq, Serialize)]
     #[serde(rename_all = "camelCase")]
     pub struct CommandState {
-        pub lock: Option<bool>,
+        pub online: bool,
+        #[serde(flatten)]
+        pub traits: TraitCommandState,
+    }
+
+    /// The state reported by the trait which executed the command
+    #[derive(Debug, PartialEq, Serialize)]
+    #[serde(untagged)]
+    pub enum TraitCommandState {
+        LockUnlock(LockUnlockCommandState),
+        NetworkControl(NetworkControlCommandState),
+        OnOff(OnOffCommandState),
+        OpenClose(OpenCloseCommandState),
+        Volume(VolumeCommandState),
+    }
+
+    #[derive(Debug, PartialEq, Serialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct LockUnlockCommandState {
+        pub is_locked: bool,
+    }
+
+    #[derive(Debug, Default, PartialEq, Serialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct NetworkControlCommandState {
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub guest_network_password: Option<String>,
+        /// The results of the download speed test, if one was requested
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub last_network_download_speed_test: Option<DownloadSpeedTestResult>,
+        /// The results of the upload speed test, if one was requested
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub last_network_upload_speed_test: Option<UploadSpeedTestResult>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub network_speed_test_in_progress: Option<bool>,
+    }
+
+    #[derive(Debug, PartialEq, Serialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct OnOffCommandState {
+        pub on: bool,
+    }
+
+    #[derive(Debug, PartialEq, Serialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct OpenCloseCommandState {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub open_percent: Option<f32>,
+        /// The state of every direction, for devices which open in more than one direction
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub open_state: Option<Vec<OpenState>>,
+    }
+
+    #[derive(Debug, PartialEq, Serialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct VolumeCommandState {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub current_volume: Option<i32>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub is_muted: Option<bool>,
     }
 }
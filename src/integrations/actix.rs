@@ -0,0 +1,26 @@
+//! Fulfillment handler for [actix-web](https://docs.rs/actix-web), available with the `actix` feature.
+//!
+//! ```
+//! use std::sync::Mutex;
+//! use actix_web::{web, App};
+//! use homelander::Homelander;
+//! use homelander::integrations::actix::fulfillment_handler;
+//!
+//! let homelander = web::Data::new(Mutex::new(Homelander::new("my_user_id".to_string())));
+//! let app = App::new()
+//!     .app_data(homelander.clone())
+//!     .route("/fulfillment", web::post().to(fulfillment_handler));
+//! ```
+//!
+//! Create the [web::Data] once, outside of the `HttpServer::new` closure, so all workers share the same [Homelander].
+
+use crate::{Homelander, Request, Response};
+use actix_web::web;
+use std::sync::{Mutex, PoisonError};
+
+/// Handle a fulfillment request from Google with the [Homelander] in the app data.
+/// Mount this as a `POST` route at your fulfillment URL.
+pub async fn fulfillment_handler(homelander: web::Data<Mutex<Homelander>>, request: web::Json<Request>) -> web::Json<Response> {
+    let mut homelander = homelander.lock().unwrap_or_else(PoisonError::into_inner);
+    web::Json(homelander.handle_request(request.into_inner()))
+}
@@ -0,0 +1,24 @@
+//! Fulfillment handler for [axum](https://docs.rs/axum), available with the `axum` feature.
+//!
+//! ```
+//! use std::sync::{Arc, Mutex};
+//! use axum::routing::post;
+//! use axum::Router;
+//! use homelander::Homelander;
+//! use homelander::integrations::axum::fulfillment_handler;
+//!
+//! let homelander = Arc::new(Mutex::new(Homelander::new("my_user_id".to_string())));
+//! let router: Router = Router::new().route("/fulfillment", post(fulfillment_handler)).with_state(homelander);
+//! ```
+
+use crate::{Homelander, Request, Response};
+use ::axum::extract::State;
+use ::axum::Json;
+use std::sync::{Arc, Mutex, PoisonError};
+
+/// Handle a fulfillment request from Google with the [Homelander] in the router's state.
+/// Mount this as a `POST` route at your fulfillment URL.
+pub async fn fulfillment_handler(State(homelander): State<Arc<Mutex<Homelander>>>, Json(request): Json<Request>) -> Json<Response> {
+    let mut homelander = homelander.lock().unwrap_or_else(PoisonError::into_inner);
+    Json(homelander.handle_request(request))
+}
@@ -0,0 +1,9 @@
+//! Ready made fulfillment handlers for web frameworks.
+//!
+//! These serve a single [Homelander](crate::Homelander), i.e. a single user. If you serve multiple users,
+//! look up the [Homelander](crate::Homelander) for the user identified by the OAuth2 token in your own handler instead.
+
+#[cfg(feature = "actix")]
+pub mod actix;
+#[cfg(feature = "axum")]
+pub mod axum;
@@ -144,7 +144,7 @@
 //! #        request_id: String::default(),
 //! #        inputs: vec![
 //! #            Input::Sync
-//! #        ]
+//! #        ],
 //! #    }
 //! # }
 //!
@@ -156,34 +156,66 @@
 //! let the_request = get_incoming_request(); // Usually you'd get this from your web framework
 //! let response = homelander.handle_request(the_request);
 //! ```
+//! If you'd rather not deal with (de)serialization yourself, [Homelander::handle_request_json] takes the raw request body
+//! and returns the JSON response. If it returns an error, the request body was invalid and you should respond with `400 Bad Request`.
 //!
 
-use crate::fulfillment::request::execute::CommandType;
+use crate::device::{unknown_command_output, unknown_query_device_state};
+use crate::fulfillment::request::execute::{Command, CommandType, Execute, Execution};
 use crate::fulfillment::request::Input;
 use crate::fulfillment::response::execute::CommandStatus;
+use crate::metrics::{Intent, MetricsSink};
 use crate::traits::arm_disarm::ArmDisarm;
 use crate::traits::brightness::Brightness;
 use crate::traits::color_setting::ColorSetting;
-use crate::traits::{CombinedDeviceError, GoogleHomeDevice};
-use std::collections::HashMap;
+use crate::traits::{CombinedDeviceError, GoogleHomeDevice, Language};
+use std::any::Any;
+use std::collections::{HashMap, HashSet};
 use std::error::Error;
 use std::fmt::Debug;
-use tracing::{instrument, trace};
+use tracing::{instrument, trace, warn};
 
+#[cfg(feature = "async")]
+mod async_device;
 mod device;
+mod device_builder;
 mod device_trait;
 mod device_type;
+mod error_code;
 mod execute_error;
+pub mod follow_up;
 #[doc(hidden)]
 pub mod fulfillment;
+#[cfg(any(feature = "axum", feature = "actix"))]
+pub mod integrations;
+mod localize;
+pub mod metrics;
+mod parallel;
+#[doc(hidden)]
+pub mod register_traits;
+mod registry;
 mod serializable_error;
+#[cfg(feature = "testkit")]
+pub mod testkit;
 pub mod traits;
+mod validation_error;
 
+#[cfg(feature = "async")]
+pub use async_device::AsyncDevice;
+#[cfg(feature = "async")]
+pub use async_trait::async_trait;
 pub use device::Device;
+pub use device_builder::DeviceBuilder;
+pub use device_trait::Trait;
 pub use device_type::DeviceType;
+pub use error_code::ErrorCode;
 pub use fulfillment::request::Request;
 pub use fulfillment::response::Response;
+#[cfg(feature = "derive")]
+pub use homelander_derive::GoogleHomeDevice;
+pub use registry::HomelanderRegistry;
 pub use serializable_error::*;
+pub use validation_error::ValidationError;
 
 /// The output of an EXECUTE command
 struct CommandOutput {
@@ -191,28 +223,84 @@ struct CommandOutput {
     status: CommandStatus,
     state: Option<fulfillment::response::execute::CommandState>,
     error: Option<SerializableError>,
+    /// The error in the language of the user
+    error_string: Option<String>,
     debug_string: Option<String>,
+    challenge_needed: Option<traits::ChallengeType>,
 }
 
-pub trait DeviceTraits: GoogleHomeDevice + Send + Sync + Debug + 'static {}
+pub trait DeviceTraits: GoogleHomeDevice + Send + Sync + Debug + 'static {
+    #[doc(hidden)]
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+}
 
-impl<T: GoogleHomeDevice + Send + Debug + Sync + 'static> DeviceTraits for T {}
+impl<T: GoogleHomeDevice + Send + Debug + Sync + 'static> DeviceTraits for T {
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
 
 /// Keeps track of all devices owned by a specific user.
 #[derive(Debug)]
 pub struct Homelander {
     agent_user_id: String,
     devices: Vec<Device<dyn crate::DeviceTraits>>,
+    #[cfg(feature = "async")]
+    async_devices: Vec<AsyncDevice<dyn crate::DeviceTraits>>,
+    metrics: Option<Box<dyn MetricsSink>>,
+    sync_size_warning_threshold: usize,
+    locale: Option<Language>,
+    max_threads: usize,
 }
 
 impl Homelander {
+    /// The size in bytes of a serialized SYNC payload above which a warning is logged, unless changed with [Self::set_sync_size_warning_threshold]
+    pub const DEFAULT_SYNC_SIZE_WARNING_THRESHOLD: usize = 512 * 1024;
+
     pub fn new(user_id: String) -> Self {
         Self {
             agent_user_id: user_id,
             devices: Vec::new(),
+            #[cfg(feature = "async")]
+            async_devices: Vec::new(),
+            metrics: None,
+            sync_size_warning_threshold: Self::DEFAULT_SYNC_SIZE_WARNING_THRESHOLD,
+            locale: None,
+            max_threads: std::thread::available_parallelism().map_or(1, std::num::NonZeroUsize::get),
         }
     }
 
+    /// Replace the agent user ID reported in SYNC and follow-up notifications,
+    /// e.g. when the token of a request maps to a different account than the one this was created for
+    pub fn set_agent_user_id(&mut self, user_id: String) {
+        self.agent_user_id = user_id;
+    }
+
+    /// Set the sink which receives metrics about the intents and commands handled, replacing any previous sink
+    pub fn set_metrics_sink<M: MetricsSink + 'static>(&mut self, sink: M) {
+        self.metrics = Some(Box::new(sink));
+    }
+
+    /// Set the size in bytes of a serialized SYNC payload above which a warning is logged.
+    /// Google limits the size of SYNC responses and rejects larger ones, see [Self::sync_estimated_size].
+    pub fn set_sync_size_warning_threshold(&mut self, bytes: usize) {
+        self.sync_size_warning_threshold = bytes;
+    }
+
+    /// Set the language of the user, Google does not send it along with requests.
+    /// During SYNC, only the synonyms in this language (or English, if there are none) are sent to Google.
+    /// Used to localize error messages, see [GoogleHomeDevice::get_localized_error](crate::traits::GoogleHomeDevice::get_localized_error).
+    pub fn set_locale(&mut self, locale: Option<Language>) {
+        self.locale = locale;
+    }
+
+    /// Set the maximum number of threads devices are handled on during a single QUERY or EXECUTE, at least one is used.
+    /// Defaults to the available parallelism of the machine. Raise it if the devices mostly wait on the network.
+    /// Only used with the `parallel` feature, otherwise all devices are handled on the calling thread.
+    pub fn set_max_threads(&mut self, threads: usize) {
+        self.max_threads = threads.max(1);
+    }
+
     /// Add a device
     pub fn add_device<T: DeviceTraits>(&mut self, device: Device<T>) {
         self.devices.push(device.unsize());
@@ -221,104 +309,132 @@ impl Homelander {
     /// Remove a device with ID `id`
     pub fn remove_device<S: AsRef<str>>(&mut self, id: S) {
         self.devices.retain(|f| f.id.ne(id.as_ref()));
+        #[cfg(feature = "async")]
+        self.async_devices.retain(|f| f.id.ne(id.as_ref()));
+    }
+
+    /// Create the notification reporting a [FollowUpResponse](follow_up::FollowUpResponse) for the device with ID `device_id` to Google
+    pub fn follow_up_notification(
+        &self,
+        request_id: String,
+        device_id: String,
+        device_trait: Trait,
+        response: follow_up::FollowUpResponse,
+    ) -> follow_up::FollowUpNotification {
+        follow_up::FollowUpNotification::new(request_id, self.agent_user_id.clone(), device_id, device_trait, response)
+    }
+
+    /// Get the device with ID `id`
+    pub fn get_device<S: AsRef<str>>(&self, id: S) -> Option<&Device<dyn DeviceTraits>> {
+        self.devices.iter().find(|f| f.id.eq(id.as_ref()))
+    }
+
+    /// Get the device with ID `id` mutably, e.g. to deregister a trait
+    pub fn get_device_mut<S: AsRef<str>>(&mut self, id: S) -> Option<&mut Device<dyn DeviceTraits>> {
+        self.devices.iter_mut().find(|f| f.id.eq(id.as_ref()))
+    }
+
+    /// Access the state of the device with ID `id` as its concrete type `T`.
+    /// Returns `None` if there is no such device, or if it is not a `T`.
+    pub fn with_device_mut<T: DeviceTraits, S: AsRef<str>, R, F: FnOnce(&mut T) -> R>(&mut self, id: S, f: F) -> Option<R> {
+        self.get_device_mut(id)?.with_inner(|inner| inner.as_any_mut().downcast_mut::<T>().map(f))
+    }
+
+    /// The size in bytes of the serialized SYNC payload for the current devices and locale. Async devices are not included.
+    ///
+    /// Google limits the size of SYNC responses. Devices with many channels, modes or synonyms add up quickly;
+    /// keep lists such as the available channels of [Channel](traits::channel::Channel) short (30 channels or less)
+    /// and only include the synonyms users actually need.
+    pub fn sync_estimated_size(&self) -> usize {
+        self.warn_async_devices_skipped("sync_estimated_size");
+        Self::serialized_size(&self.sync(self.locale.as_ref()))
+    }
+
+    /// QUERY the state of the device with ID `id`, e.g. to report it to Google with Report State.
+    /// Returns `None` if there is no such device. Async devices are not included.
+    pub fn query_device<S: AsRef<str>>(&self, id: S) -> Option<fulfillment::response::query::QueryDeviceState> {
+        self.warn_async_devices_skipped("query_device");
+        Some(self.get_device(id)?.query())
+    }
+
+    /// EXECUTE `command` on the device with ID `device_id` without a request from Google, e.g. for local control.
+    /// Returns `None` if there is no such device. Async devices are not included.
+    pub fn execute_command<S: AsRef<str>>(&mut self, device_id: S, command: CommandType) -> Option<fulfillment::response::execute::Command> {
+        self.warn_async_devices_skipped("execute_command");
+        let device = self.get_device_mut(device_id)?;
+        // Don't let the device mistake this command for a retry of the last EXECUTE from Google
+        device.set_request_id(None);
+        device.set_custom_data(None);
+
+        let output = Self::execute_device(
+            device,
+            &Execution {
+                command: command.clone(),
+                challenge: None,
+            },
+            None,
+        );
+        self.record_command(&command, &output);
+        Self::execute_payload(vec![output]).commands.pop()
     }
 
-    /// Handle an incomming fulfillment request from Google and create a response for it
-    #[instrument]
+    /// Handle an incomming fulfillment request from Google and create a response for it.
+    /// Async devices are skipped, use [Self::handle_request_async] if any are registered.
+    #[instrument(skip(self))]
     pub fn handle_request(&mut self, request: fulfillment::request::Request) -> fulfillment::response::Response {
-        let payload = request
+        self.warn_async_devices_skipped("handle_request");
+        let locale = self.locale.clone();
+        let payloads = request
             .inputs
             .into_iter()
-            .map(|input| match input {
-                Input::Execute(execute) => {
-                    let commands = execute
-                        .commands
-                        .into_iter()
-                        .map(|command| {
-                            command
-                                .devices
-                                .into_iter()
-                                .map(|device| device.id)
-                                .map(|device_id| {
-                                    command
-                                        .execution
-                                        .iter()
-                                        .map(|command_type| self.execute(&device_id, command_type.clone()))
-                                        .filter_map(|command_output| command_output)
-                                        .collect::<Vec<_>>()
-                                })
-                                .flatten()
-                                .collect::<Vec<_>>()
-                        })
-                        .flatten()
-                        .collect::<Vec<_>>()
-                        .into_iter()
-                        .map(|output| match output.status {
-                            CommandStatus::Success | CommandStatus::Exceptions => fulfillment::response::execute::Command {
-                                ids: vec![output.id],
-                                status: output.status,
-                                states: output.state,
-                                error_code: None,
-                                debug_string: output.debug_string,
-                            },
-                            CommandStatus::Error => fulfillment::response::execute::Command {
-                                ids: vec![output.id],
-                                status: CommandStatus::Error,
-                                states: None,
-                                error_code: output.error,
-                                debug_string: output.debug_string,
-                            },
-                            CommandStatus::Offline | CommandStatus::Pending => fulfillment::response::execute::Command {
-                                ids: vec![output.id],
-                                status: output.status,
-                                states: None,
-                                error_code: None,
-                                debug_string: output.debug_string,
-                            },
-                        })
-                        .collect::<Vec<_>>();
-
-                    fulfillment::response::ResponsePayload::Execute(fulfillment::response::execute::Payload { commands })
-                }
-                Input::Sync => fulfillment::response::ResponsePayload::Sync(self.sync()),
-                Input::Query(payload) => fulfillment::response::ResponsePayload::Query(self.query(payload)),
-                Input::Disconnect => {
-                    self.devices.iter_mut().for_each(|x| x.disconnect());
-                    fulfillment::response::ResponsePayload::Disconnect
+            .map(|input| {
+                self.record_intent(&input);
+                match input {
+                    Input::Execute(execute) => fulfillment::response::ResponsePayload::Execute(self.execute(execute, &request.request_id, locale.as_ref())),
+                    Input::Sync => fulfillment::response::ResponsePayload::Sync(self.sync(locale.as_ref())),
+                    Input::Query(payload) => fulfillment::response::ResponsePayload::Query(self.query(payload)),
+                    Input::Disconnect => {
+                        self.devices.iter_mut().for_each(|x| x.disconnect());
+                        fulfillment::response::ResponsePayload::Disconnect
+                    }
                 }
             })
-            .collect::<Vec<_>>()
-            .remove(0);
+            .collect::<Vec<_>>();
 
-        fulfillment::response::Response {
-            request_id: request.request_id,
-            payload,
-        }
+        Self::response(request.request_id, payloads)
+    }
+
+    /// Handle an incomming fulfillment request from Google as JSON and create a JSON response for it.
+    ///
+    /// # Errors
+    ///
+    /// If `body` is not a valid fulfillment request. This should be answered with `400 Bad Request`.
+    #[instrument(skip(self))]
+    pub fn handle_request_json(&mut self, body: &str) -> Result<String, serde_json::Error> {
+        let request = serde_json::from_str::<Request>(body)?;
+        let response = self.handle_request(request);
+        serde_json::to_string(&response)
     }
 
     /// QUERY all devices specified in `payload`
-    #[instrument]
+    #[instrument(skip(self))]
     fn query(&self, payload: fulfillment::request::query::Payload) -> fulfillment::response::query::Payload {
         trace!("Running QUERY operation");
 
-        let device_states = payload
-            .devices
+        let device_ids = payload.devices.into_iter().map(|device| device.id).collect::<Vec<_>>();
+        let devices = device_ids
+            .iter()
+            .filter_map(|device_id| self.devices.iter().find(|d| d.id.eq(device_id)))
+            .collect::<Vec<_>>();
+        let mut device_states = parallel::map_devices(self.max_threads, devices, |device| (device.id.clone(), device.query()))
             .into_iter()
-            .map(|device| device.id)
-            .map(|device_id| {
-                (
-                    device_id.clone(),
-                    self.devices
-                        .iter()
-                        .filter(|device| device.id.eq(&device_id))
-                        .map(|device| device.query())
-                        .collect::<Vec<_>>(),
-                )
-            })
-            .filter(|(_, device_states)| !device_states.is_empty())
-            .map(|(id, mut device_state)| (id, device_state.remove(0)))
             .collect::<HashMap<_, _>>();
 
+        // Google still expects a state for devices which don't exist (anymore)
+        for device_id in device_ids {
+            device_states.entry(device_id).or_insert_with(unknown_query_device_state);
+        }
+
         fulfillment::response::query::Payload {
             devices: device_states,
             error_code: None,
@@ -326,54 +442,389 @@ impl Homelander {
         }
     }
 
-    /// SYNC all devices
-    #[instrument]
-    fn sync(&self) -> fulfillment::response::sync::Payload {
+    /// SYNC all devices.
+    /// If `locale` is set, only synonyms in that language are included.
+    #[instrument(skip(self))]
+    fn sync(&self, locale: Option<&Language>) -> fulfillment::response::sync::Payload {
         trace!("Running SYNC operation");
         let devices = self.devices.iter().map(|x| x.sync()).collect::<Result<Vec<_>, Box<dyn Error>>>();
+        self.sync_payload(devices, locale)
+    }
 
+    /// Create the SYNC response payload from the SYNC output of all devices
+    fn sync_payload(
+        &self,
+        devices: Result<Vec<fulfillment::response::sync::Device>, Box<dyn Error>>,
+        locale: Option<&Language>,
+    ) -> fulfillment::response::sync::Payload {
         struct PayloadContent {
             devices: Vec<fulfillment::response::sync::Device>,
-            error_code: Option<String>,
+            error_code: Option<ErrorCode>,
             debug_string: Option<String>,
         }
 
         let content = match devices {
-            Ok(d) => PayloadContent {
-                devices: d,
-                error_code: None,
-                debug_string: None,
-            },
-            Err(e) => PayloadContent {
-                devices: Vec::with_capacity(0),
-                error_code: Some("deviceOffline".to_string()),
-                debug_string: Some(e.to_string()),
-            },
+            Ok(mut d) => {
+                if let Some(language) = locale {
+                    d.iter_mut().for_each(|device| localize::localize_attributes(&mut device.attributes, language));
+                }
+
+                PayloadContent {
+                    devices: d,
+                    error_code: None,
+                    debug_string: None,
+                }
+            }
+            Err(e) => {
+                let (error_code, debug_string) = match e.downcast_ref::<CombinedDeviceError>() {
+                    Some(e) => e.to_google_error(),
+                    None => (None, Some(e.to_string())),
+                };
+
+                PayloadContent {
+                    devices: Vec::with_capacity(0),
+                    error_code: Some(error_code.map(ErrorCode::from).unwrap_or(ErrorCode::DeviceOffline)),
+                    debug_string,
+                }
+            }
         };
 
-        fulfillment::response::sync::Payload {
+        let payload = fulfillment::response::sync::Payload {
             agent_user_id: self.agent_user_id.clone(),
             devices: content.devices,
             error_code: content.error_code,
             debug_string: content.debug_string,
+        };
+
+        let size = Self::serialized_size(&payload);
+        if size > self.sync_size_warning_threshold {
+            warn!(
+                "SYNC payload is {size} bytes, which exceeds the threshold of {} bytes. Google may reject it, consider trimming channels, modes or synonyms",
+                self.sync_size_warning_threshold
+            );
+        }
+
+        payload
+    }
+
+    /// The size in bytes of `payload` serialized as JSON
+    fn serialized_size(payload: &fulfillment::response::sync::Payload) -> usize {
+        // The payload only contains serializable types, serializing it cannot fail
+        serde_json::to_vec(payload).map(|x| x.len()).unwrap_or_default()
+    }
+
+    /// Create the response to the request with ID `request_id` from the payloads of its inputs.
+    /// Google only sends a single input per request, so only the first payload is used.
+    fn response(request_id: String, payloads: Vec<fulfillment::response::ResponsePayload>) -> fulfillment::response::Response {
+        let payload = payloads.into_iter().next().unwrap_or_else(|| {
+            fulfillment::response::ResponsePayload::Error(fulfillment::response::ErrorPayload {
+                error_code: ErrorCode::ProtocolError,
+                debug_string: Some("The request has no inputs".to_string()),
+            })
+        });
+
+        fulfillment::response::Response { request_id, payload }
+    }
+
+    /// Create the EXECUTE response payload from the output of all executed commands
+    fn execute_payload(outputs: Vec<CommandOutput>) -> fulfillment::response::execute::Payload {
+        let commands = outputs
+            .into_iter()
+            .map(|output| match output.status {
+                CommandStatus::Success | CommandStatus::Exceptions => fulfillment::response::execute::Command {
+                    ids: vec![output.id],
+                    status: output.status,
+                    states: output.state,
+                    error_code: None,
+                    error_code_string: None,
+                    debug_string: output.debug_string,
+                    challenge_needed: None,
+                },
+                CommandStatus::Error => fulfillment::response::execute::Command {
+                    ids: vec![output.id],
+                    status: CommandStatus::Error,
+                    states: None,
+                    error_code: output.error.map(|e| ErrorCode::from_trait_error(&e)),
+                    error_code_string: output.error_string,
+                    debug_string: output.debug_string,
+                    challenge_needed: output
+                        .challenge_needed
+                        .map(|challenge_type| fulfillment::response::execute::ChallengeNeeded { challenge_type }),
+                },
+                CommandStatus::Offline | CommandStatus::Pending => fulfillment::response::execute::Command {
+                    ids: vec![output.id],
+                    status: output.status,
+                    states: None,
+                    error_code: None,
+                    error_code_string: None,
+                    debug_string: output.debug_string,
+                    challenge_needed: None,
+                },
+            })
+            .fold(Vec::new(), Self::group_command);
+
+        fulfillment::response::execute::Payload { commands }
+    }
+
+    /// Add `command` to `commands`. A successful command is merged into an earlier one with the same outcome,
+    /// so that e.g. turning on many lights results in a single command listing all their IDs.
+    fn group_command(
+        mut commands: Vec<fulfillment::response::execute::Command>,
+        mut command: fulfillment::response::execute::Command,
+    ) -> Vec<fulfillment::response::execute::Command> {
+        let same_outcome = |other: &fulfillment::response::execute::Command| {
+            other.status == command.status
+                && other.states == command.states
+                && other.error_code == command.error_code
+                && other.error_code_string == command.error_code_string
+                && other.debug_string == command.debug_string
+                && other.challenge_needed == command.challenge_needed
+        };
+
+        match commands
+            .iter_mut()
+            .find(|other| command.status == CommandStatus::Success && same_outcome(other))
+        {
+            Some(other) => other.ids.append(&mut command.ids),
+            None => commands.push(command),
         }
+
+        commands
     }
 
-    /// EXECUTE `command` on `device_id`
-    #[instrument]
-    fn execute(&mut self, device_id: &str, command: CommandType) -> Option<CommandOutput> {
+    /// EXECUTE every command group in `execute`. Each group addresses its own devices with its own executions,
+    /// the outputs of all groups are combined into a single response.
+    #[instrument(skip(self))]
+    fn execute(&mut self, execute: Execute, request_id: &str, locale: Option<&Language>) -> fulfillment::response::execute::Payload {
         trace!("Running EXECUTE intent");
-        let mut output = self
+
+        let mut outputs = Vec::new();
+        for command in execute.commands {
+            outputs.extend(self.execute_group(command, request_id, locale));
+        }
+
+        Self::execute_payload(outputs)
+    }
+
+    /// EXECUTE all executions of `command` on every device it addresses, returning an output per device and execution.
+    /// Devices which don't exist are answered with [ErrorCode::DeviceNotFound], a device addressed twice is executed once.
+    /// If `locale` is set, errors are localized by the device.
+    fn execute_group(&mut self, command: Command, request_id: &str, locale: Option<&Language>) -> Vec<CommandOutput> {
+        let mut devices = HashMap::new();
+        for device in &mut self.devices {
+            devices.entry(device.id.clone()).or_insert(device);
+        }
+
+        let mut addressed = HashSet::new();
+        let targets = command
             .devices
-            .iter_mut()
-            .filter(|x| x.id.eq(device_id))
-            .map(|device| device.execute(command.clone()))
+            .into_iter()
+            .filter(|target| addressed.insert(target.id.clone()))
+            .map(|target| match devices.remove(&target.id) {
+                Some(device) => Ok((device, target.custom_data)),
+                None => Err(target.id),
+            })
             .collect::<Vec<_>>();
 
-        if output.is_empty() {
-            None
-        } else {
-            Some(output.remove(0))
+        let outputs = parallel::map_devices(self.max_threads, targets, |target| match target {
+            Ok((device, custom_data)) => {
+                // Pass the request ID and the custom data Google sent along to the device
+                device.set_request_id(Some(request_id));
+                device.set_custom_data(custom_data);
+
+                command
+                    .execution
+                    .iter()
+                    .map(|execution| Self::execute_device(device, execution, locale))
+                    .collect::<Vec<_>>()
+            }
+            Err(id) => command.execution.iter().map(|_| unknown_command_output(id.clone())).collect(),
+        });
+
+        for device_outputs in &outputs {
+            for (execution, output) in command.execution.iter().zip(device_outputs) {
+                self.record_command(&execution.command, output);
+            }
+        }
+
+        outputs.into_iter().flatten().collect()
+    }
+
+    /// EXECUTE `execution` on `device`
+    fn execute_device(device: &mut Device<dyn DeviceTraits>, execution: &Execution, locale: Option<&Language>) -> CommandOutput {
+        device.set_challenge(execution.challenge.clone());
+        let mut output = device.execute(execution.command.clone());
+        if let (Some(error), Some(language)) = (&output.error, locale) {
+            output.error_string = device.get_localized_error(&error.to_string(), language);
+        }
+
+        output
+    }
+
+    /// Warn that `entry_point` skips the registered async devices, which are only handled by [Self::handle_request_async]
+    #[cfg(feature = "async")]
+    fn warn_async_devices_skipped(&self, entry_point: &str) {
+        if !self.async_devices.is_empty() {
+            warn!("{entry_point} skips the {} registered async devices, use handle_request_async to handle them", self.async_devices.len());
+        }
+    }
+
+    #[cfg(not(feature = "async"))]
+    fn warn_async_devices_skipped(&self, _entry_point: &str) {}
+
+    /// Report `input` to the metrics sink, if one is set
+    fn record_intent(&self, input: &Input) {
+        if let Some(metrics) = &self.metrics {
+            metrics.record_intent(match input {
+                Input::Sync => Intent::Sync,
+                Input::Query(_) => Intent::Query,
+                Input::Execute(_) => Intent::Execute,
+                Input::Disconnect => Intent::Disconnect,
+            });
+        }
+    }
+
+    /// Report the outcome of `command` to the metrics sink, if one is set
+    fn record_command(&self, command: &CommandType, output: &CommandOutput) {
+        if let Some(metrics) = &self.metrics {
+            metrics.record_command(command.device_trait(), &output.status);
+        }
+    }
+}
+
+#[cfg(feature = "async")]
+impl Homelander {
+    /// Add an async device.
+    /// Async devices are only handled by [Self::handle_request_async]
+    pub fn add_async_device<T: DeviceTraits>(&mut self, device: AsyncDevice<T>) {
+        self.async_devices.push(device.unsize());
+    }
+
+    /// Handle an incomming fulfillment request from Google and create a response for it.
+    /// Both regular and async devices are handled, awaiting the traits of async devices.
+    #[instrument(skip(self))]
+    pub async fn handle_request_async(&mut self, request: fulfillment::request::Request) -> fulfillment::response::Response {
+        let locale = self.locale.clone();
+        let mut payloads = Vec::with_capacity(request.inputs.len());
+        for input in request.inputs {
+            self.record_intent(&input);
+            let payload = match input {
+                Input::Execute(execute) => {
+                    let mut outputs = Vec::new();
+                    for command in execute.commands {
+                        outputs.extend(self.execute_group_async(command, &request.request_id, locale.as_ref()).await);
+                    }
+
+                    fulfillment::response::ResponsePayload::Execute(Self::execute_payload(outputs))
+                }
+                Input::Sync => fulfillment::response::ResponsePayload::Sync(self.sync_async(locale.as_ref()).await),
+                Input::Query(payload) => fulfillment::response::ResponsePayload::Query(self.query_async(payload).await),
+                Input::Disconnect => {
+                    self.devices.iter_mut().for_each(|x| x.disconnect());
+                    for device in &mut self.async_devices {
+                        device.disconnect().await;
+                    }
+
+                    fulfillment::response::ResponsePayload::Disconnect
+                }
+            };
+
+            payloads.push(payload);
+        }
+
+        Self::response(request.request_id, payloads)
+    }
+
+    /// QUERY all devices specified in `payload`, including async devices
+    #[instrument(skip(self))]
+    async fn query_async(&self, payload: fulfillment::request::query::Payload) -> fulfillment::response::query::Payload {
+        trace!("Running QUERY operation");
+
+        let mut device_states = HashMap::new();
+        for device_id in payload.devices.into_iter().map(|device| device.id) {
+            if let Some(device) = self.devices.iter().find(|device| device.id.eq(&device_id)) {
+                device_states.insert(device_id, device.query());
+            } else if let Some(device) = self.async_devices.iter().find(|device| device.id.eq(&device_id)) {
+                device_states.insert(device_id, device.query().await);
+            } else {
+                device_states.insert(device_id, unknown_query_device_state());
+            }
+        }
+
+        fulfillment::response::query::Payload {
+            devices: device_states,
+            error_code: None,
+            debug_string: None,
+        }
+    }
+
+    /// SYNC all devices, including async devices.
+    /// If `locale` is set, only synonyms in that language are included.
+    #[instrument(skip(self))]
+    async fn sync_async(&self, locale: Option<&Language>) -> fulfillment::response::sync::Payload {
+        trace!("Running SYNC operation");
+        let mut devices = match self.devices.iter().map(|x| x.sync()).collect::<Result<Vec<_>, Box<dyn Error>>>() {
+            Ok(d) => d,
+            Err(e) => return self.sync_payload(Err(e), locale),
+        };
+
+        for device in &self.async_devices {
+            match device.sync().await {
+                Ok(d) => devices.push(d),
+                Err(e) => return self.sync_payload(Err(e), locale),
+            }
+        }
+
+        self.sync_payload(Ok(devices), locale)
+    }
+
+    /// EXECUTE all executions of `command` on every device it addresses, which may be async devices.
+    /// Returns an output per device and execution, like [Self::execute_group].
+    async fn execute_group_async(&mut self, command: Command, request_id: &str, locale: Option<&Language>) -> Vec<CommandOutput> {
+        let mut addressed = HashSet::new();
+        let mut outputs = Vec::new();
+        for device in command.devices.into_iter().filter(|device| addressed.insert(device.id.clone())) {
+            self.set_execute_context_async(&device.id, request_id, device.custom_data).await;
+            for execution in &command.execution {
+                let output = self.execute_async(&device.id, execution, locale).await;
+                self.record_command(&execution.command, &output);
+                outputs.push(output);
+            }
+        }
+
+        outputs
+    }
+
+    /// Pass the request ID and the custom data Google sent in an EXECUTE to `device_id`, which may be an async device
+    async fn set_execute_context_async(&mut self, device_id: &str, request_id: &str, custom_data: Option<serde_json::Value>) {
+        if let Some(device) = self.async_devices.iter_mut().find(|x| x.id.eq(device_id)) {
+            device.set_request_id(Some(request_id)).await;
+            device.set_custom_data(custom_data).await;
+        } else if let Some(device) = self.devices.iter_mut().find(|x| x.id.eq(device_id)) {
+            device.set_request_id(Some(request_id));
+            device.set_custom_data(custom_data);
+        }
+    }
+
+    /// EXECUTE `execution` on `device_id`, which may be an async device
+    #[instrument(skip(self))]
+    async fn execute_async(&mut self, device_id: &str, execution: &Execution, locale: Option<&Language>) -> CommandOutput {
+        trace!("Running EXECUTE intent");
+        if let Some(device) = self.devices.iter_mut().find(|x| x.id.eq(device_id)) {
+            return Self::execute_device(device, execution, locale);
+        }
+
+        match self.async_devices.iter_mut().find(|x| x.id.eq(device_id)) {
+            Some(device) => {
+                device.set_challenge(execution.challenge.clone()).await;
+                let mut output = device.execute(execution.command.clone()).await;
+                if let (Some(error), Some(language)) = (&output.error, locale) {
+                    output.error_string = device.get_localized_error(&error.to_string(), language).await;
+                }
+
+                output
+            }
+            None => unknown_command_output(device_id.to_string()),
         }
     }
 }
@@ -471,4 +922,11 @@ mod test {
             arm_level: None,
         });
     }
+
+    #[test]
+    fn homelander_is_send_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<Homelander>();
+        assert_send_sync::<Device<Foo>>();
+    }
 }
@@ -0,0 +1,91 @@
+//! Filtering of language specific synonyms in the SYNC response to the locale of the user
+
+use crate::fulfillment::response::sync::SyncAttributes;
+use crate::traits::{app_selector, arm_disarm, fan_speed, fill, input_selector, modes, toggles, Language, Synonym};
+
+/// A value which only applies to a single language
+trait Localized {
+    fn lang(&self) -> &Language;
+}
+
+/// Implement [Localized] for types with a `lang` field
+macro_rules! localized {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            impl Localized for $ty {
+                fn lang(&self) -> &Language {
+                    &self.lang
+                }
+            }
+        )*
+    };
+}
+
+localized!(
+    app_selector::Name,
+    arm_disarm::LevelValue,
+    fan_speed::FanSpeedValue,
+    fill::LevelValue,
+    input_selector::InputName,
+    modes::NameValue,
+    modes::SettingValue,
+    toggles::NameValue,
+    Synonym,
+);
+
+/// Keep only the values in `language`. If there are none, the English values are kept instead.
+/// If there are no English values either, all values are kept, as Google requires at least one.
+fn retain_language<T: Localized>(values: &mut Vec<T>, language: &Language) {
+    for language in [language, &Language::English] {
+        if values.iter().any(|v| v.lang() == language) {
+            values.retain(|v| v.lang() == language);
+            return;
+        }
+    }
+}
+
+/// Remove all synonyms from `attributes` which are not in `language`
+pub(crate) fn localize_attributes(attributes: &mut SyncAttributes, language: &Language) {
+    for application in attributes.available_applications.iter_mut().flatten() {
+        retain_language(&mut application.names, language);
+    }
+
+    for level in attributes.available_arm_levels.iter_mut().flat_map(|l| l.levels.iter_mut().flatten()) {
+        retain_language(&mut level.level_values, language);
+    }
+
+    for preset in attributes.food_presets.iter_mut().flatten() {
+        retain_language(&mut preset.food_synonyms, language);
+    }
+
+    for item in attributes.supported_dispense_items.iter_mut().flatten() {
+        retain_language(&mut item.item_name_synonyms, language);
+    }
+
+    for preset in attributes.supported_dispense_presets.iter_mut().flatten() {
+        retain_language(&mut preset.preset_name_synonyms, language);
+    }
+
+    for speed in attributes.available_fan_speeds.iter_mut().flat_map(|s| s.speeds.iter_mut()) {
+        retain_language(&mut speed.speed_values, language);
+    }
+
+    for level in attributes.available_fill_levels.iter_mut().flat_map(|l| l.levels.iter_mut()) {
+        retain_language(&mut level.level_values, language);
+    }
+
+    for input in attributes.available_inputs.iter_mut().flatten() {
+        retain_language(&mut input.names, language);
+    }
+
+    for mode in attributes.available_modes.iter_mut().flatten() {
+        retain_language(&mut mode.name_values, language);
+        for setting in &mut mode.settings {
+            retain_language(&mut setting.setting_values, language);
+        }
+    }
+
+    for toggle in attributes.available_toggles.iter_mut().flatten() {
+        retain_language(&mut toggle.name_values, language);
+    }
+}
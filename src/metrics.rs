@@ -0,0 +1,27 @@
+//! Hooks to collect metrics about the requests Homelander handles, e.g. to show which commands fail on a dashboard.
+//!
+//! Implement [MetricsSink] and register it with [Homelander::set_metrics_sink](crate::Homelander::set_metrics_sink).
+
+use crate::fulfillment::response::execute::CommandStatus;
+use crate::Trait;
+use std::fmt::Debug;
+
+/// An intent sent by Google
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Intent {
+    Sync,
+    Query,
+    Execute,
+    Disconnect,
+}
+
+/// Receives the intents and command outcomes handled by Homelander.
+/// Both methods do nothing by default, so only the metrics of interest have to be implemented.
+pub trait MetricsSink: Debug + Send + Sync {
+    /// Called once for every intent in a fulfillment request
+    fn record_intent(&self, _intent: Intent) {}
+
+    /// Called once for every command executed on a device, with the trait the command belongs to and its outcome.
+    /// `device_trait` is `None` for commands Homelander does not know.
+    fn record_command(&self, _device_trait: Option<Trait>, _status: &CommandStatus) {}
+}
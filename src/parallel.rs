@@ -0,0 +1,47 @@
+//! Handling multiple devices in a single request.
+//!
+//! With the `parallel` feature, the devices are spread over a number of threads,
+//! so one device making a slow network call doesn't hold up the others.
+//! The number of threads is capped, see [Homelander::set_max_threads](crate::Homelander::set_max_threads).
+
+/// Run `f` for every item on at most `max_threads` threads, returning the results in the order of `items`
+#[cfg(feature = "parallel")]
+pub(crate) fn map_devices<I, R, F>(max_threads: usize, items: Vec<I>, f: F) -> Vec<R>
+where
+    I: Send,
+    R: Send,
+    F: Fn(I) -> R + Sync,
+{
+    let threads = max_threads.min(items.len());
+    // Not worth spawning a thread for
+    if threads < 2 {
+        return items.into_iter().map(f).collect();
+    }
+
+    // Every thread handles a consecutive chunk of the items, so the results can be joined in order
+    let chunk_size = items.len().div_ceil(threads);
+    let mut items = items.into_iter();
+    let chunks = (0..threads).map(|_| items.by_ref().take(chunk_size).collect::<Vec<_>>()).collect::<Vec<_>>();
+
+    std::thread::scope(|scope| {
+        let f = &f;
+        let handles = chunks
+            .into_iter()
+            .map(|chunk| scope.spawn(move || chunk.into_iter().map(f).collect::<Vec<_>>()))
+            .collect::<Vec<_>>();
+
+        handles
+            .into_iter()
+            .flat_map(|handle| handle.join().unwrap_or_else(|e| std::panic::resume_unwind(e)))
+            .collect()
+    })
+}
+
+/// Run `f` for every item, returning the results in the order of `items`
+#[cfg(not(feature = "parallel"))]
+pub(crate) fn map_devices<I, R, F>(_max_threads: usize, items: Vec<I>, f: F) -> Vec<R>
+where
+    F: Fn(I) -> R,
+{
+    items.into_iter().map(f).collect()
+}
@@ -0,0 +1,224 @@
+//! Registering traits which are only known at runtime, see [register_traits!](crate::register_traits).
+//!
+//! Whether a device implements a trait can only be checked where its type is known,
+//! so the check is done by the macro using autoref specialization on a [Probe].
+
+use crate::{Device, GoogleHomeDevice, Trait};
+use std::fmt::Debug;
+
+/// Register traits on a [Device](crate::Device) from a list of [Trait](crate::Trait)s, e.g. read from a configuration file.
+/// Calls the setter of every trait, like [Device::set_on_off](crate::Device::set_on_off) for [Trait::OnOff](crate::Trait::OnOff).
+///
+/// # Errors
+///
+/// If the device does not implement one of the traits. The traits before it are registered.
+/// [Trait::ObjectDetection](crate::Trait::ObjectDetection) can't be registered, it is only used for notifications.
+///
+/// A trait which is already registered, or listed twice, is registered once.
+///
+/// # Limitations
+///
+/// Whether the device implements a trait is decided by the type of the device where the macro is used.
+/// In a generic context, e.g. in `fn f<T: GoogleHomeDevice>(device: &mut Device<T>)`, only the bounds on `T` are known,
+/// so every trait not in those bounds is reported as not implemented, even if the device does implement it.
+/// Use the macro where the concrete type of the device is known.
+///
+/// ```
+/// # use homelander::traits::{CombinedDeviceError, DeviceInfo, DeviceName, GoogleHomeDevice};
+/// # use homelander::traits::on_off::OnOff;
+/// # use homelander::{register_traits, Device, DeviceType, Trait};
+/// #
+/// # #[derive(Debug)]
+/// # struct MyDevice(bool);
+/// #
+/// # impl GoogleHomeDevice for MyDevice {
+/// #     fn get_device_info(&self) -> DeviceInfo {
+/// #         DeviceInfo {
+/// #             model: "mydevice".to_string(),
+/// #             manufacturer: "mydevice company".to_string(),
+/// #             hw: "0.1.0".to_string(),
+/// #             sw: "0.1.0".to_string(),
+/// #         }
+/// #     }
+/// #
+/// #     fn will_report_state(&self) -> bool {
+/// #         false
+/// #     }
+/// #
+/// #     fn get_device_name(&self) -> DeviceName {
+/// #         DeviceName {
+/// #             name: "MyDevice".to_string(),
+/// #             default_names: Vec::new(),
+/// #             nicknames: Vec::new(),
+/// #         }
+/// #     }
+/// #
+/// #     fn is_online(&self) -> bool {
+/// #         true
+/// #     }
+/// #
+/// #     fn disconnect(&mut self) {}
+/// # }
+/// #
+/// # impl OnOff for MyDevice {
+/// #     fn is_on(&self) -> Result<bool, CombinedDeviceError> {
+/// #         Ok(self.0)
+/// #     }
+/// #
+/// #     fn set_on(&mut self, on: bool) -> Result<(), CombinedDeviceError> {
+/// #         self.0 = on;
+/// #         Ok(())
+/// #     }
+/// # }
+/// let mut device = Device::new(MyDevice(false), DeviceType::Outlet, "my_id".to_string());
+/// register_traits!(device, &[Trait::OnOff]).unwrap();
+/// assert_eq!(device.registered_traits(), &[Trait::OnOff]);
+///
+/// // Already registered
+/// register_traits!(device, &[Trait::OnOff]).unwrap();
+/// assert_eq!(device.registered_traits(), &[Trait::OnOff]);
+///
+/// // MyDevice does not implement Brightness
+/// assert!(register_traits!(device, &[Trait::Brightness]).is_err());
+/// ```
+#[macro_export]
+macro_rules! register_traits {
+    ($device:expr, $traits:expr) => {{
+        let device = &mut $device;
+        let mut result = ::std::result::Result::Ok(());
+        for device_trait in $traits {
+            let registered = $crate::__registrable_traits!([$crate::__register_trait](device, device_trait));
+            if let ::std::result::Result::Err(message) = registered {
+                result = ::std::result::Result::Err($crate::ValidationError {
+                    device_trait: ::std::clone::Clone::clone(device_trait),
+                    message: message.to_string(),
+                });
+                break;
+            }
+        }
+
+        result
+    }};
+}
+
+/// Register `device_trait` on `device` if the device implements it, returning why it didn't otherwise.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __register_trait {
+    (($device:ident, $device_trait:ident) $($variant:ident => $setter:ident: $module:ident::$trait_name:ident;)*) => {
+        match $device_trait {
+            $(
+                $crate::Trait::$variant => {
+                    use $crate::register_traits::$setter::{Fallback as _, Register as _};
+                    if (&mut $crate::register_traits::Probe(&mut *$device)).register() {
+                        ::std::result::Result::Ok(())
+                    } else {
+                        ::std::result::Result::Err("not implemented by the device")
+                    }
+                }
+            )*
+            $crate::Trait::ObjectDetection => ::std::result::Result::Err("only used for notifications, it can't be registered"),
+            // Every other trait is listed, which is checked when defining the probes
+            _ => ::std::result::Result::Err("can't be registered"),
+        }
+    };
+}
+
+/// The traits which can be registered with [register_traits!](crate::register_traits), with their setter and Rust trait.
+/// Passes the list to the macro `[$callback]`, after `$args`.
+/// This is the only list of traits, both the probes and the match in [register_traits!](crate::register_traits) are generated from it.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __registrable_traits {
+    ([$($callback:tt)*] $args:tt) => {
+        $($callback)*! {
+            $args
+            AppSelector => set_app_selector: app_selector::AppSelector;
+            ArmDisarm => set_arm_disarm: arm_disarm::ArmDisarm;
+            Brightness => set_brightness: brightness::Brightness;
+            CameraStream => set_camera_stream: camera_stream::CameraStream;
+            Channel => set_channel: channel::Channel;
+            ColorSetting => set_color_setting: color_setting::ColorSetting;
+            Cook => set_cook: cook::Cook;
+            Dispense => set_dispense: dispense::Dispense;
+            Dock => set_dock: dock::Dock;
+            EnergyStorage => set_energy_storage: energy_storage::EnergyStorage;
+            FanSpeed => set_fan_speed: fan_speed::FanSpeed;
+            Fill => set_fill: fill::Fill;
+            HumiditySetting => set_humidity_setting: humidity_setting::HumiditySetting;
+            InputSelector => set_input_selector: input_selector::InputSelector;
+            LightEffects => set_light_effects: light_effects::LightEffects;
+            Locator => set_locator: locator::Locator;
+            LockUnlock => set_lock_unlock: lock_unlock::LockUnlock;
+            MediaState => set_media_state: media_state::MediaState;
+            Modes => set_modes: modes::Modes;
+            NetworkControl => set_network_control: network_control::NetworkControl;
+            OnOff => set_on_off: on_off::OnOff;
+            OpenClose => set_open_close: open_close::OpenClose;
+            Reboot => set_reboot: reboot::Reboot;
+            Rotation => set_rotation: rotation::Rotation;
+            RunCycle => set_run_cycle: run_cycle::RunCycle;
+            Scene => set_scene: scene::Scene;
+            SensorState => set_sensor_state: sensor_state::SensorState;
+            SoftwareUpdate => set_software_update: software_update::SoftwareUpdate;
+            StartStop => set_start_stop: start_stop::StartStop;
+            StatusReport => set_status_report: status_report::StatusReport;
+            TemperatureControl => set_temperature_control: temperature_control::TemperatureControl;
+            TemperatureSetting => set_temperature_setting: temperature_setting::TemperatureSetting;
+            Timer => set_timer: timer::Timer;
+            Toggles => set_toggles: toggles::Toggles;
+            TransportControl => set_transport_control: transport_control::TransportControl;
+            Volume => set_volume: volume::Volume;
+        }
+    };
+}
+
+/// Wraps a device to find out which traits it implements, see [register_traits!](crate::register_traits)
+#[doc(hidden)]
+pub struct Probe<'a, T: GoogleHomeDevice + Debug + Send + Sync + 'static>(pub &'a mut Device<T>);
+
+/// Define a module per trait, with a `Register` trait which registers the trait if the device implements it,
+/// and a `Fallback` trait which is picked by method resolution otherwise.
+/// Fails to compile if a [Trait] is missing from the list.
+macro_rules! probes {
+    (() $($variant:ident => $setter:ident: $module:ident::$trait_name:ident;)*) => {
+        #[allow(dead_code)]
+        fn all_traits_registrable(device_trait: &Trait) {
+            match device_trait {
+                $(Trait::$variant => {})*
+                Trait::ObjectDetection => {}
+            }
+        }
+
+        $(
+            #[doc(hidden)]
+            pub mod $setter {
+                use super::Probe;
+                use crate::traits::$module::$trait_name;
+                use crate::GoogleHomeDevice;
+                use std::fmt::Debug;
+
+                pub trait Register {
+                    fn register(&mut self) -> bool;
+                }
+
+                impl<T: $trait_name + GoogleHomeDevice + Debug + Send + Sync + 'static> Register for Probe<'_, T> {
+                    fn register(&mut self) -> bool {
+                        self.0.$setter();
+                        true
+                    }
+                }
+
+                pub trait Fallback {
+                    fn register(&mut self) -> bool {
+                        false
+                    }
+                }
+
+                impl<T: GoogleHomeDevice + Debug + Send + Sync + 'static> Fallback for &mut Probe<'_, T> {}
+            }
+        )*
+    };
+}
+
+crate::__registrable_traits!([probes]());
@@ -0,0 +1,50 @@
+use crate::fulfillment::request::Request;
+use crate::fulfillment::response::Response;
+use crate::Homelander;
+use std::collections::HashMap;
+use tracing::instrument;
+
+/// Keeps track of the [Homelander] of every user.
+///
+/// Requests are routed to the [Homelander] of the agent user they belong to, creating it if it doesn't exist yet.
+/// When shared between request handlers, wrap the registry in a single lock instead of locking every [Homelander].
+#[derive(Debug, Default)]
+pub struct HomelanderRegistry {
+    users: HashMap<String, Homelander>,
+}
+
+impl HomelanderRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the [Homelander] of the user with ID `user_id`
+    pub fn get<S: AsRef<str>>(&self, user_id: S) -> Option<&Homelander> {
+        self.users.get(user_id.as_ref())
+    }
+
+    /// Get the [Homelander] of the user with ID `user_id`, creating it if the user is not known yet
+    pub fn get_or_create<S: AsRef<str>>(&mut self, user_id: S) -> &mut Homelander {
+        let user_id = user_id.as_ref();
+        self.users.entry(user_id.to_string()).or_insert_with(|| Homelander::new(user_id.to_string()))
+    }
+
+    /// Remove the user with ID `user_id` and all of their devices
+    pub fn remove<S: AsRef<str>>(&mut self, user_id: S) -> Option<Homelander> {
+        self.users.remove(user_id.as_ref())
+    }
+
+    /// Handle an incomming fulfillment request from Google for the user with ID `user_id`.
+    /// The user ID is usually derived from the access token Google sent along with the request.
+    #[instrument(skip(self, request))]
+    pub fn handle_request<S: AsRef<str> + std::fmt::Debug>(&mut self, user_id: S, request: Request) -> Response {
+        self.get_or_create(user_id).handle_request(request)
+    }
+
+    /// Handle an incomming fulfillment request from Google for the user with ID `user_id`, including async devices.
+    #[cfg(feature = "async")]
+    #[instrument(skip(self, request))]
+    pub async fn handle_request_async<S: AsRef<str> + std::fmt::Debug>(&mut self, user_id: S, request: Request) -> Response {
+        self.get_or_create(user_id).handle_request_async(request).await
+    }
+}
@@ -2,9 +2,9 @@ use serde::{Serialize, Serializer};
 use std::error::Error;
 use std::fmt;
 
-pub trait ToStringError: Error + ToString + 'static {}
+pub trait ToStringError: Error + ToString + Send + Sync + 'static {}
 
-impl<T: Error + ToString + 'static> ToStringError for T {}
+impl<T: Error + ToString + Send + Sync + 'static> ToStringError for T {}
 
 pub struct SerializableError(pub(crate) Box<dyn ToStringError>);
 
@@ -0,0 +1,194 @@
+//! Helpers to test the wiring of your devices, without standing up a [Homelander] and building requests by hand.
+//!
+//! ```
+//! # use homelander::{Device, DeviceType, Trait};
+//! # use homelander::fulfillment::request::execute::CommandType;
+//! # use homelander::testkit::TraitHarness;
+//! # use homelander::traits::{CombinedDeviceError, DeviceInfo, DeviceName, GoogleHomeDevice};
+//! # use homelander::traits::on_off::OnOff;
+//! # #[derive(Debug)]
+//! # struct MyDevice(bool);
+//! #
+//! # impl GoogleHomeDevice for MyDevice {
+//! #    fn get_device_info(&self) -> DeviceInfo {
+//! #        DeviceInfo {
+//! #            model: "mydevice".to_string(),
+//! #            manufacturer: "mydevice company".to_string(),
+//! #            hw: "0.1.0".to_string(),
+//! #            sw: "0.1.0".to_string(),
+//! #        }
+//! #    }
+//! #
+//! #    fn will_report_state(&self) -> bool {
+//! #        false
+//! #    }
+//! #
+//! #    fn get_device_name(&self) -> DeviceName {
+//! #        DeviceName {
+//! #            name: "MyDevice".to_string(),
+//! #            default_names: Vec::new(),
+//! #            nicknames: Vec::new(),
+//! #        }
+//! #    }
+//! #
+//! #    fn is_online(&self) -> bool {
+//! #        true
+//! #    }
+//! #
+//! #    fn disconnect(&mut self) {}
+//! # }
+//! #
+//! # impl OnOff for MyDevice {
+//! #    fn is_on(&self) -> Result<bool, CombinedDeviceError> {
+//! #        Ok(self.0)
+//! #    }
+//! #
+//! #    fn set_on(&mut self, on: bool) -> Result<(), CombinedDeviceError> {
+//! #        self.0 = on;
+//! #        Ok(())
+//! #    }
+//! # }
+//! let mut device = Device::new(MyDevice(false), DeviceType::Outlet, "my_id".to_string());
+//! device.set_on_off();
+//!
+//! TraitHarness::new(device)
+//!     .assert_sync_traits(&[Trait::OnOff])
+//!     .assert_execute(CommandType::OnOff { on: true })
+//!     .assert_query_state(serde_json::json!({ "on": true }));
+//! ```
+
+use crate::fulfillment::request::execute::{Command, CommandType, Execute, Execution};
+use crate::fulfillment::request::{query, Input};
+use crate::fulfillment::response::execute::CommandStatus;
+use crate::fulfillment::response::{self, ResponsePayload};
+use crate::{Device, DeviceTraits, Homelander, Request, Trait};
+use serde_json::Value;
+
+/// Drives SYNC, QUERY and EXECUTE requests for a single device and asserts on the responses.
+/// All assertions panic with a description of the mismatch.
+#[derive(Debug)]
+pub struct TraitHarness {
+    homelander: Homelander,
+    device_id: String,
+}
+
+impl TraitHarness {
+    /// Register `device` with a new [Homelander]
+    pub fn new<T: DeviceTraits>(device: Device<T>) -> Self {
+        let device_id = device.id.clone();
+        let mut homelander = Homelander::new("testkit".to_string());
+        homelander.add_device(device);
+
+        Self { homelander, device_id }
+    }
+
+    /// The [Homelander] the device is registered with
+    pub fn homelander(&mut self) -> &mut Homelander {
+        &mut self.homelander
+    }
+
+    /// Run a SYNC intent and return the device in the response
+    pub fn sync(&mut self) -> response::sync::Device {
+        let request = self.request(Input::Sync);
+        let payload = match self.homelander.handle_request(request).payload {
+            ResponsePayload::Sync(payload) => payload,
+            payload => panic!("Expected a SYNC payload, got {payload:?}"),
+        };
+
+        payload
+            .devices
+            .into_iter()
+            .find(|device| device.id == self.device_id)
+            .unwrap_or_else(|| panic!("Device '{}' is missing from the SYNC response", self.device_id))
+    }
+
+    /// Run a QUERY intent and return the state of the device as JSON
+    pub fn query(&mut self) -> Value {
+        let request = self.request(Input::Query(query::Payload {
+            devices: vec![query::Device { id: self.device_id.clone() }],
+        }));
+        let mut payload = match self.homelander.handle_request(request).payload {
+            ResponsePayload::Query(payload) => payload,
+            payload => panic!("Expected a QUERY payload, got {payload:?}"),
+        };
+
+        let state = payload
+            .devices
+            .remove(&self.device_id)
+            .unwrap_or_else(|| panic!("Device '{}' is missing from the QUERY response", self.device_id));
+        serde_json::to_value(state).expect("Serializing QUERY state")
+    }
+
+    /// Run an EXECUTE intent with `command` and return the result for the device
+    pub fn execute(&mut self, command: CommandType) -> response::execute::Command {
+        let request = self.request(Input::Execute(Execute {
+            commands: vec![Command {
+                devices: vec![crate::fulfillment::request::execute::Device {
+                    id: self.device_id.clone(),
+                    custom_data: None,
+                }],
+                execution: vec![Execution { command, challenge: None }],
+            }],
+            structure_data: None,
+        }));
+        let mut payload = match self.homelander.handle_request(request).payload {
+            ResponsePayload::Execute(payload) => payload,
+            payload => panic!("Expected an EXECUTE payload, got {payload:?}"),
+        };
+
+        if payload.commands.is_empty() {
+            panic!("Device '{}' is missing from the EXECUTE response", self.device_id);
+        }
+        payload.commands.remove(0)
+    }
+
+    /// Assert that SYNC lists exactly `traits`, in any order
+    pub fn assert_sync_traits(&mut self, traits: &[Trait]) -> &mut Self {
+        let synced = self.sync().traits;
+        let missing = traits.iter().filter(|t| !synced.contains(t)).collect::<Vec<_>>();
+        let unexpected = synced.iter().filter(|t| !traits.contains(t)).collect::<Vec<_>>();
+
+        assert!(missing.is_empty() && unexpected.is_empty(), "SYNC traits mismatch. Missing: {missing:?}, unexpected: {unexpected:?}");
+        self
+    }
+
+    /// Assert that the QUERY state contains `expected`.
+    /// Fields not present in `expected` are not checked, nested objects are compared the same way.
+    pub fn assert_query_state(&mut self, expected: Value) -> &mut Self {
+        let state = self.query();
+        if let Err(path) = contains(&state, &expected, "") {
+            panic!("QUERY state mismatch at '{path}'.\nExpected: {expected}\nActual: {state}");
+        }
+        self
+    }
+
+    /// Assert that `command` executes successfully
+    pub fn assert_execute(&mut self, command: CommandType) -> &mut Self {
+        let description = format!("{command:?}");
+        let result = self.execute(command);
+        assert_eq!(CommandStatus::Success, result.status, "EXECUTE of {description} failed with {:?}: {:?}", result.error_code, result.debug_string);
+        self
+    }
+
+    fn request(&self, input: Input) -> Request {
+        Request {
+            request_id: "testkit".to_string(),
+            inputs: vec![input],
+        }
+    }
+}
+
+/// Check if `actual` contains all fields in `expected`. On a mismatch, the path to the mismatching field is returned.
+fn contains(actual: &Value, expected: &Value, path: &str) -> Result<(), String> {
+    match (actual, expected) {
+        (Value::Object(actual), Value::Object(expected)) => expected.iter().try_for_each(|(key, expected)| {
+            let path = format!("{path}.{key}");
+            match actual.get(key) {
+                Some(actual) => contains(actual, expected, &path),
+                None => Err(path),
+            }
+        }),
+        (actual, expected) if actual == expected => Ok(()),
+        _ => Err(path.to_string()),
+    }
+}
@@ -13,17 +13,30 @@ pub struct AvailableArmLevels {
 #[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ArmLevel {
     /// The internal name of the security level that is used in commands and states. This name can be non-user-friendly and is shared across all languages.
-    level_name: String,
-    level_values: Vec<LevelValue>,
+    pub level_name: String,
+    /// The names of the level in each supported language.
+    pub level_values: Vec<LevelValue>,
+}
+
+impl ArmLevel {
+    pub fn new(level_name: String, level_values: Vec<LevelValue>) -> Self {
+        Self { level_name, level_values }
+    }
 }
 
 /// Contains `level_synonym` and `lang`.
 #[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct LevelValue {
     /// User-friendly names for the level in each supported language. The first item is treated as the canonical name.
-    level_synonym: Vec<String>,
+    pub level_synonym: Vec<String>,
     /// Language code for the level synonyms.
-    lang: Language,
+    pub lang: Language,
+}
+
+impl LevelValue {
+    pub fn new(level_synonym: Vec<String>, lang: Language) -> Self {
+        Self { level_synonym, lang }
+    }
 }
 
 /// An error occurred arming or disarming the device.
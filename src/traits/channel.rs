@@ -18,6 +18,7 @@ pub struct AvailableChannel {
 /// during SYNC via the availableChannels attribute.
 /// This list should comprise of all top or popular channels that the user or device is subscribed to.
 /// To ensure a low query latency, we recommend that you keep the channel list small (to 30 channels or less).
+/// Long channel lists also grow the SYNC response, which Google limits in size. Use [Homelander::sync_estimated_size](crate::Homelander::sync_estimated_size) to check it.
 pub trait Channel {
     /// List of objects describing available media channels for this particular device.
     /// Each item describes a channel the user can select on this device.
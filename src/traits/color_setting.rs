@@ -1,5 +1,6 @@
-use crate::traits::CombinedDeviceError;
-use serde::{Deserialize, Serialize};
+use crate::traits::{CombinedDeviceError, DeviceError};
+use serde::de::Error;
+use serde::{Deserialize, Deserializer, Serialize};
 
 /// Color model support. At least one of the fields has to be [Some]
 #[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
@@ -12,6 +13,31 @@ pub struct ColorModelSupport {
     pub color_temperature_range: Option<ColorTemperatureRange>,
 }
 
+impl ColorModelSupport {
+    /// Create the color model support. At least one of `color_model` and `color_temperature_range` has to be [Some]
+    pub fn new(color_model: Option<ColorModel>, color_temperature_range: Option<ColorTemperatureRange>) -> Self {
+        Self {
+            color_model,
+            color_temperature_range,
+        }
+    }
+
+    /// Whether the device can be set to the color in `command`
+    pub(crate) fn supports(&self, command: &ColorCommand) -> bool {
+        match command {
+            ColorCommand::Temperature(temperature) => self
+                .color_temperature_range
+                .as_ref()
+                .map(|range| (range.temperature_min_k..=range.temperature_max_k).contains(temperature))
+                .unwrap_or(false),
+            ColorCommand::SpectrumRgb(rgb) => self.color_model == Some(ColorModel::Rgb) && (0..=0xFFFFFF).contains(rgb),
+            ColorCommand::SpectrumHsv(_) => self.color_model == Some(ColorModel::Hsv),
+            // Whether the name is known is up to the device
+            ColorCommand::Name(_) => true,
+        }
+    }
+}
+
 /// Supported color temperature range in Kelvin.
 #[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ColorTemperatureRange {
@@ -23,6 +49,15 @@ pub struct ColorTemperatureRange {
     pub temperature_max_k: i32,
 }
 
+impl ColorTemperatureRange {
+    pub fn new(temperature_min_k: i32, temperature_max_k: i32) -> Self {
+        Self {
+            temperature_min_k,
+            temperature_max_k,
+        }
+    }
+}
+
 /// Full spectrum color model supported by the device.
 #[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ColorModel {
@@ -32,18 +67,26 @@ pub enum ColorModel {
     Hsv,
 }
 
+/// The current color of a device, reported in QUERY.
+/// Note that Google uses different keys in the state than in [ColorCommand], e.g. `temperatureK` instead of `temperature`.
 #[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Color {
-    #[serde(rename = "temperatureK")]
+    /// Color temperature in Kelvin
+    #[serde(rename = "temperatureK", skip_serializing_if = "Option::is_none")]
     pub temperature_k: Option<i32>,
-    #[serde(rename = "spectrumRgb")]
+    /// Spectrum value as a decimal integer
+    #[serde(rename = "spectrumRgb", skip_serializing_if = "Option::is_none")]
     pub spectrum_rgb: Option<i32>,
-    #[serde(rename = "spectrumHsv")]
+    /// Spectrum HSV value
+    #[serde(rename = "spectrumHsv", skip_serializing_if = "Option::is_none")]
     pub spectrum_hsv: Option<SpectrumHsv>,
 }
 
-/// Coloor to set
-#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+/// Coloor to set.
+///
+/// Google sends the name the user spoke along with the value of a named color, e.g. `{ "name": "magenta", "spectrumRGB": 16711935 }`.
+/// The value is used if there is one, [ColorCommand::Name] is only used for colors sent without a value.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
 pub enum ColorCommand {
     /// Temperature value in Kelvin
     #[serde(rename = "temperature")]
@@ -54,6 +97,40 @@ pub enum ColorCommand {
     /// Spectrum HSV value
     #[serde(rename = "spectrumHSV")]
     SpectrumHsv(SpectrumHsv),
+    /// Name of the color, e.g. `magenta`
+    #[serde(rename = "name")]
+    Name(String),
+}
+
+impl<'de> Deserialize<'de> for ColorCommand {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        /// The color as sent by Google, which may contain both a name and a value
+        #[derive(Deserialize)]
+        struct RawColor {
+            name: Option<String>,
+            temperature: Option<i32>,
+            #[serde(rename = "spectrumRGB")]
+            spectrum_rgb: Option<i32>,
+            #[serde(rename = "spectrumHSV")]
+            spectrum_hsv: Option<SpectrumHsv>,
+        }
+
+        let color = RawColor::deserialize(deserializer)?;
+        if let Some(temperature) = color.temperature {
+            Ok(Self::Temperature(temperature))
+        } else if let Some(rgb) = color.spectrum_rgb {
+            Ok(Self::SpectrumRgb(rgb))
+        } else if let Some(hsv) = color.spectrum_hsv {
+            Ok(Self::SpectrumHsv(hsv))
+        } else if let Some(name) = color.name {
+            Ok(Self::Name(name))
+        } else {
+            Err(D::Error::custom("expected one of `temperature`, `spectrumRGB`, `spectrumHSV` or `name`"))
+        }
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
@@ -74,6 +151,13 @@ pub trait ColorSetting {
     /// The current color setting currently being used on the device.
     fn get_color(&self) -> Result<Color, CombinedDeviceError>;
 
-    /// Set a color
+    /// Set a color. Never called with [ColorCommand::Name], see [Self::set_color_by_name]
     fn set_color(&mut self, command: ColorCommand) -> Result<(), CombinedDeviceError>;
+
+    /// Set a color by its name, e.g. `magenta`. Only called if Google sent the name without the value of the color.
+    ///
+    /// Default: not supported
+    fn set_color_by_name(&mut self, _name: String) -> Result<(), CombinedDeviceError> {
+        Err(CombinedDeviceError::DeviceError(DeviceError::NotSupported))
+    }
 }
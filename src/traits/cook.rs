@@ -1,4 +1,4 @@
-use crate::traits::{SizeUnit, Synonym};
+use crate::traits::{DeviceError, SizeUnit, Synonym};
 use crate::CombinedDeviceError;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
@@ -72,12 +72,15 @@ pub struct CookingConfig {
     /// The name of the food preset requested by the user, from foodPresets attribute.
     pub food_preset: Option<String>,
     /// The quantity of the food requested by the user.
-    pub quantity: Option<i32>,
+    pub quantity: Option<f32>,
     /// The unit associated with the quantity, from supported_units attribute.
     pub unit: Option<SizeUnit>,
 }
 
 /// This trait belongs to devices that can cook food according to various food presets and supported cooking modes.
+///
+/// Before [Self::start] is called, the food preset, unit and quantity requested by the user are checked
+/// against [Self::get_food_presets], [Self::get_max_food_quantity] and [Self::supports_fractional_food_quantity].
 pub trait Cook {
     /// Cooking modes supported by this device.
     fn get_supported_cooking_modes(&self) -> Result<Vec<CookingMode>, CookError>;
@@ -85,12 +88,26 @@ pub trait Cook {
     /// Presets for certain types of food.
     fn get_food_presets(&self) -> Result<Vec<FoodPreset>, CookError>;
 
+    /// The largest quantity of `food_preset`, in `unit`, the device can cook at once.
+    /// Larger quantities are rejected with [CookError::AmountAboveLimit].
+    /// Default: None, there is no limit
+    fn get_max_food_quantity(&self, _food_preset: &str, _unit: &SizeUnit) -> Result<Option<f32>, CookError> {
+        Ok(None)
+    }
+
+    /// Whether the device can cook a fractional quantity of food, e.g. 1.5 cups.
+    /// If not, such quantities are rejected with [CookError::FractionalAmountNotSupported].
+    /// Default: true
+    fn supports_fractional_food_quantity(&self) -> Result<bool, CookError> {
+        Ok(true)
+    }
+
     /// Describes the current cooking mode set on the device, from the list of supportedCookingModes attribute. Only one mode may be reported.
     /// If no mode is currently selected, this should be set to [CookingMode::None].
     fn get_current_cooking_mode(&self) -> Result<CookingMode, CookError>;
 
     /// Describes the current food cooking in the device, from the list of foodPresets attribute.
-    /// Only one food may be reported. If no food is currently selected, this should be set to NONE.
+    /// Only one food may be reported. If no food is currently selected, return [None] and the preset is left out of QUERY.
     fn get_current_food_preset(&self) -> Result<Option<String>, CookError>;
 
     /// Defines the current amount of food cooking associated with the currentFoodUnit,
@@ -107,3 +124,39 @@ pub trait Cook {
     /// Stop the current cooking mode
     fn stop(&mut self) -> Result<(), CookError>;
 }
+
+/// Check the food preset, unit and quantity in `config` against the capabilities of `device`
+pub(crate) fn validate_cooking_config<D: Cook + ?Sized>(device: &D, config: &CookingConfig) -> Result<(), CookError> {
+    let food_preset = match &config.food_preset {
+        Some(food_preset) => food_preset,
+        None => return Ok(()),
+    };
+
+    let presets = device.get_food_presets()?;
+    let preset = presets
+        .iter()
+        .find(|preset| preset.food_preset_name.eq(food_preset))
+        .ok_or(CookError::UnknownFoodPreset)?;
+
+    if let Some(unit) = &config.unit {
+        if !preset.supported_unit.contains(unit) {
+            return Err(CookError::Other(CombinedDeviceError::DeviceError(DeviceError::FunctionNotSupported)));
+        }
+    }
+
+    if let Some(quantity) = config.quantity {
+        if quantity.fract() != 0.0 && !device.supports_fractional_food_quantity()? {
+            return Err(CookError::FractionalAmountNotSupported);
+        }
+
+        if let Some(unit) = &config.unit {
+            if let Some(max) = device.get_max_food_quantity(food_preset, unit)? {
+                if quantity > max {
+                    return Err(CookError::AmountAboveLimit);
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -6,30 +6,30 @@ use thiserror::Error;
 #[derive(Debug, PartialEq, Serialize)]
 pub struct DispenseItem {
     /// Internal name for the dispensed item. This can be non-user-friendly, and is shared across all languages.
-    item_name: String,
+    pub item_name: String,
     /// Synonyms names for the dispensed in each supported language.
-    item_name_synonyms: Vec<Synonym>,
+    pub item_name_synonyms: Vec<Synonym>,
     /// Set of units the device supports for that item.
-    supported_units: Vec<SizeUnit>,
+    pub supported_units: Vec<SizeUnit>,
     /// Typical amount of the item that may be dispensed.
-    default_portion: DispenseAmount,
+    pub default_portion: DispenseAmount,
 }
 
 #[derive(Debug, PartialEq, Serialize)]
 pub struct DispenseAmount {
     /// Dispensed amount.
-    amount: f32,
+    pub amount: f32,
     /// Dispensed unit.
-    unit: SizeUnit,
+    pub unit: SizeUnit,
 }
 
 /// Preset.
 #[derive(Debug, PartialEq, Serialize)]
 pub struct DispensePreset {
     /// Internal name for the preset. This name can be non-user-friendly, and is shared across all languages.
-    preset_name: String,
+    pub preset_name: String,
     /// Synonym names for the preset in each supported language.
-    preset_name_synonyms: Vec<Synonym>,
+    pub preset_name_synonyms: Vec<Synonym>,
 }
 
 #[derive(Debug, Error, PartialEq, Serialize)]
@@ -101,15 +101,15 @@ pub enum DispenseError {
 #[serde(rename_all = "camelCase")]
 pub struct DispenseItemState {
     /// Name of the item from the item_name attribute.
-    item_name: String,
+    pub item_name: String,
     /// Amount of that item remaining in the device. If the device is currently dispensing,
     /// this reports the remaining amount or what the amount will be once the device finishes dispensing.
-    amount_remaining: DispenseAmount,
+    pub amount_remaining: DispenseAmount,
     /// Amount of that item that the device most recently dispensed. If the device is currently dispensing,
     /// this should report the amount it dispensed prior to the current dispensing amount.
-    amount_last_dispensed: DispenseAmount,
+    pub amount_last_dispensed: DispenseAmount,
     /// Indicates if the device is currently dispensing this item.
-    is_currently_dispensing: bool,
+    pub is_currently_dispensing: bool,
 }
 
 /// This trait belongs to devices that support dispensing a specified amount of one or more physical items.
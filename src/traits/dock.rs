@@ -1,4 +1,4 @@
-use crate::CombinedDeviceError;
+use crate::traits::{CombinedDeviceError, DeviceError};
 
 /// This trait is designed for self-mobile devices that can be commanded to return for charging.
 pub trait Dock {
@@ -6,4 +6,14 @@ pub trait Dock {
     fn is_docked(&self) -> Result<bool, CombinedDeviceError>;
     /// Dock the device.
     fn dock(&mut self) -> Result<(), CombinedDeviceError>;
+
+    /// Undock the device.
+    ///
+    /// Google does not currently define an undock command, so this is never called by Homelander.
+    /// It is provided for devices which can also leave their docking station.
+    ///
+    /// Default: not supported
+    fn undock(&mut self) -> Result<(), CombinedDeviceError> {
+        Err(CombinedDeviceError::DeviceError(DeviceError::NotSupported))
+    }
 }
@@ -25,7 +25,7 @@ pub enum FanSpeedError {
 /// Speed settings supported by the device.
 #[derive(Debug, PartialEq, Serialize)]
 pub struct AvailableFanSpeeds {
-    /// If set to true, additional grammar for increase or decrease logic will apply, in the order (increasing) of the speeds array.
+    /// Speed settings, in increasing order if [Self::ordered] is set.
     pub speeds: Vec<FanSpeedItem>,
     /// If set to true, additional grammar for increase or decrease logic will apply, in the order (increasing) of the speeds array.
     pub ordered: bool,
@@ -35,9 +35,15 @@ pub struct AvailableFanSpeeds {
 #[derive(Debug, PartialEq, Serialize)]
 pub struct FanSpeedItem {
     /// Internal name of the speed setting. This can be non-user-friendly, and will be shared across all languages.
-    speed_name: String,
+    pub speed_name: String,
     /// Synonyms for the speed setting in each supported languages.
-    speed_values: Vec<FanSpeedValue>,
+    pub speed_values: Vec<FanSpeedValue>,
+}
+
+impl FanSpeedItem {
+    pub fn new(speed_name: String, speed_values: Vec<FanSpeedValue>) -> Self {
+        Self { speed_name, speed_values }
+    }
 }
 
 /// Synonym for the speed setting in a given language.
@@ -45,9 +51,15 @@ pub struct FanSpeedItem {
 pub struct FanSpeedValue {
     /// Synonyms for the speed setting, should include both singular and plural forms, if applicable.
     /// The first synonym in the list will be considered the canonical name of the speed setting.
-    speed_synonym: Vec<String>,
+    pub speed_synonym: Vec<String>,
     /// Language code
-    lang: Language,
+    pub lang: Language,
+}
+
+impl FanSpeedValue {
+    pub fn new(speed_synonym: Vec<String>, lang: Language) -> Self {
+        Self { speed_synonym, lang }
+    }
 }
 
 /// This trait belongs to devices that support setting the speed of a fan (that is, blowing air from the device at various levels,
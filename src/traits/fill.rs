@@ -10,9 +10,20 @@ pub struct AvailableFillLevels {
     /// If true, the device handles additional grammar for increase or decrease logic, as represented by the levels array (for example, half level).
     pub ordered: bool,
     /// If true, accept commands for adjusting the level to a percentage.
+    #[serde(rename = "supportsFillPercent")]
     pub supports_fill_percent: bool,
 }
 
+impl AvailableFillLevels {
+    pub fn new(levels: Vec<FillLevel>, ordered: bool, supports_fill_percent: bool) -> Self {
+        Self {
+            levels,
+            ordered,
+            supports_fill_percent,
+        }
+    }
+}
+
 /// Level name and its language-specific synonyms.
 #[derive(Debug, PartialEq, Serialize)]
 pub struct FillLevel {
@@ -22,6 +33,12 @@ pub struct FillLevel {
     pub level_values: Vec<LevelValue>,
 }
 
+impl FillLevel {
+    pub fn new(level_name: String, level_values: Vec<LevelValue>) -> Self {
+        Self { level_name, level_values }
+    }
+}
+
 /// Synonyms of the level in a given language.
 #[derive(Debug, PartialEq, Serialize)]
 pub struct LevelValue {
@@ -31,6 +48,12 @@ pub struct LevelValue {
     pub lang: Language,
 }
 
+impl LevelValue {
+    pub fn new(level_synonym: Vec<String>, lang: Language) -> Self {
+        Self { level_synonym, lang }
+    }
+}
+
 /// This trait applies to devices that support being filled, such as a bathtub.
 pub trait Fill {
     /// Describes different levels of filling the device.
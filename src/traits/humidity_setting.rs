@@ -6,10 +6,17 @@ use serde::Serialize;
 pub struct HumiditySetPointRange {
     /// Represents the minimum humdity level as a percentage.
     /// Default: 0
-    min_percent: Option<i32>,
+    pub min_percent: Option<i32>,
     /// Represents the maximum humidity level as a percentage.
     /// Default: 100
-    max_percent: Option<i32>,
+    pub max_percent: Option<i32>,
+}
+
+impl HumiditySetPointRange {
+    /// Create the range. Bounds which are [None] default to 0 and 100 percent
+    pub fn new(min_percent: Option<i32>, max_percent: Option<i32>) -> Self {
+        Self { min_percent, max_percent }
+    }
 }
 
 /// This trait belongs to devices that support humidity settings such as humidifiers and dehumidifiers.
@@ -31,8 +38,8 @@ pub trait HumiditySetting {
         Ok(None)
     }
 
-    /// Indicates the current target humidity percentage of the device. Must fall within humiditySetpointRange.
-    fn get_current_humidity_set_point_range(&self) -> Result<i32, CombinedDeviceError>;
+    /// Indicates the current target humidity percentage of the device. Must fall within [Self::get_humidity_set_point_range_minmax].
+    fn get_current_humidity_setpoint_percent(&self) -> Result<i32, CombinedDeviceError>;
 
     /// Indicates the current ambient humidity reading of the device as a percentage.
     fn get_current_humidity_ambient_percent(&self) -> Result<i32, CombinedDeviceError>;
@@ -28,6 +28,12 @@ pub struct AvailableInput {
     pub names: Vec<InputName>,
 }
 
+impl AvailableInput {
+    pub fn new(key: String, names: Vec<InputName>) -> Self {
+        Self { key, names }
+    }
+}
+
 /// Input for a given available language.
 #[derive(Debug, PartialEq, Serialize)]
 pub struct InputName {
@@ -37,6 +43,12 @@ pub struct InputName {
     pub name_synonym: Vec<String>,
 }
 
+impl InputName {
+    pub fn new(lang: Language, name_synonym: Vec<String>) -> Self {
+        Self { lang, name_synonym }
+    }
+}
+
 /// Trait for devices that can change media inputs. These inputs can have dynamic names per device, and may represent audio or video feeds, hardwired or networked.
 pub trait InputSelector {
     /// List of objects representing input audio or video feeds.
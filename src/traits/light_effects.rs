@@ -49,11 +49,13 @@ pub trait LightEffects {
 
     /// Request the device to cycle through a set of colors.
     /// - `duration` Duration for the color loop command, in seconds.
+    ///
     /// Only called if [LightEffectType::ColorLoop] is among the supported effects
     fn set_color_loop(&mut self, duration: Option<i32>) -> Result<(), CombinedDeviceError>;
 
     /// Gradually lower the device's brightness and, optionally, adjusts the color temperature over a duration of time.
     /// - `duration` Duration for the sleep command, in seconds.
+    ///
     /// Only called if [LightEffectType::Sleep] is among the supported effects
     fn set_sleep(&mut self, duration: Option<i32>) -> Result<(), CombinedDeviceError>;
 
@@ -62,6 +64,7 @@ pub trait LightEffects {
 
     /// Gradually increase the device's brightness and, optionally, adjusts the color temperature over a duration of time.
     /// - `duration` Duration for the sleep command, in seconds.
+    ///
     /// Only called if [LightEffectType::Wake] is among the supported effects
     fn set_wake(&mut self, duration: Option<i32>) -> Result<(), CombinedDeviceError>;
 }
@@ -1,5 +1,8 @@
+use crate::ErrorCode;
 use serde::{Deserialize, Serialize};
+use std::fmt;
 use std::fmt::Debug;
+use std::str::FromStr;
 use thiserror::Error;
 
 pub mod app_selector;
@@ -54,6 +57,16 @@ pub struct DeviceName {
     pub nicknames: Vec<String>,
 }
 
+/// The ID of a device as known to the Local Home SDK, used for local fulfillment.
+///
+/// ## See also
+/// <https://developers.google.com/assistant/smarthome/concepts/local>
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OtherDeviceId {
+    pub device_id: String,
+}
+
 pub trait GoogleHomeDevice {
     fn get_device_info(&self) -> DeviceInfo;
 
@@ -61,13 +74,68 @@ pub trait GoogleHomeDevice {
         None
     }
 
+    /// Provides the structure (e.g. the home) in which this device is located.
+    /// Google uses this as a hint when the user has multiple structures.
+    /// Default: None
+    fn get_structure_hint(&self) -> Option<String> {
+        None
+    }
+
     fn will_report_state(&self) -> bool;
 
+    /// Indicates if the device sends proactive notifications to Google,
+    /// e.g. for ObjectDetection, RunCycle, SensorState or LockUnlock.
+    /// Google only accepts notifications for devices which advertise this during SYNC.
+    /// Default: false
+    fn supports_notifications(&self) -> bool {
+        false
+    }
+
     fn get_device_name(&self) -> DeviceName;
 
+    /// The IDs under which the device can be reached locally, enabling local fulfillment.
+    /// Default: empty
+    fn get_other_device_ids(&self) -> Vec<OtherDeviceId> {
+        Vec::new()
+    }
+
     /// Indicates if the device is online (that is, reachable) or not.
     fn is_online(&self) -> bool;
 
+    /// An error with the device itself, e.g. because it failed to initialize, reported during SYNC.
+    /// The device is still listed, so the other devices of the user are not affected.
+    /// Default: None
+    fn get_sync_error(&self) -> Option<ErrorCode> {
+        None
+    }
+
+    /// Custom data for this device, sent to Google during SYNC.
+    /// Google sends this data back with every EXECUTE for this device, see [Self::set_custom_data].
+    /// Default: None
+    fn get_custom_data(&self) -> Option<serde_json::Value> {
+        None
+    }
+
+    /// Called with the custom data Google sent along with an EXECUTE for this device, before the commands are executed.
+    /// This is the data returned from [Self::get_custom_data] during SYNC, or [None] if Google did not send any.
+    fn set_custom_data(&mut self, _custom_data: Option<serde_json::Value>) {}
+
+    /// Called with the ID of the request Google sent, before the commands in an EXECUTE for this device are executed.
+    /// Google may retry an EXECUTE with the same request ID, which allows commands to be handled idempotently.
+    /// [None] for commands which did not come from Google, see [Homelander::execute_command](crate::Homelander::execute_command).
+    fn set_request_id(&mut self, _request_id: Option<&str>) {}
+
+    /// Called with the secondary user verification Google sent along with a command, before the command is executed.
+    /// This is [None] until a trait asked for it by returning [CombinedDeviceError::ChallengeNeeded].
+    fn set_challenge(&mut self, _challenge: Option<Challenge>) {}
+
+    /// A message describing the error with code `error_code` in `language`, which Google may read to the user.
+    /// Only called when a command failed and a locale is set, see [Homelander::set_locale](crate::Homelander::set_locale).
+    /// Default: None
+    fn get_localized_error(&self, _error_code: &str, _language: &Language) -> Option<String> {
+        None
+    }
+
     /// This intent is triggered to inform you when a user has unlinked their device account from Google Assistant.
     ///
     /// This intent indicates that Google Assistant will not send additional intents for this user.
@@ -76,9 +144,25 @@ pub trait GoogleHomeDevice {
 }
 
 #[derive(Debug, PartialEq, Serialize, Error)]
+#[serde(rename_all = "camelCase")]
 pub enum DeviceError {
     // Todo
     // https://developers.google.com/assistant/smarthome/reference/errors-exceptions#error_list
+    /// The requested value is outside of the range supported by the device.
+    #[error("valueOutOfRange")]
+    ValueOutOfRange,
+    /// The command or its parameters are not supported by the device.
+    #[error("functionNotSupported")]
+    FunctionNotSupported,
+    /// The command is not supported in the current state of the device, e.g. its current mode.
+    #[error("notSupported")]
+    NotSupported,
+    /// The thermostat is in heat or cool mode, and can't be set to a range.
+    #[error("inHeatOrCool")]
+    InHeatOrCool,
+    /// The high and low setpoint of a thermostat are closer together than its buffer range.
+    #[error("rangeTooClose")]
+    RangeTooClose,
 }
 
 #[derive(Debug, PartialEq, Serialize, Error)]
@@ -95,44 +179,182 @@ pub enum CombinedDeviceError {
     DeviceException(DeviceException),
     #[error("{0}")]
     Other(#[from] crate::SerializableError),
+    /// The command requires secondary user verification.
+    /// Google will ask the user for it, and send the command again with a [Challenge].
+    #[error("challengeNeeded")]
+    ChallengeNeeded(ChallengeType),
+}
+
+impl CombinedDeviceError {
+    /// The `errorCode` and `debugString` to report this error to Google with, in any intent.
+    /// [Self::Other] has no error code Google knows about, so it is only described in the debug string.
+    pub fn to_google_error(&self) -> (Option<String>, Option<String>) {
+        match self {
+            Self::DeviceError(e) => (Some(ErrorCode::from_trait_error(e).to_string()), None),
+            Self::DeviceException(e) => (Some(ErrorCode::from_trait_error(e).to_string()), None),
+            Self::Other(e) => (None, Some(e.to_string())),
+            Self::ChallengeNeeded(_) => (Some(ErrorCode::ChallengeNeeded.to_string()), None),
+        }
+    }
+}
+
+/// Secondary user verification Google sends along with a command,
+/// after the device asked for it with [CombinedDeviceError::ChallengeNeeded].
+///
+/// ## See also
+/// <https://developers.google.com/assistant/smarthome/develop/secondary-user-verification>
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+pub struct Challenge {
+    /// The user confirmed the command.
+    pub ack: Option<bool>,
+    /// The PIN entered by the user.
+    pub pin: Option<String>,
+}
+
+impl Challenge {
+    /// Check if the user confirmed the command in `challenge`
+    pub fn check_ack(challenge: Option<&Challenge>) -> ChallengeResult {
+        match challenge.and_then(|c| c.ack) {
+            Some(true) => ChallengeResult::Passed,
+            _ => ChallengeResult::Ack,
+        }
+    }
+
+    /// Check if the user entered `pin` in `challenge`
+    pub fn check_pin(challenge: Option<&Challenge>, pin: &str) -> ChallengeResult {
+        match challenge.and_then(|c| c.pin.as_deref()) {
+            None => ChallengeResult::Pin,
+            Some(entered) if entered == pin => ChallengeResult::Passed,
+            Some(_) => ChallengeResult::Failed,
+        }
+    }
+}
+
+/// The outcome of checking the [Challenge] sent along with a command.
+///
+/// ```
+/// # use homelander::traits::{Challenge, CombinedDeviceError};
+/// fn unlock(challenge: Option<&Challenge>) -> Result<(), CombinedDeviceError> {
+///     Challenge::check_pin(challenge, "1234").into_result()?;
+///     // Unlock the device
+///     Ok(())
+/// }
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ChallengeResult {
+    /// The user passed the challenge, the command may be executed.
+    Passed,
+    /// The user has to confirm the command.
+    Ack,
+    /// The user has to enter a PIN.
+    Pin,
+    /// The user entered an incorrect PIN, and has to enter it again.
+    Failed,
+}
+
+impl ChallengeResult {
+    /// `Ok` if the challenge was passed, otherwise the error asking Google for the required verification
+    pub fn into_result(self) -> Result<(), CombinedDeviceError> {
+        match self {
+            Self::Passed => Ok(()),
+            Self::Ack => Err(CombinedDeviceError::ChallengeNeeded(ChallengeType::AckNeeded)),
+            Self::Pin => Err(CombinedDeviceError::ChallengeNeeded(ChallengeType::PinNeeded)),
+            Self::Failed => Err(CombinedDeviceError::ChallengeNeeded(ChallengeType::ChallengeFailedPinNeeded)),
+        }
+    }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
-pub enum Language {
-    #[serde(rename = "da")]
-    Danish,
-    #[serde(rename = "nl")]
-    Dutch,
-    #[serde(rename = "en")]
-    English,
-    #[serde(rename = "fr")]
-    French,
-    #[serde(rename = "de")]
-    German,
-    #[serde(rename = "hi")]
-    Hindi,
-    #[serde(rename = "id")]
-    Indonesian,
-    #[serde(rename = "it")]
-    Italian,
-    #[serde(rename = "ja")]
-    Japanese,
-    #[serde(rename = "ko")]
-    Korean,
-    #[serde(rename = "no")]
-    Norwegian,
-    #[serde(rename = "pt-BR")]
-    Portuguese,
-    #[serde(rename = "es")]
-    Spanish,
-    #[serde(rename = "sv")]
-    Swedish,
-    #[serde(rename = "th")]
-    Thai,
-    #[serde(rename = "zh-TW")]
-    Chinese,
+/// The type of secondary user verification required for a command.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ChallengeType {
+    /// The user has to confirm the command.
+    AckNeeded,
+    /// The user has to enter a PIN.
+    PinNeeded,
+    /// The user entered an incorrect PIN, and has to enter it again.
+    ChallengeFailedPinNeeded,
 }
 
+/// Define [Language] from a list of `Variant => "code"` pairs
+macro_rules! languages {
+    ($($(#[$attr:meta])* $variant:ident => $code:literal,)*) => {
+        /// A language supported by Google, identified by its BCP-47 code.
+        #[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+        pub enum Language {
+            $($(#[$attr])* #[serde(rename = $code)] $variant,)*
+        }
+
+        impl Language {
+            /// The BCP-47 code of the language
+            pub fn as_str(&self) -> &'static str {
+                match self {
+                    $(Self::$variant => $code,)*
+                }
+            }
+        }
+
+        impl FromStr for Language {
+            type Err = UnknownLanguage;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                match s {
+                    $($code => Ok(Self::$variant),)*
+                    s => Err(UnknownLanguage(s.to_string())),
+                }
+            }
+        }
+    };
+}
+
+languages! {
+    Danish => "da",
+    Dutch => "nl",
+    English => "en",
+    EnglishAustralia => "en-AU",
+    EnglishCanada => "en-CA",
+    EnglishUnitedKingdom => "en-GB",
+    EnglishIndia => "en-IN",
+    EnglishSingapore => "en-SG",
+    EnglishUnitedStates => "en-US",
+    French => "fr",
+    FrenchCanada => "fr-CA",
+    FrenchFrance => "fr-FR",
+    German => "de",
+    Hindi => "hi",
+    Indonesian => "id",
+    Italian => "it",
+    Japanese => "ja",
+    Korean => "ko",
+    Norwegian => "no",
+    Polish => "pl",
+    /// Portuguese as spoken in Brazil
+    Portuguese => "pt-BR",
+    PortuguesePortugal => "pt-PT",
+    Russian => "ru",
+    Spanish => "es",
+    SpanishLatinAmerica => "es-419",
+    SpanishSpain => "es-ES",
+    Swedish => "sv",
+    Thai => "th",
+    Turkish => "tr",
+    Vietnamese => "vi",
+    /// Traditional Chinese as used in Taiwan
+    Chinese => "zh-TW",
+    ChineseChina => "zh-CN",
+}
+
+impl fmt::Display for Language {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// The language code is not supported by Google
+#[derive(Debug, PartialEq, Eq, Error)]
+#[error("unknown language '{0}'")]
+pub struct UnknownLanguage(pub String);
+
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum SizeUnit {
@@ -164,6 +386,7 @@ pub enum SizeUnit {
 
 /// Supported temperature range of the device.
 #[derive(Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct TemperatureRange {
     /// Minimum temperature for the range, in degrees Celsius.
     pub min_threshold_celsius: f32,
@@ -180,8 +403,50 @@ pub enum TemperatureUnit {
     Fahrenheit,
 }
 
+/// A temperature, for converting between degrees Celsius and Fahrenheit.
+/// Google always expects temperatures in degrees Celsius, which is also how a [Temperature] is serialized.
+///
+/// ```
+/// # use homelander::traits::Temperature;
+/// let setpoint = Temperature::from_fahrenheit(68.0);
+/// assert_eq!(20.0, setpoint.as_celsius());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Temperature {
+    celsius: f32,
+}
+
+impl Temperature {
+    pub fn from_celsius(celsius: f32) -> Self {
+        Self { celsius }
+    }
+
+    pub fn from_fahrenheit(fahrenheit: f32) -> Self {
+        Self {
+            celsius: (fahrenheit - 32.0) * 5.0 / 9.0,
+        }
+    }
+
+    pub fn as_celsius(&self) -> f32 {
+        self.celsius
+    }
+
+    pub fn as_fahrenheit(&self) -> f32 {
+        self.celsius * 9.0 / 5.0 + 32.0
+    }
+
+    /// The temperature in `unit`, e.g. the unit returned by [TemperatureControl::get_temperature_unit_for_ux](temperature_control::TemperatureControl::get_temperature_unit_for_ux)
+    pub fn as_unit(&self, unit: &TemperatureUnit) -> f32 {
+        match unit {
+            TemperatureUnit::Celsius => self.as_celsius(),
+            TemperatureUnit::Fahrenheit => self.as_fahrenheit(),
+        }
+    }
+}
+
 /// Name synonyms in each supported language.
-#[derive(Debug, PartialEq, Serialize)]
+#[derive(Clone, Debug, PartialEq, Serialize)]
 pub struct Synonym {
     /// Synonyms for the preset, should include both singular and plural forms, if applicable.
     pub synonym: Vec<String>,
@@ -189,9 +454,39 @@ pub struct Synonym {
     pub lang: Language,
 }
 
+impl Synonym {
+    pub fn new(synonym: Vec<String>, lang: Language) -> Self {
+        Self { synonym, lang }
+    }
+}
+
 /// This trait belongs to devices that can detect objects or people and send a notification to the user.
 /// For example, it can be used for doorbells to indicate that a person (named or unnamed) rang the doorbell,
 /// as well as for cameras and sensors that can detect movement of objects or people approaching.
 pub trait ObjectDetection {
     // TODO
 }
+
+#[cfg(test)]
+mod test {
+    use super::{ChallengeType, CombinedDeviceError, DeviceError};
+    use crate::{ErrorCode, SerializableError};
+
+    #[test]
+    fn test_device_error_google_error() {
+        let error = CombinedDeviceError::DeviceError(DeviceError::ValueOutOfRange);
+        assert_eq!(error.to_google_error(), (Some("valueOutOfRange".to_string()), None));
+    }
+
+    #[test]
+    fn test_other_google_error() {
+        let error = CombinedDeviceError::Other(SerializableError(Box::new(ErrorCode::from("connectionRefused"))));
+        assert_eq!(error.to_google_error(), (None, Some("connectionRefused".to_string())));
+    }
+
+    #[test]
+    fn test_challenge_google_error() {
+        let error = CombinedDeviceError::ChallengeNeeded(ChallengeType::PinNeeded);
+        assert_eq!(error.to_google_error(), (Some("challengeNeeded".to_string()), None));
+    }
+}
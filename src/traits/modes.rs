@@ -1,5 +1,5 @@
 use crate::traits::Language;
-use crate::CombinedDeviceError;
+use crate::{CombinedDeviceError, Trait, ValidationError};
 use serde::Serialize;
 use std::collections::HashMap;
 
@@ -17,6 +17,22 @@ pub struct AvailableMode {
     pub ordered: bool,
 }
 
+impl AvailableMode {
+    /// Create a mode, failing if fewer than 2 settings are given.
+    pub fn new(name: String, name_values: Vec<NameValue>, settings: Vec<Setting>, ordered: bool) -> Result<Self, ValidationError> {
+        if settings.len() < 2 {
+            return Err(ValidationError::new(Trait::Modes, format!("mode '{name}' must have at least 2 settings")));
+        }
+
+        Ok(Self {
+            name,
+            name_values,
+            settings,
+            ordered,
+        })
+    }
+}
+
 /// Supported setting.
 #[derive(Debug, PartialEq, Serialize)]
 pub struct Setting {
@@ -6,12 +6,14 @@ use crate::CombinedDeviceError;
 /// <https://developers.google.com/assistant/smarthome/traits/onoff>
 pub trait OnOff {
     /// Indicates if the device can only controlled through commands, and cannot be queried for state information.
+    /// The state of a command-only device is left out of QUERY, [Self::is_on] is not called.
     /// Default: false
     fn is_command_only(&self) -> Result<Option<bool>, CombinedDeviceError> {
         Ok(None)
     }
 
     /// Indicates if the device can only be queried for state information, and cannot be controlled through commands.
+    /// Commands sent to a query-only device are rejected with `notSupported`.
     /// Default: false
     fn is_query_only(&self) -> Result<Option<bool>, CombinedDeviceError> {
         Ok(None)
@@ -24,3 +26,29 @@ pub trait OnOff {
     /// - `on` Whether to turn the device on or off.
     fn set_on(&mut self, on: bool) -> Result<(), CombinedDeviceError>;
 }
+
+/// Async variant of [OnOff], for devices which have to await e.g. a cloud API to change or report their state.
+///
+/// Only available with the `async` feature. Register it on an [AsyncDevice](crate::AsyncDevice).
+#[cfg(feature = "async")]
+#[async_trait::async_trait]
+pub trait AsyncOnOff {
+    /// Indicates if the device can only controlled through commands, and cannot be queried for state information.
+    /// Default: false
+    async fn is_command_only(&self) -> Result<Option<bool>, CombinedDeviceError> {
+        Ok(None)
+    }
+
+    /// Indicates if the device can only be queried for state information, and cannot be controlled through commands.
+    /// Default: false
+    async fn is_query_only(&self) -> Result<Option<bool>, CombinedDeviceError> {
+        Ok(None)
+    }
+
+    /// Whether a device with an on/off switch is on or off.
+    async fn is_on(&self) -> Result<bool, CombinedDeviceError>;
+
+    /// Turn the device on or off.
+    /// - `on` Whether to turn the device on or off.
+    async fn set_on(&mut self, on: bool) -> Result<(), CombinedDeviceError>;
+}
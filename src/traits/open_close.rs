@@ -21,7 +21,7 @@ pub enum OpenCloseError {
 
 /// Direction in which the device is opened.
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
-#[serde(rename = "SCREAMING_SNAKE_CASE")]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum OpenDirection {
     Up,
     Down,
@@ -33,11 +33,12 @@ pub enum OpenDirection {
 
 /// Current state for the given open direction.
 #[derive(Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct OpenState {
     /// Indicates the percentage that a device is opened, where 0 is closed and 100 is fully open.
-    open_percent: f32,
+    pub open_percent: f32,
     /// Direction in which the device is opened.
-    open_direction: OpenDirection,
+    pub open_direction: OpenDirection,
 }
 
 /// This trait belongs to devices that support opening and closing,
@@ -9,6 +9,7 @@ pub struct CurrentRunCycle {
     /// Current cycle being performed.
     pub current_cycle: String,
     /// Optional. Next cycle to perform.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub next_cycle: Option<String>,
     /// Language code for the given cycle names
     pub lang: Language,
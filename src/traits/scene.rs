@@ -8,6 +8,7 @@ use crate::CombinedDeviceError;
 /// and the Assistant will make these scenes available to users through simple activation grammar:
 /// - Start Party Mode
 /// - Activate midnight scene
+///
 /// As virtual objects, scenes can be placed in rooms (if relevant) for disambiguation:
 /// - Start party time in the kitchen.
 /// - Activate nightlight mode in the bedrooms.
@@ -42,8 +42,10 @@ pub struct CurrentSensorState {
     /// Sensor state name. Matches a value from sensorStatesSupported.
     pub name: String,
     /// Current descriptive state value. Matches a value from sensorStatesSupported.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub current_sensor_state: Option<String>,
     /// Current numeric sensor value.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub raw_value: Option<f32>,
 }
 
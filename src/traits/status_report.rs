@@ -14,6 +14,7 @@ pub struct CurrentStatusReport {
     /// Depending on the surface, Google may report only high priority errors or exceptions.
     pub priority: i32,
     /// The current status of the device. See the full list of [errors and exceptions](https://developers.google.com/assistant/smarthome/reference/errors-exceptions?).
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub status_code: Option<String>,
 }
 
@@ -31,6 +31,9 @@ pub enum ThermostatMode {
     Dry,
 }
 
+/// The thermostat state reported in QUERY.
+/// Its fields are flattened into the device state, so a fixed set point is reported as `thermostatTemperatureSetpoint`,
+/// and a range as `thermostatTemperatureSetpointHigh` and `thermostatTemperatureSetpointLow`.
 #[derive(Debug, PartialEq, Serialize)]
 #[serde(untagged)]
 pub enum QueryThermostatMode {
@@ -116,7 +119,8 @@ pub trait TemperatureSetting {
     fn set_temperature_setpoint(&mut self, setpoint: f32) -> Result<(), CombinedDeviceError>;
 
     /// Set a target temperature range for a thermostat device.
-    /// Requires the device to support [ThermostatMode::Heatcool].
+    /// Requires the device to support [ThermostatMode::Heatcool], otherwise the command is rejected before this is called.
+    /// A range with `setpoint_low` above `setpoint_high`, or with setpoints closer together than [Self::get_buffer_range_celsius], is rejected as well.
     /// - `setpoint_high` High target setpoint for the range.
     /// - `setpoint_low` Low target setpoint for the range.
     fn set_temperature_set_range(&mut self, setpoint_high: f32, setpoint_low: f32) -> Result<(), CombinedDeviceError>;
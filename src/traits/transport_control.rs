@@ -11,7 +11,7 @@ pub enum SupportedCommand {
     Previous,
     Resume,
     SeekRelative,
-    SeekAbsolute,
+    SeekToPosition,
     SetRepeat,
     Shuffle,
     Stop,
@@ -27,53 +27,53 @@ pub trait TransportControl {
     /// A list of strings describing supported transport control commands on this device.
     fn get_supported_control_commands(&self) -> Result<Vec<SupportedCommand>, CombinedDeviceError>;
 
-    /// Pause media playback.
-    /// Only called if [Self::get_supported_control_commands] returns [SupportedCommands::Stop]
+    /// Stop media playback.
+    /// Only called if [Self::get_supported_control_commands] returns [SupportedCommand::Stop]
     fn media_stop(&mut self) -> Result<(), CombinedDeviceError>;
 
     /// Skip to next media item.
-    /// Only called if [Self::get_supported_control_commands] returns [SupportedCommands::Next]
+    /// Only called if [Self::get_supported_control_commands] returns [SupportedCommand::Next]
     fn media_next(&mut self) -> Result<(), CombinedDeviceError>;
 
     /// Skip to previous media item.
-    /// Only called if [Self::get_supported_control_commands] returns [SupportedCommands::Previous]
+    /// Only called if [Self::get_supported_control_commands] returns [SupportedCommand::Previous]
     fn media_previous(&mut self) -> Result<(), CombinedDeviceError>;
 
     /// Pause media playback.
-    /// Only called if [Self::get_supported_control_commands] returns [SupportedCommands::Pause]
+    /// Only called if [Self::get_supported_control_commands] returns [SupportedCommand::Pause]
     fn media_pause(&mut self) -> Result<(), CombinedDeviceError>;
 
     /// Resume media playback.
-    /// Only called if [Self::get_supported_control_commands] returns [SupportedCommands::Resume]
+    /// Only called if [Self::get_supported_control_commands] returns [SupportedCommand::Resume]
     fn media_resume(&mut self) -> Result<(), CombinedDeviceError>;
 
     /// Seek to a relative position.
-    /// Only called if [Self::get_supported_control_commands] returns [SupportedCommands::SeekRelative]
+    /// Only called if [Self::get_supported_control_commands] returns [SupportedCommand::SeekRelative]
     /// - `relative_position_ms` Milliseconds of the forward (positive int) or backward (negative int) amount to seek.
     fn media_seek_relative(&mut self, relative_position_ms: i32) -> Result<(), CombinedDeviceError>;
 
     /// Seek to an absolute position
-    /// Only called if [Self::get_supported_control_commands] returns [SupportedCommands::SeekToPosition]
+    /// Only called if [Self::get_supported_control_commands] returns [SupportedCommand::SeekToPosition]
     /// - `abs_position_ms` Millisecond of the absolute position to seek to.
     fn media_seek_to_position(&mut self, abs_position_ms: i32) -> Result<(), CombinedDeviceError>;
 
     /// Set repeat playback mode
-    /// Only called if [Self::get_supported_control_commands] returns [SupportedCommands::SetRepeat]
+    /// Only called if [Self::get_supported_control_commands] returns [SupportedCommand::SetRepeat]
     /// - `is_on` True to turn on repeat mode, false to turn off repeat mode.
     /// - `is_single` True means turning on single-item repeat mode, false means turning on normal repeat mode (for example a playlist).
     fn media_repeat_mode(&mut self, is_on: bool, single_mode: bool) -> Result<(), CombinedDeviceError>;
 
     /// Shuffle the current playlist
-    /// Only called if [Self::get_supported_control_commands] returns [SupportedCommands::Shuffle]
+    /// Only called if [Self::get_supported_control_commands] returns [SupportedCommand::Shuffle]
     fn media_shuffle(&mut self) -> Result<(), CombinedDeviceError>;
 
     /// Turn captions on
-    /// Only called if [Self::get_supported_control_commands] returns [SupportedCommands::CaptionControl]
+    /// Only called if [Self::get_supported_control_commands] returns [SupportedCommand::CaptionControl]
     /// - `cc_lang` Language or locale for closed captioning.
     /// - `user_query_lang`
     fn media_closed_captioning_on(&mut self, cc_lang: String, user_query_lang: String) -> Result<(), CombinedDeviceError>;
 
     /// Turn captions off
-    /// Only called if [Self::get_supported_control_commands] returns [SupportedCommands::CaptionControl]
+    /// Only called if [Self::get_supported_control_commands] returns [SupportedCommand::CaptionControl]
     fn media_closed_captioning_off(&mut self) -> Result<(), CombinedDeviceError>;
 }
@@ -0,0 +1,22 @@
+use crate::Trait;
+use thiserror::Error;
+
+/// A trait registered on a device does not meet the requirements of Google.
+/// Returned by [Device::validate](crate::Device::validate) and [Device::validate_trait_compatibility](crate::Device::validate_trait_compatibility).
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("{device_trait:?}: {message}")]
+pub struct ValidationError {
+    /// The trait which is not implemented correctly
+    pub device_trait: Trait,
+    /// What is wrong with the implementation
+    pub message: String,
+}
+
+impl ValidationError {
+    pub(crate) fn new<S: ToString>(device_trait: Trait, message: S) -> Self {
+        Self {
+            device_trait,
+            message: message.to_string(),
+        }
+    }
+}
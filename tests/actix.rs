@@ -0,0 +1,77 @@
+#![cfg(feature = "actix")]
+
+mod common;
+
+use actix_web::{test, web, App};
+use homelander::integrations::actix::fulfillment_handler;
+use homelander::traits::on_off::OnOff;
+use homelander::traits::CombinedDeviceError;
+use homelander::{Device, DeviceType, Homelander};
+use serde_json::{json, Value};
+use std::sync::Mutex;
+
+#[derive(Debug)]
+struct Outlet {
+    on: bool,
+}
+
+google_home_device!(Outlet, "Outlet");
+
+impl OnOff for Outlet {
+    fn is_on(&self) -> Result<bool, CombinedDeviceError> {
+        Ok(self.on)
+    }
+
+    fn set_on(&mut self, on: bool) -> Result<(), CombinedDeviceError> {
+        self.on = on;
+        Ok(())
+    }
+}
+
+fn setup_homelander() -> web::Data<Mutex<Homelander>> {
+    let mut device = Device::new(Outlet { on: false }, DeviceType::Outlet, "00".to_string());
+    device.set_on_off();
+
+    let mut homelander = Homelander::new("01".to_string());
+    homelander.add_device(device);
+    web::Data::new(Mutex::new(homelander))
+}
+
+#[actix_web::test]
+async fn sync_actix() {
+    let app = test::init_service(
+        App::new()
+            .app_data(setup_homelander())
+            .route("/fulfillment", web::post().to(fulfillment_handler)),
+    )
+    .await;
+
+    let request = test::TestRequest::post()
+        .uri("/fulfillment")
+        .insert_header(("Content-Type", "application/json"))
+        .set_payload(r#"{"requestId": "02", "inputs": [{"intent": "action.devices.SYNC"}]}"#)
+        .to_request();
+    let body: Value = test::call_and_read_body_json(&app, request).await;
+
+    assert_eq!(body["requestId"], json!("02"));
+    assert_eq!(body["payload"]["agentUserId"], json!("01"));
+    assert_eq!(body["payload"]["devices"][0]["traits"], json!(["action.devices.traits.OnOff"]));
+}
+
+#[actix_web::test]
+async fn malformed_actix() {
+    let app = test::init_service(
+        App::new()
+            .app_data(setup_homelander())
+            .route("/fulfillment", web::post().to(fulfillment_handler)),
+    )
+    .await;
+
+    let request = test::TestRequest::post()
+        .uri("/fulfillment")
+        .insert_header(("Content-Type", "application/json"))
+        .set_payload(r#"{"requestId": "02"}"#)
+        .to_request();
+    let response = test::call_service(&app, request).await;
+    assert!(response.status().is_client_error());
+}
@@ -0,0 +1,188 @@
+mod common;
+
+use homelander::traits::arm_disarm::{ArmDisarm, ArmDisarmError, ArmLevel, LevelValue};
+use homelander::traits::{Challenge, DeviceInfo, DeviceName, GoogleHomeDevice, Language};
+use homelander::{Device, DeviceType, Homelander};
+use serde_json::{json, Value};
+
+#[derive(Debug)]
+struct SecuritySystem {
+    armed: bool,
+    level: String,
+    challenge: Option<Challenge>,
+}
+
+impl GoogleHomeDevice for SecuritySystem {
+    fn get_device_info(&self) -> DeviceInfo {
+        DeviceInfo {
+            manufacturer: "Array21 Development".to_string(),
+            model: "SecuritySystem".to_string(),
+            hw: "0.1.0".to_string(),
+            sw: "0.1.0".to_string(),
+        }
+    }
+
+    fn will_report_state(&self) -> bool {
+        false
+    }
+
+    fn get_device_name(&self) -> DeviceName {
+        DeviceName {
+            name: "SecuritySystem".to_string(),
+            nicknames: Vec::new(),
+            default_names: Vec::new(),
+        }
+    }
+
+    fn is_online(&self) -> bool {
+        true
+    }
+
+    fn set_challenge(&mut self, challenge: Option<Challenge>) {
+        self.challenge = challenge;
+    }
+
+    fn disconnect(&mut self) {}
+}
+
+impl ArmDisarm for SecuritySystem {
+    fn get_available_arm_levels(&self) -> Result<Option<Vec<ArmLevel>>, ArmDisarmError> {
+        Ok(Some(vec![
+            ArmLevel::new("L1".to_string(), vec![LevelValue::new(vec!["home and guarding".to_string()], Language::English)]),
+            ArmLevel::new("L2".to_string(), vec![LevelValue::new(vec!["away".to_string()], Language::English)]),
+        ]))
+    }
+
+    fn is_ordered(&self) -> Result<bool, ArmDisarmError> {
+        Ok(true)
+    }
+
+    fn is_armed(&self) -> Result<bool, ArmDisarmError> {
+        Ok(self.armed)
+    }
+
+    fn current_arm_level(&self) -> Result<String, ArmDisarmError> {
+        Ok(self.level.clone())
+    }
+
+    fn exit_allowance(&self) -> Result<i32, ArmDisarmError> {
+        Ok(60)
+    }
+
+    fn arm(&mut self, arm: bool) -> Result<(), ArmDisarmError> {
+        // Disarming requires the PIN of the system
+        if !arm {
+            Challenge::check_pin(self.challenge.as_ref(), "1234")
+                .into_result()
+                .map_err(ArmDisarmError::Other)?;
+        }
+
+        self.armed = arm;
+        Ok(())
+    }
+
+    fn cancel_arm(&mut self) -> Result<(), ArmDisarmError> {
+        self.armed = false;
+        Ok(())
+    }
+
+    fn arm_with_level(&mut self, arm: bool, level: String) -> Result<(), ArmDisarmError> {
+        self.armed = arm;
+        self.level = level;
+        Ok(())
+    }
+}
+
+fn setup_homelander() -> Homelander {
+    let system = SecuritySystem {
+        armed: true,
+        level: "L2".to_string(),
+        challenge: None,
+    };
+    let mut device = Device::new(system, DeviceType::SecuritySystem, "00".to_string());
+    device.set_arm_disarm();
+
+    let mut homelander = Homelander::new("01".to_string());
+    homelander.add_device(device);
+    homelander
+}
+
+#[test]
+fn sync_arm_levels() {
+    let mut homelander = setup_homelander();
+    let payload = common::sync(&mut homelander);
+    let attributes = serde_json::to_value(&payload.devices[0].attributes).unwrap();
+
+    assert_eq!(
+        attributes["availableArmLevels"],
+        json!({
+            "levels": [
+                { "level_name": "L1", "level_values": [{ "level_synonym": ["home and guarding"], "lang": "en" }] },
+                { "level_name": "L2", "level_values": [{ "level_synonym": ["away"], "lang": "en" }] }
+            ],
+            "ordered": true
+        })
+    );
+}
+
+#[test]
+fn query_arm_disarm() {
+    let mut homelander = setup_homelander();
+    let payload = common::query(&mut homelander, &["00"]);
+    let state = serde_json::to_value(&payload.devices["00"]).unwrap();
+
+    assert_eq!(state["isArmed"], json!(true));
+    assert_eq!(state["currentArmLevel"], json!("L2"));
+    assert_eq!(state["exitAllowance"], json!(60));
+}
+
+/// Disarm the system and return the single command in the response
+fn disarm(homelander: &mut Homelander, challenge: Option<Value>) -> Value {
+    let mut execution = json!({
+        "command": "action.devices.commands.ArmDisarm",
+        "params": { "arm": false }
+    });
+    if let Some(challenge) = challenge {
+        execution["challenge"] = challenge;
+    }
+
+    let body = json!({
+        "requestId": "02",
+        "inputs": [{
+            "intent": "action.devices.EXECUTE",
+            "payload": {
+                "commands": [{
+                    "devices": [{ "id": "00" }],
+                    "execution": [execution]
+                }]
+            }
+        }]
+    });
+
+    let response: Value = serde_json::from_str(&homelander.handle_request_json(&body.to_string()).unwrap()).unwrap();
+    response["payload"]["commands"][0].clone()
+}
+
+fn is_armed(homelander: &mut Homelander) -> bool {
+    let payload = common::query(homelander, &["00"]);
+    serde_json::to_value(&payload.devices["00"]).unwrap()["isArmed"].as_bool().unwrap()
+}
+
+#[test]
+fn disarm_with_wrong_pin() {
+    let mut homelander = setup_homelander();
+
+    let command = disarm(&mut homelander, None);
+    assert_eq!(command["errorCode"], json!("challengeNeeded"));
+    assert_eq!(command["challengeNeeded"], json!({ "type": "pinNeeded" }));
+
+    let command = disarm(&mut homelander, Some(json!({ "pin": "0000" })));
+    assert_eq!(command["status"], json!("ERROR"));
+    assert_eq!(command["errorCode"], json!("challengeNeeded"));
+    assert_eq!(command["challengeNeeded"], json!({ "type": "challengeFailedPinNeeded" }));
+    assert!(is_armed(&mut homelander));
+
+    let command = disarm(&mut homelander, Some(json!({ "pin": "1234" })));
+    assert_eq!(command["status"], json!("SUCCESS"));
+    assert!(!is_armed(&mut homelander));
+}
@@ -0,0 +1,147 @@
+#![cfg(feature = "async")]
+
+mod common;
+
+use homelander::fulfillment::request::execute::CommandType;
+use homelander::fulfillment::response::execute::CommandStatus;
+use homelander::fulfillment::response::ResponsePayload;
+use homelander::traits::on_off::{AsyncOnOff, OnOff};
+use homelander::traits::CombinedDeviceError;
+use homelander::{async_trait, AsyncDevice, Device, DeviceType, Homelander};
+use serde_json::json;
+
+#[derive(Debug)]
+struct CloudSwitch {
+    on: bool,
+}
+
+google_home_device!(CloudSwitch, "CloudSwitch");
+
+#[async_trait]
+impl AsyncOnOff for CloudSwitch {
+    async fn is_on(&self) -> Result<bool, CombinedDeviceError> {
+        Ok(self.on)
+    }
+
+    async fn set_on(&mut self, on: bool) -> Result<(), CombinedDeviceError> {
+        self.on = on;
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+struct LocalSwitch {
+    on: bool,
+}
+
+google_home_device!(LocalSwitch, "LocalSwitch");
+
+impl OnOff for LocalSwitch {
+    fn is_on(&self) -> Result<bool, CombinedDeviceError> {
+        Ok(self.on)
+    }
+
+    fn set_on(&mut self, on: bool) -> Result<(), CombinedDeviceError> {
+        self.on = on;
+        Ok(())
+    }
+}
+
+fn setup_homelander() -> Homelander {
+    let mut homelander = Homelander::new("01".to_string());
+
+    let mut device = AsyncDevice::new(CloudSwitch { on: false }, DeviceType::Switch, "00".to_string());
+    device.set_on_off();
+    homelander.add_async_device(device);
+
+    let mut device = Device::new(LocalSwitch { on: false }, DeviceType::Switch, "10".to_string());
+    device.set_on_off();
+    homelander.add_device(device);
+
+    homelander
+}
+
+#[test]
+fn handle_request_async_is_send() {
+    fn assert_send<T: Send>(_: T) {}
+
+    let mut homelander = setup_homelander();
+    assert_send(homelander.handle_request_async(common::sync_request()));
+}
+
+#[tokio::test]
+async fn sync_async_device() {
+    let mut homelander = setup_homelander();
+    let payload = match homelander.handle_request_async(common::sync_request()).await.payload {
+        ResponsePayload::Sync(payload) => payload,
+        _ => panic!("Expected a SYNC payload"),
+    };
+
+    let ids = payload.devices.iter().map(|device| device.id.as_str()).collect::<Vec<_>>();
+    assert_eq!(ids, vec!["10", "00"]);
+
+    let device = serde_json::to_value(&payload.devices[1]).unwrap();
+    assert_eq!(device["traits"], json!(["action.devices.traits.OnOff"]));
+    assert_eq!(device["name"]["name"], json!("CloudSwitch"));
+}
+
+#[tokio::test]
+async fn execute_and_query_async_device() {
+    let mut homelander = setup_homelander();
+
+    let payload = match homelander
+        .handle_request_async(common::execute_request(&["00", "10"], CommandType::OnOff { on: true }))
+        .await
+        .payload
+    {
+        ResponsePayload::Execute(payload) => payload,
+        _ => panic!("Expected an EXECUTE payload"),
+    };
+    // Both devices succeeded in the same way, so they are reported in a single command
+    assert_eq!(payload.commands.len(), 1);
+    assert_eq!(payload.commands[0].status, CommandStatus::Success);
+    assert_eq!(payload.commands[0].ids, vec!["00".to_string(), "10".to_string()]);
+
+    let payload = match homelander.handle_request_async(common::query_request(&["00", "10"])).await.payload {
+        ResponsePayload::Query(payload) => payload,
+        _ => panic!("Expected a QUERY payload"),
+    };
+    for id in ["00", "10"] {
+        let state = serde_json::to_value(&payload.devices[id]).unwrap();
+        assert_eq!(state["on"], json!(true));
+    }
+}
+
+#[tokio::test]
+async fn execute_without_async_variant_not_supported() {
+    let mut homelander = setup_homelander();
+
+    let payload = match homelander
+        .handle_request_async(common::execute_request(&["00"], CommandType::Reboot))
+        .await
+        .payload
+    {
+        ResponsePayload::Execute(payload) => payload,
+        _ => panic!("Expected an EXECUTE payload"),
+    };
+    assert_eq!(payload.commands[0].status, CommandStatus::Error);
+    assert_eq!(serde_json::to_value(&payload.commands[0].error_code).unwrap(), json!("notSupported"));
+}
+
+#[tokio::test]
+async fn execute_unknown_device() {
+    let mut homelander = setup_homelander();
+
+    let payload = match homelander
+        .handle_request_async(common::execute_request(&["00", "99"], CommandType::OnOff { on: true }))
+        .await
+        .payload
+    {
+        ResponsePayload::Execute(payload) => payload,
+        _ => panic!("Expected an EXECUTE payload"),
+    };
+    assert_eq!(payload.commands.len(), 2);
+    assert_eq!(payload.commands[1].ids, vec!["99".to_string()]);
+    assert_eq!(payload.commands[1].status, CommandStatus::Error);
+    assert_eq!(serde_json::to_value(&payload.commands[1].error_code).unwrap(), json!("deviceNotFound"));
+}
@@ -0,0 +1,74 @@
+#![cfg(feature = "axum")]
+
+mod common;
+
+use axum::body::Body;
+use axum::http::{header, Request, StatusCode};
+use axum::routing::post;
+use axum::Router;
+use homelander::integrations::axum::fulfillment_handler;
+use homelander::traits::on_off::OnOff;
+use homelander::traits::CombinedDeviceError;
+use homelander::{Device, DeviceType, Homelander};
+use http_body_util::BodyExt;
+use serde_json::{json, Value};
+use std::sync::{Arc, Mutex};
+use tower::ServiceExt;
+
+#[derive(Debug)]
+struct Outlet {
+    on: bool,
+}
+
+google_home_device!(Outlet, "Outlet");
+
+impl OnOff for Outlet {
+    fn is_on(&self) -> Result<bool, CombinedDeviceError> {
+        Ok(self.on)
+    }
+
+    fn set_on(&mut self, on: bool) -> Result<(), CombinedDeviceError> {
+        self.on = on;
+        Ok(())
+    }
+}
+
+fn setup_router() -> Router {
+    let mut device = Device::new(Outlet { on: false }, DeviceType::Outlet, "00".to_string());
+    device.set_on_off();
+
+    let mut homelander = Homelander::new("01".to_string());
+    homelander.add_device(device);
+
+    Router::new()
+        .route("/fulfillment", post(fulfillment_handler))
+        .with_state(Arc::new(Mutex::new(homelander)))
+}
+
+fn post_fulfillment(body: &'static str) -> Request<Body> {
+    Request::post("/fulfillment")
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from(body))
+        .unwrap()
+}
+
+#[tokio::test]
+async fn sync_axum() {
+    let response = setup_router()
+        .oneshot(post_fulfillment(r#"{"requestId": "02", "inputs": [{"intent": "action.devices.SYNC"}]}"#))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let body: Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(body["requestId"], json!("02"));
+    assert_eq!(body["payload"]["agentUserId"], json!("01"));
+    assert_eq!(body["payload"]["devices"][0]["traits"], json!(["action.devices.traits.OnOff"]));
+}
+
+#[tokio::test]
+async fn malformed_axum() {
+    let response = setup_router().oneshot(post_fulfillment(r#"{"requestId": "02"}"#)).await.unwrap();
+    assert!(response.status().is_client_error());
+}
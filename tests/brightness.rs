@@ -0,0 +1,79 @@
+mod common;
+
+use homelander::fulfillment::request::execute::CommandType;
+use homelander::fulfillment::response::execute::CommandStatus;
+use homelander::traits::brightness::Brightness;
+use homelander::traits::CombinedDeviceError;
+use homelander::{Device, DeviceType, Homelander};
+use serde_json::json;
+
+#[derive(Debug)]
+struct Lamp {
+    brightness: i32,
+}
+
+google_home_device!(Lamp, "Lamp");
+
+impl Brightness for Lamp {
+    fn is_command_only_brightness(&self) -> Result<bool, CombinedDeviceError> {
+        Ok(false)
+    }
+
+    fn get_brightness(&self) -> Result<i32, CombinedDeviceError> {
+        Ok(self.brightness)
+    }
+
+    fn set_brightness_absolute(&mut self, brightness: i32) -> Result<(), CombinedDeviceError> {
+        self.brightness = brightness;
+        Ok(())
+    }
+
+    fn set_brightness_relative_percent(&mut self, brightness: i32) -> Result<(), CombinedDeviceError> {
+        self.brightness += brightness;
+        Ok(())
+    }
+
+    fn set_brightness_relative_weight(&mut self, weight: i32) -> Result<(), CombinedDeviceError> {
+        self.brightness += weight * 10;
+        Ok(())
+    }
+}
+
+fn setup_homelander() -> Homelander {
+    let mut device = Device::new(Lamp { brightness: 50 }, DeviceType::Light, "00".to_string());
+    device.set_brightness();
+
+    let mut homelander = Homelander::new("01".to_string());
+    homelander.add_device(device);
+    homelander
+}
+
+fn assert_brightness(homelander: &mut Homelander, brightness: i32) {
+    let payload = common::query(homelander, &["00"]);
+    let state = serde_json::to_value(&payload.devices["00"]).unwrap();
+    assert_eq!(state["brightness"], json!(brightness));
+}
+
+#[test]
+fn brightness_absolute_in_range() {
+    let mut homelander = setup_homelander();
+
+    for brightness in [0, 100] {
+        let payload = common::execute(&mut homelander, &["00"], CommandType::BrightnessAbsolute { brightness });
+        assert_eq!(payload.commands[0].status, CommandStatus::Success);
+        assert_brightness(&mut homelander, brightness);
+    }
+}
+
+#[test]
+fn brightness_absolute_out_of_range() {
+    let mut homelander = setup_homelander();
+
+    for brightness in [-1, 101, 250] {
+        let payload = common::execute(&mut homelander, &["00"], CommandType::BrightnessAbsolute { brightness });
+        let command = serde_json::to_value(&payload.commands[0]).unwrap();
+        assert_eq!(payload.commands[0].status, CommandStatus::Error);
+        assert_eq!(command["errorCode"], json!("valueOutOfRange"));
+        assert_brightness(&mut homelander, 50);
+    }
+}
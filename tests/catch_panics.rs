@@ -0,0 +1,191 @@
+#![cfg(feature = "catch-panics")]
+
+mod common;
+
+use homelander::fulfillment::request::execute::CommandType;
+use homelander::fulfillment::response::execute::CommandStatus;
+use homelander::traits::on_off::OnOff;
+use homelander::traits::{CombinedDeviceError, DeviceInfo, DeviceName, GoogleHomeDevice};
+use homelander::{Device, DeviceType, Homelander};
+use serde_json::json;
+
+#[derive(Debug)]
+struct Switch {
+    on: bool,
+}
+
+google_home_device!(Switch, "Switch");
+
+impl OnOff for Switch {
+    fn is_on(&self) -> Result<bool, CombinedDeviceError> {
+        Ok(self.on)
+    }
+
+    fn set_on(&mut self, on: bool) -> Result<(), CombinedDeviceError> {
+        self.on = on;
+        Ok(())
+    }
+}
+
+/// A switch whose connection was never set up
+#[derive(Debug)]
+struct BrokenSwitch {
+    connection: Option<bool>,
+}
+
+google_home_device!(BrokenSwitch, "BrokenSwitch");
+
+impl OnOff for BrokenSwitch {
+    fn is_on(&self) -> Result<bool, CombinedDeviceError> {
+        Ok(self.connection.unwrap())
+    }
+
+    fn set_on(&mut self, _on: bool) -> Result<(), CombinedDeviceError> {
+        self.connection.unwrap();
+        Ok(())
+    }
+}
+
+/// A switch which panics halfway through turning on, after it already updated part of its state
+#[derive(Debug)]
+struct FlakySwitch {
+    on: bool,
+    relay: bool,
+}
+
+google_home_device!(FlakySwitch, "FlakySwitch");
+
+impl OnOff for FlakySwitch {
+    fn is_on(&self) -> Result<bool, CombinedDeviceError> {
+        Ok(self.on)
+    }
+
+    fn set_on(&mut self, on: bool) -> Result<(), CombinedDeviceError> {
+        self.on = on;
+        if on {
+            panic!("relay did not respond");
+        }
+
+        self.relay = on;
+        Ok(())
+    }
+}
+
+/// A switch which panics when it is told about the request it handles
+#[derive(Debug)]
+struct NosySwitch;
+
+impl GoogleHomeDevice for NosySwitch {
+    fn get_device_info(&self) -> DeviceInfo {
+        DeviceInfo {
+            manufacturer: "Array21 Development".to_string(),
+            model: "NosySwitch".to_string(),
+            hw: "0.1.0".to_string(),
+            sw: "0.1.0".to_string(),
+        }
+    }
+
+    fn will_report_state(&self) -> bool {
+        false
+    }
+
+    fn get_device_name(&self) -> DeviceName {
+        DeviceName {
+            name: "NosySwitch".to_string(),
+            nicknames: Vec::new(),
+            default_names: Vec::new(),
+        }
+    }
+
+    fn is_online(&self) -> bool {
+        true
+    }
+
+    fn disconnect(&mut self) {}
+
+    fn set_request_id(&mut self, _request_id: Option<&str>) {
+        panic!("no request id expected");
+    }
+}
+
+impl OnOff for NosySwitch {
+    fn is_on(&self) -> Result<bool, CombinedDeviceError> {
+        Ok(false)
+    }
+
+    fn set_on(&mut self, _on: bool) -> Result<(), CombinedDeviceError> {
+        Ok(())
+    }
+}
+
+fn setup_homelander() -> Homelander {
+    let mut switch = Device::new(Switch { on: false }, DeviceType::Switch, "00".to_string());
+    switch.set_on_off();
+
+    let mut broken = Device::new(BrokenSwitch { connection: None }, DeviceType::Switch, "10".to_string());
+    broken.set_on_off();
+
+    let mut homelander = Homelander::new("01".to_string());
+    homelander.add_device(switch);
+    homelander.add_device(broken);
+    homelander
+}
+
+#[test]
+fn query_panicking_device() {
+    let mut homelander = setup_homelander();
+    let payload = common::query(&mut homelander, &["00", "10"]);
+
+    assert_eq!(serde_json::to_value(&payload.devices["00"]).unwrap()["on"], json!(false));
+    assert_eq!(serde_json::to_value(&payload.devices["10"]).unwrap(), json!({ "online": true, "status": "ERROR", "errorCode": "hardError" }));
+}
+
+#[test]
+fn execute_panicking_device() {
+    let mut homelander = setup_homelander();
+    let payload = common::execute(&mut homelander, &["00", "10"], CommandType::OnOff { on: true });
+
+    let success = payload.commands.iter().find(|c| c.ids.contains(&"00".to_string())).unwrap();
+    assert_eq!(success.status, CommandStatus::Success);
+
+    let error = payload.commands.iter().find(|c| c.ids.contains(&"10".to_string())).unwrap();
+    assert_eq!(error.status, CommandStatus::Error);
+    assert_eq!(serde_json::to_value(&error.error_code).unwrap(), json!("hardError"));
+
+    // The panic did not leave the device unusable
+    let payload = common::query(&mut homelander, &["00", "10"]);
+    assert_eq!(serde_json::to_value(&payload.devices["00"]).unwrap()["on"], json!(true));
+}
+
+#[test]
+fn panicked_device_stays_broken() {
+    let mut device = Device::new(FlakySwitch { on: false, relay: false }, DeviceType::Switch, "00".to_string());
+    device.set_on_off();
+
+    let mut homelander = Homelander::new("01".to_string());
+    homelander.add_device(device);
+
+    let payload = common::execute(&mut homelander, &["00"], CommandType::OnOff { on: true });
+    assert_eq!(serde_json::to_value(&payload.commands[0].error_code).unwrap(), json!("hardError"));
+
+    // The switch is partly turned on, so it is not trusted to report or change its state anymore
+    let payload = common::query(&mut homelander, &["00"]);
+    assert_eq!(serde_json::to_value(&payload.devices["00"]).unwrap(), json!({ "online": true, "status": "ERROR", "errorCode": "hardError" }));
+
+    let payload = common::execute(&mut homelander, &["00"], CommandType::OnOff { on: false });
+    assert_eq!(serde_json::to_value(&payload.commands[0].error_code).unwrap(), json!("hardError"));
+    assert_eq!(homelander.with_device_mut("00", |switch: &mut FlakySwitch| (switch.on, switch.relay)), Some((true, false)));
+}
+
+#[test]
+fn panicking_request_id_callback() {
+    let mut device = Device::new(NosySwitch, DeviceType::Switch, "00".to_string());
+    device.set_on_off();
+
+    let mut homelander = Homelander::new("01".to_string());
+    homelander.add_device(device);
+
+    let payload = common::execute(&mut homelander, &["00"], CommandType::OnOff { on: true });
+    assert_eq!(payload.commands[0].status, CommandStatus::Error);
+    assert_eq!(serde_json::to_value(&payload.commands[0].error_code).unwrap(), json!("hardError"));
+}
@@ -0,0 +1,207 @@
+mod common;
+
+use homelander::fulfillment::request::execute::CommandType;
+use homelander::fulfillment::response::execute::CommandStatus;
+use homelander::traits::color_setting::{Color, ColorCommand, ColorModel, ColorModelSupport, ColorSetting, ColorTemperatureRange, SpectrumHsv};
+use homelander::traits::CombinedDeviceError;
+use homelander::{Device, DeviceType, Homelander};
+use serde_json::json;
+
+#[derive(Debug)]
+struct Lamp {
+    support: fn() -> ColorModelSupport,
+    color: Option<ColorCommand>,
+}
+
+google_home_device!(Lamp, "Lamp");
+
+impl ColorSetting for Lamp {
+    fn is_command_only_color_setting(&self) -> Result<bool, CombinedDeviceError> {
+        Ok(false)
+    }
+
+    fn get_color_model_support(&self) -> Result<ColorModelSupport, CombinedDeviceError> {
+        Ok((self.support)())
+    }
+
+    fn get_color(&self) -> Result<Color, CombinedDeviceError> {
+        Ok(Color {
+            temperature_k: Some(2700),
+            spectrum_rgb: None,
+            spectrum_hsv: None,
+        })
+    }
+
+    fn set_color(&mut self, command: ColorCommand) -> Result<(), CombinedDeviceError> {
+        self.color = Some(command);
+        Ok(())
+    }
+
+    fn set_color_by_name(&mut self, name: String) -> Result<(), CombinedDeviceError> {
+        self.color = Some(ColorCommand::Name(name));
+        Ok(())
+    }
+}
+
+fn temperature_only() -> ColorModelSupport {
+    ColorModelSupport {
+        color_model: None,
+        color_temperature_range: Some(ColorTemperatureRange {
+            temperature_min_k: 2000,
+            temperature_max_k: 6500,
+        }),
+    }
+}
+
+fn rgb_only() -> ColorModelSupport {
+    ColorModelSupport {
+        color_model: Some(ColorModel::Rgb),
+        color_temperature_range: None,
+    }
+}
+
+fn full_color() -> ColorModelSupport {
+    ColorModelSupport::new(Some(ColorModel::Hsv), Some(ColorTemperatureRange::new(2700, 5500)))
+}
+
+fn setup_homelander(support: fn() -> ColorModelSupport) -> Homelander {
+    let mut device = Device::new(Lamp { support, color: None }, DeviceType::Light, "00".to_string());
+    device.set_color_setting();
+
+    let mut homelander = Homelander::new("01".to_string());
+    homelander.add_device(device);
+    homelander
+}
+
+fn set_color(homelander: &mut Homelander, color: ColorCommand) -> serde_json::Value {
+    let payload = common::execute(homelander, &["00"], CommandType::ColorAbsolute { color });
+    serde_json::to_value(&payload.commands[0]).unwrap()
+}
+
+fn assert_color(homelander: &mut Homelander, color: Option<ColorCommand>) {
+    assert_eq!(homelander.with_device_mut("00", |lamp: &mut Lamp| lamp.color.clone()), Some(color));
+}
+
+#[test]
+fn color_absolute_supported() {
+    let mut homelander = setup_homelander(temperature_only);
+    let command = set_color(&mut homelander, ColorCommand::Temperature(2700));
+    assert_eq!(command["status"], json!("SUCCESS"));
+    assert_color(&mut homelander, Some(ColorCommand::Temperature(2700)));
+
+    let mut homelander = setup_homelander(rgb_only);
+    let command = set_color(&mut homelander, ColorCommand::SpectrumRgb(0xFF0000));
+    assert_eq!(command["status"], json!("SUCCESS"));
+    assert_color(&mut homelander, Some(ColorCommand::SpectrumRgb(0xFF0000)));
+}
+
+#[test]
+fn color_absolute_wrong_model() {
+    let mut homelander = setup_homelander(temperature_only);
+    let command = set_color(&mut homelander, ColorCommand::SpectrumRgb(0xFF0000));
+    assert_eq!(command["status"], json!(CommandStatus::Error));
+    assert_eq!(command["errorCode"], json!("valueOutOfRange"));
+    assert_color(&mut homelander, None);
+
+    let mut homelander = setup_homelander(rgb_only);
+    let command = set_color(&mut homelander, ColorCommand::Temperature(2700));
+    assert_eq!(command["errorCode"], json!("valueOutOfRange"));
+    assert_color(&mut homelander, None);
+}
+
+#[test]
+fn color_absolute_temperature_out_of_range() {
+    let mut homelander = setup_homelander(temperature_only);
+    let command = set_color(&mut homelander, ColorCommand::Temperature(1500));
+    assert_eq!(command["status"], json!(CommandStatus::Error));
+    assert_eq!(command["errorCode"], json!("valueOutOfRange"));
+    assert_color(&mut homelander, None);
+}
+
+#[test]
+fn sync_color_setting() {
+    let mut homelander = setup_homelander(full_color);
+    let payload = common::sync(&mut homelander);
+    let attributes = serde_json::to_value(&payload.devices[0].attributes).unwrap();
+
+    assert_eq!(attributes["colorModel"], json!("hsv"));
+    assert_eq!(attributes["colorTemperatureRange"], json!({ "temperatureMinK": 2700, "temperatureMaxK": 5500 }));
+}
+
+#[test]
+fn query_color_temperature_only() {
+    let mut homelander = setup_homelander(temperature_only);
+    let payload = common::query(&mut homelander, &["00"]);
+    let state = serde_json::to_value(&payload.devices["00"]).unwrap();
+    assert_eq!(state["color"], json!({ "temperatureK": 2700 }));
+}
+
+#[test]
+fn deserialize_named_color() {
+    let execution: CommandType = serde_json::from_value(json!({
+        "command": "action.devices.commands.ColorAbsolute",
+        "params": { "color": { "name": "magenta", "spectrumRGB": 16711935 } },
+    }))
+    .unwrap();
+    assert_eq!(
+        execution,
+        CommandType::ColorAbsolute {
+            color: ColorCommand::SpectrumRgb(16711935)
+        }
+    );
+
+    let color: ColorCommand = serde_json::from_value(json!({ "name": "magenta" })).unwrap();
+    assert_eq!(color, ColorCommand::Name("magenta".to_string()));
+
+    assert!(serde_json::from_value::<ColorCommand>(json!({})).is_err());
+}
+
+#[test]
+fn color_absolute_by_name() {
+    let mut homelander = setup_homelander(rgb_only);
+    let command = set_color(&mut homelander, ColorCommand::Name("magenta".to_string()));
+    assert_eq!(command["status"], json!("SUCCESS"));
+    assert_color(&mut homelander, Some(ColorCommand::Name("magenta".to_string())));
+}
+
+#[test]
+fn color_state_keys() {
+    let color = Color {
+        temperature_k: Some(2700),
+        spectrum_rgb: Some(16711935),
+        spectrum_hsv: Some(SpectrumHsv {
+            hue: 300,
+            saturation: 1,
+            value: 1,
+        }),
+    };
+
+    assert_eq!(
+        serde_json::to_value(&color).unwrap(),
+        json!({
+            "temperatureK": 2700,
+            "spectrumRgb": 16711935,
+            "spectrumHsv": { "hue": 300, "saturation": 1, "value": 1 },
+        })
+    );
+}
+
+#[test]
+fn color_command_keys() {
+    let color: ColorCommand = serde_json::from_value(json!({ "temperature": 2700 })).unwrap();
+    assert_eq!(color, ColorCommand::Temperature(2700));
+
+    let color: ColorCommand = serde_json::from_value(json!({ "spectrumHSV": { "hue": 300, "saturation": 1, "value": 1 } })).unwrap();
+    assert_eq!(
+        color,
+        ColorCommand::SpectrumHsv(SpectrumHsv {
+            hue: 300,
+            saturation: 1,
+            value: 1
+        })
+    );
+
+    // The keys of the state are not accepted in a command
+    assert!(serde_json::from_value::<ColorCommand>(json!({ "temperatureK": 2700 })).is_err());
+    assert!(serde_json::from_value::<ColorCommand>(json!({ "spectrumRgb": 16711935 })).is_err());
+}
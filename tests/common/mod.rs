@@ -0,0 +1,105 @@
+#![allow(dead_code)]
+
+use homelander::fulfillment::request::execute::CommandType;
+use homelander::fulfillment::request::Input;
+use homelander::fulfillment::response::ResponsePayload;
+use homelander::fulfillment::{request, response};
+use homelander::{Homelander, Request};
+
+/// Implement [homelander::traits::GoogleHomeDevice] for a test device with static metadata.
+/// The device is always online and does not report state.
+#[macro_export]
+macro_rules! google_home_device {
+    ($ty:ty, $name:expr) => {
+        impl homelander::traits::GoogleHomeDevice for $ty {
+            fn get_device_info(&self) -> homelander::traits::DeviceInfo {
+                homelander::traits::DeviceInfo {
+                    manufacturer: "Array21 Development".to_string(),
+                    model: $name.to_string(),
+                    hw: "0.1.0".to_string(),
+                    sw: "0.1.0".to_string(),
+                }
+            }
+
+            fn will_report_state(&self) -> bool {
+                false
+            }
+
+            fn get_device_name(&self) -> homelander::traits::DeviceName {
+                homelander::traits::DeviceName {
+                    name: $name.to_string(),
+                    nicknames: Vec::new(),
+                    default_names: Vec::new(),
+                }
+            }
+
+            fn is_online(&self) -> bool {
+                true
+            }
+
+            fn disconnect(&mut self) {}
+        }
+    };
+}
+
+/// Create a SYNC request
+pub fn sync_request() -> Request {
+    Request {
+        request_id: "sync".to_string(),
+        inputs: vec![Input::Sync],
+    }
+}
+
+/// Create a QUERY request for the devices with the provided IDs
+pub fn query_request(ids: &[&str]) -> Request {
+    Request {
+        request_id: "query".to_string(),
+        inputs: vec![Input::Query(request::query::Payload {
+            devices: ids.iter().map(|id| request::query::Device { id: id.to_string() }).collect(),
+        })],
+    }
+}
+
+/// Create an EXECUTE request with a single command for the devices with the provided IDs
+pub fn execute_request(ids: &[&str], command: CommandType) -> Request {
+    Request {
+        request_id: "execute".to_string(),
+        inputs: vec![Input::Execute(request::execute::Execute {
+            commands: vec![request::execute::Command {
+                devices: ids
+                    .iter()
+                    .map(|id| request::execute::Device {
+                        id: id.to_string(),
+                        custom_data: None,
+                    })
+                    .collect(),
+                execution: vec![request::execute::Execution { command, challenge: None }],
+            }],
+            structure_data: None,
+        })],
+    }
+}
+
+/// Run a SYNC intent
+pub fn sync(homelander: &mut Homelander) -> response::sync::Payload {
+    match homelander.handle_request(sync_request()).payload {
+        ResponsePayload::Sync(payload) => payload,
+        _ => panic!("Expected a SYNC payload"),
+    }
+}
+
+/// Run a QUERY intent for the devices with the provided IDs
+pub fn query(homelander: &mut Homelander, ids: &[&str]) -> response::query::Payload {
+    match homelander.handle_request(query_request(ids)).payload {
+        ResponsePayload::Query(payload) => payload,
+        _ => panic!("Expected a QUERY payload"),
+    }
+}
+
+/// Run an EXECUTE intent with a single command for the devices with the provided IDs
+pub fn execute(homelander: &mut Homelander, ids: &[&str], command: CommandType) -> response::execute::Payload {
+    match homelander.handle_request(execute_request(ids, command)).payload {
+        ResponsePayload::Execute(payload) => payload,
+        _ => panic!("Expected an EXECUTE payload"),
+    }
+}
@@ -0,0 +1,157 @@
+mod common;
+
+use homelander::fulfillment::request::execute::CommandType;
+use homelander::fulfillment::response::execute::CommandStatus;
+use homelander::traits::cook::{Cook, CookError, CookingConfig, CookingMode, FoodPreset};
+use homelander::traits::{Language, SizeUnit, Synonym};
+use homelander::{Device, DeviceType, Homelander};
+use serde_json::json;
+
+#[derive(Debug, Default)]
+struct RiceCooker {
+    cooking: Option<(String, f32)>,
+}
+
+google_home_device!(RiceCooker, "RiceCooker");
+
+impl Cook for RiceCooker {
+    fn get_supported_cooking_modes(&self) -> Result<Vec<CookingMode>, CookError> {
+        Ok(vec![CookingMode::Cook])
+    }
+
+    fn get_food_presets(&self) -> Result<Vec<FoodPreset>, CookError> {
+        Ok(vec![FoodPreset {
+            food_preset_name: "white_rice".to_string(),
+            supported_unit: vec![SizeUnit::Cups],
+            food_synonyms: vec![Synonym::new(vec!["white rice".to_string(), "rice".to_string()], Language::English)],
+        }])
+    }
+
+    fn get_max_food_quantity(&self, _food_preset: &str, _unit: &SizeUnit) -> Result<Option<f32>, CookError> {
+        Ok(Some(5.0))
+    }
+
+    fn supports_fractional_food_quantity(&self) -> Result<bool, CookError> {
+        Ok(false)
+    }
+
+    fn get_current_cooking_mode(&self) -> Result<CookingMode, CookError> {
+        Ok(if self.cooking.is_some() { CookingMode::Cook } else { CookingMode::None })
+    }
+
+    fn get_current_food_preset(&self) -> Result<Option<String>, CookError> {
+        Ok(self.cooking.as_ref().map(|(preset, _)| preset.clone()))
+    }
+
+    fn get_current_food_quantity(&self) -> Result<Option<f32>, CookError> {
+        Ok(self.cooking.as_ref().map(|(_, quantity)| *quantity))
+    }
+
+    fn get_current_food_unit(&self) -> Result<Option<SizeUnit>, CookError> {
+        Ok(self.cooking.as_ref().map(|_| SizeUnit::Cups))
+    }
+
+    fn start(&mut self, config: CookingConfig) -> Result<(), CookError> {
+        self.cooking = Some((config.food_preset.unwrap_or_default(), config.quantity.unwrap_or(1.0)));
+        Ok(())
+    }
+
+    fn stop(&mut self) -> Result<(), CookError> {
+        self.cooking = None;
+        Ok(())
+    }
+}
+
+/// Start cooking `quantity` of `food_preset` in `unit` and return the error code, if any
+fn cook(food_preset: &str, quantity: f32, unit: SizeUnit) -> Option<serde_json::Value> {
+    let mut device = Device::new(RiceCooker::default(), DeviceType::Multicooker, "00".to_string());
+    device.set_cook();
+
+    let mut homelander = Homelander::new("01".to_string());
+    homelander.add_device(device);
+
+    let payload = common::execute(
+        &mut homelander,
+        &["00"],
+        CommandType::Cook {
+            start: true,
+            cooking_mode: Some(CookingMode::Cook),
+            food_preset: Some(food_preset.to_string()),
+            quantity: Some(quantity),
+            unit: Some(unit),
+        },
+    );
+
+    match payload.commands[0].status {
+        CommandStatus::Success => None,
+        _ => Some(serde_json::to_value(&payload.commands[0].error_code).unwrap()),
+    }
+}
+
+#[test]
+fn cook_known_preset() {
+    assert_eq!(cook("white_rice", 2.0, SizeUnit::Cups), None);
+}
+
+#[test]
+fn cook_unknown_food_preset() {
+    assert_eq!(cook("brown_rice", 2.0, SizeUnit::Cups), Some(json!("unknownFoodPreset")));
+}
+
+#[test]
+fn cook_unsupported_unit() {
+    assert_eq!(cook("white_rice", 2.0, SizeUnit::Pounds), Some(json!("functionNotSupported")));
+}
+
+#[test]
+fn cook_above_limit() {
+    assert_eq!(cook("white_rice", 6.0, SizeUnit::Cups), Some(json!("amountAboveLimit")));
+}
+
+#[test]
+fn cook_fractional_amount() {
+    assert_eq!(cook("white_rice", 1.5, SizeUnit::Cups), Some(json!("fractionalAmountNotSupported")));
+}
+
+/// QUERY the state of `cooker`
+fn query(cooker: RiceCooker) -> serde_json::Value {
+    let mut device = Device::new(cooker, DeviceType::Multicooker, "00".to_string());
+    device.set_cook();
+
+    let mut homelander = Homelander::new("01".to_string());
+    homelander.add_device(device);
+
+    let payload = common::query(&mut homelander, &["00"]);
+    serde_json::to_value(&payload.devices["00"]).unwrap()
+}
+
+#[test]
+fn query_cooking_rice() {
+    let cooker = RiceCooker {
+        cooking: Some(("white_rice".to_string(), 2.0)),
+    };
+
+    assert_eq!(
+        query(cooker),
+        json!({
+            "online": true,
+            "status": "SUCCESS",
+            "currentCookingMode": "COOK",
+            "currentFoodPreset": "white_rice",
+            "currentFoodQuantity": 2.0,
+            "currentFoodUnit": "CUPS",
+        })
+    );
+}
+
+#[test]
+fn query_idle_cooker() {
+    assert_eq!(
+        query(RiceCooker::default()),
+        json!({
+            "online": true,
+            "status": "SUCCESS",
+            "currentCookingMode": "NONE",
+        })
+    );
+}
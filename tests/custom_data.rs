@@ -0,0 +1,172 @@
+use homelander::fulfillment::request::execute::CommandType;
+use homelander::fulfillment::request::{Input, Request};
+use homelander::traits::on_off::OnOff;
+use homelander::traits::{CombinedDeviceError, DeviceInfo, DeviceName, GoogleHomeDevice};
+use homelander::{Device, DeviceType, Homelander};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::sync::{Arc, Mutex};
+
+/// Keeps the custom data it received during EXECUTE in a handle shared with the test
+#[derive(Debug)]
+struct Outlet {
+    on: bool,
+    custom_data: Option<Value>,
+    executed_with: Arc<Mutex<Vec<Option<Value>>>>,
+}
+
+impl GoogleHomeDevice for Outlet {
+    fn get_device_info(&self) -> DeviceInfo {
+        DeviceInfo {
+            manufacturer: "Array21 Development".to_string(),
+            model: "Outlet".to_string(),
+            hw: "0.1.0".to_string(),
+            sw: "0.1.0".to_string(),
+        }
+    }
+
+    fn will_report_state(&self) -> bool {
+        false
+    }
+
+    fn get_device_name(&self) -> DeviceName {
+        DeviceName {
+            name: "Outlet".to_string(),
+            nicknames: Vec::new(),
+            default_names: Vec::new(),
+        }
+    }
+
+    fn is_online(&self) -> bool {
+        true
+    }
+
+    fn get_custom_data(&self) -> Option<Value> {
+        Some(json!({ "bridge": "living-room", "channel": 3 }))
+    }
+
+    fn set_custom_data(&mut self, custom_data: Option<Value>) {
+        self.custom_data = custom_data;
+    }
+
+    fn disconnect(&mut self) {}
+}
+
+impl OnOff for Outlet {
+    fn is_on(&self) -> Result<bool, CombinedDeviceError> {
+        Ok(self.on)
+    }
+
+    fn set_on(&mut self, on: bool) -> Result<(), CombinedDeviceError> {
+        self.executed_with.lock().unwrap().push(self.custom_data.clone());
+        self.on = on;
+        Ok(())
+    }
+}
+
+fn setup_homelander(executed_with: Arc<Mutex<Vec<Option<Value>>>>) -> Homelander {
+    let outlet = Outlet {
+        on: false,
+        custom_data: None,
+        executed_with,
+    };
+    let mut device = Device::new(outlet, DeviceType::Outlet, "00".to_string());
+    device.set_on_off();
+
+    let mut homelander = Homelander::new("01".to_string());
+    homelander.add_device(device);
+    homelander
+}
+
+/// The custom data of [Outlet], as known to the bridge
+#[derive(Debug, PartialEq, Deserialize)]
+struct BridgeChannel {
+    bridge: String,
+    channel: u8,
+}
+
+fn execute_body(custom_data: Option<&Value>) -> String {
+    let mut device = json!({ "id": "00" });
+    if let Some(custom_data) = custom_data {
+        device["customData"] = custom_data.clone();
+    }
+
+    json!({
+        "requestId": "02",
+        "inputs": [{
+            "intent": "action.devices.EXECUTE",
+            "payload": {
+                "commands": [{
+                    "devices": [device],
+                    "execution": [{
+                        "command": "action.devices.commands.OnOff",
+                        "params": { "on": true }
+                    }]
+                }]
+            }
+        }]
+    })
+    .to_string()
+}
+
+#[test]
+fn custom_data_round_trip() {
+    let executed_with = Arc::new(Mutex::new(Vec::new()));
+    let mut homelander = setup_homelander(executed_with.clone());
+
+    let sync = homelander
+        .handle_request_json(r#"{"requestId": "01", "inputs": [{"intent": "action.devices.SYNC"}]}"#)
+        .unwrap();
+    let sync: Value = serde_json::from_str(&sync).unwrap();
+    let custom_data = &sync["payload"]["devices"][0]["customData"];
+    assert_eq!(custom_data, &json!({ "bridge": "living-room", "channel": 3 }));
+
+    // Google echoes the custom data from SYNC back in EXECUTE
+    homelander.handle_request_json(&execute_body(Some(custom_data))).unwrap();
+    // And the device should not hold on to stale custom data if Google sends none
+    homelander.handle_request_json(&execute_body(None)).unwrap();
+
+    assert_eq!(*executed_with.lock().unwrap(), vec![Some(custom_data.clone()), None]);
+}
+
+#[test]
+fn custom_data_and_structure_data_are_preserved() {
+    let executed_with = Arc::new(Mutex::new(Vec::new()));
+    let mut homelander = setup_homelander(executed_with);
+
+    let sync = homelander
+        .handle_request_json(r#"{"requestId": "01", "inputs": [{"intent": "action.devices.SYNC"}]}"#)
+        .unwrap();
+    let sync: Value = serde_json::from_str(&sync).unwrap();
+
+    let mut body: Value = serde_json::from_str(&execute_body(Some(&sync["payload"]["devices"][0]["customData"]))).unwrap();
+    body["inputs"][0]["payload"]["structureData"] = json!({ "home": "Amsterdam" });
+    let request: Request = serde_json::from_value(body).unwrap();
+
+    let execute = match &request.inputs[0] {
+        Input::Execute(execute) => execute,
+        input => panic!("Expected an EXECUTE input, got {input:?}"),
+    };
+    assert_eq!(execute.structure_data, Some(json!({ "home": "Amsterdam" })));
+
+    let device = &execute.commands[0].devices[0];
+    assert_eq!(
+        device.custom_data_as::<BridgeChannel>().unwrap().unwrap(),
+        BridgeChannel {
+            bridge: "living-room".to_string(),
+            channel: 3,
+        }
+    );
+}
+
+#[test]
+fn local_command_has_no_custom_data() {
+    let executed_with = Arc::new(Mutex::new(Vec::new()));
+    let mut homelander = setup_homelander(executed_with.clone());
+
+    let custom_data = json!({ "bridge": "living-room", "channel": 3 });
+    homelander.handle_request_json(&execute_body(Some(&custom_data))).unwrap();
+    homelander.execute_command("00", CommandType::OnOff { on: false });
+
+    assert_eq!(*executed_with.lock().unwrap(), vec![Some(custom_data), None]);
+}
@@ -0,0 +1,68 @@
+#![cfg(feature = "derive")]
+
+mod common;
+
+use homelander::traits::on_off::OnOff;
+use homelander::traits::{CombinedDeviceError, GoogleHomeDevice};
+use homelander::{Device, DeviceType, Homelander};
+
+#[derive(Debug, homelander::GoogleHomeDevice)]
+#[google_home(manufacturer = "Array21 Development", model = "Lamp", name = "Lamp", hw = "1.0", sw = "2.0")]
+#[google_home(nicknames = ["Desk lamp"], room_hint = "Office", will_report_state = true, is_online = "is_connected")]
+struct Lamp {
+    on: bool,
+    connected: bool,
+}
+
+impl Lamp {
+    fn is_connected(&self) -> bool {
+        self.connected
+    }
+}
+
+impl OnOff for Lamp {
+    fn is_on(&self) -> Result<bool, CombinedDeviceError> {
+        Ok(self.on)
+    }
+
+    fn set_on(&mut self, on: bool) -> Result<(), CombinedDeviceError> {
+        self.on = on;
+        Ok(())
+    }
+}
+
+#[derive(Debug, homelander::GoogleHomeDevice)]
+#[google_home(manufacturer = "Array21 Development", model = "Outlet", name = "Outlet")]
+struct Outlet;
+
+#[test]
+fn derive_uses_attributes() {
+    let mut device = Device::new(Lamp { on: false, connected: false }, DeviceType::Light, "00".to_string());
+    device.set_on_off();
+
+    let mut homelander = Homelander::new("01".to_string());
+    homelander.add_device(device);
+
+    let payload = common::sync(&mut homelander);
+    let device = serde_json::to_value(&payload.devices[0]).unwrap();
+    assert_eq!(device["name"]["name"], "Lamp");
+    assert_eq!(device["name"]["nicknames"][0], "Desk lamp");
+    assert_eq!(device["deviceInfo"]["hwVersion"], "1.0");
+    assert_eq!(device["deviceInfo"]["swVersion"], "2.0");
+    assert_eq!(device["roomHint"], "Office");
+    assert_eq!(device["willReportState"], true);
+
+    let payload = common::query(&mut homelander, &["00"]);
+    let state = serde_json::to_value(&payload).unwrap();
+    assert_eq!(state["devices"]["00"]["online"], false);
+}
+
+#[test]
+fn derive_defaults() {
+    let outlet = Outlet;
+    assert!(outlet.is_online());
+    assert!(!outlet.will_report_state());
+    assert_eq!(outlet.get_device_info().hw, env!("CARGO_PKG_VERSION"));
+    assert!(outlet.get_device_name().nicknames.is_empty());
+    assert_eq!(outlet.get_room_hint(), None);
+}
@@ -0,0 +1,73 @@
+mod common;
+
+use homelander::traits::on_off::OnOff;
+use homelander::traits::CombinedDeviceError;
+use homelander::{Device, DeviceType, Homelander, Trait};
+use serde_json::json;
+
+#[derive(Debug)]
+struct Outlet {
+    on: bool,
+}
+
+google_home_device!(Outlet, "Outlet");
+
+impl OnOff for Outlet {
+    fn is_on(&self) -> Result<bool, CombinedDeviceError> {
+        Ok(self.on)
+    }
+
+    fn set_on(&mut self, on: bool) -> Result<(), CombinedDeviceError> {
+        self.on = on;
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+struct Lamp;
+
+google_home_device!(Lamp, "Lamp");
+
+fn setup_homelander() -> Homelander {
+    let mut device = Device::new(Outlet { on: false }, DeviceType::Outlet, "00".to_string());
+    device.set_on_off();
+
+    let mut homelander = Homelander::new("01".to_string());
+    homelander.add_device(device);
+    homelander
+}
+
+fn assert_on(homelander: &mut Homelander, on: bool) {
+    let payload = common::query(homelander, &["00"]);
+    let state = serde_json::to_value(&payload.devices["00"]).unwrap();
+    assert_eq!(state["on"], json!(on));
+}
+
+#[test]
+fn get_device() {
+    let homelander = setup_homelander();
+    assert_eq!(homelander.get_device("00").unwrap().registered_traits(), &[Trait::OnOff]);
+    assert!(homelander.get_device("01").is_none());
+}
+
+#[test]
+fn get_device_mut() {
+    let mut homelander = setup_homelander();
+    homelander.get_device_mut("00").unwrap().unset_on_off();
+
+    let payload = common::sync(&mut homelander);
+    let device = serde_json::to_value(&payload.devices[0]).unwrap();
+    assert_eq!(device["traits"], json!([]));
+}
+
+#[test]
+fn with_device_mut() {
+    let mut homelander = setup_homelander();
+    assert_on(&mut homelander, false);
+
+    assert_eq!(homelander.with_device_mut("00", |outlet: &mut Outlet| outlet.on = true), Some(()));
+    assert_on(&mut homelander, true);
+
+    assert_eq!(homelander.with_device_mut("00", |_: &mut Lamp| ()), None);
+    assert_eq!(homelander.with_device_mut("01", |_: &mut Outlet| ()), None);
+}
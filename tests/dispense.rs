@@ -0,0 +1,160 @@
+mod common;
+
+use homelander::fulfillment::request::execute::CommandType;
+use homelander::fulfillment::response::execute::CommandStatus;
+use homelander::traits::dispense::{Dispense, DispenseAmount, DispenseError, DispenseItem, DispenseItemState, DispensePreset};
+use homelander::traits::{Language, SizeUnit, Synonym};
+use homelander::{Device, DeviceType, Homelander};
+use serde_json::json;
+use std::sync::Mutex;
+
+/// Dispenses cups of dry food
+#[derive(Debug)]
+struct PetFeeder {
+    remaining_cups: Mutex<f32>,
+    last_dispensed_cups: Mutex<f32>,
+}
+
+google_home_device!(PetFeeder, "PetFeeder");
+
+fn cups(amount: f32) -> DispenseAmount {
+    DispenseAmount { amount, unit: SizeUnit::Cups }
+}
+
+impl Dispense for PetFeeder {
+    fn get_supported_dispense_items(&self) -> Result<Vec<DispenseItem>, DispenseError> {
+        Ok(vec![DispenseItem {
+            item_name: "dry_food".to_string(),
+            item_name_synonyms: vec![Synonym::new(vec!["dry food".to_string(), "kibble".to_string()], Language::English)],
+            supported_units: vec![SizeUnit::Cups],
+            default_portion: cups(1.0),
+        }])
+    }
+
+    fn get_supported_dispense_presets(&self) -> Result<Vec<DispensePreset>, DispenseError> {
+        Ok(vec![DispensePreset {
+            preset_name: "breakfast".to_string(),
+            preset_name_synonyms: vec![Synonym::new(vec!["breakfast".to_string()], Language::English)],
+        }])
+    }
+
+    fn get_dispense_items_state(&self) -> Result<Vec<DispenseItemState>, DispenseError> {
+        Ok(vec![DispenseItemState {
+            item_name: "dry_food".to_string(),
+            amount_remaining: cups(*self.remaining_cups.lock().unwrap()),
+            amount_last_dispensed: cups(*self.last_dispensed_cups.lock().unwrap()),
+            is_currently_dispensing: false,
+        }])
+    }
+
+    fn dispense_amount(&self, _item: String, amount: i32, _unit: SizeUnit) -> Result<(), DispenseError> {
+        *self.remaining_cups.lock().unwrap() -= amount as f32;
+        *self.last_dispensed_cups.lock().unwrap() = amount as f32;
+        Ok(())
+    }
+
+    fn dispense_preset(&self, _preset: String) -> Result<(), DispenseError> {
+        self.dispense_amount("dry_food".to_string(), 1, SizeUnit::Cups)
+    }
+
+    fn dispense_default(&self) -> Result<(), DispenseError> {
+        self.dispense_amount("dry_food".to_string(), 1, SizeUnit::Cups)
+    }
+}
+
+fn setup_homelander() -> Homelander {
+    let feeder = PetFeeder {
+        remaining_cups: Mutex::new(10.0),
+        last_dispensed_cups: Mutex::new(0.0),
+    };
+    let mut device = Device::new(feeder, DeviceType::Petfeeder, "00".to_string());
+    device.set_dispense();
+
+    let mut homelander = Homelander::new("01".to_string());
+    homelander.add_device(device);
+    homelander
+}
+
+#[test]
+fn sync_pet_feeder() {
+    let mut homelander = setup_homelander();
+    let payload = common::sync(&mut homelander);
+    let attributes = serde_json::to_value(&payload.devices[0].attributes).unwrap();
+
+    assert_eq!(
+        attributes["supportedDispenseItems"],
+        json!([{
+            "item_name": "dry_food",
+            "item_name_synonyms": [{ "synonym": ["dry food", "kibble"], "lang": "en" }],
+            "supported_units": ["CUPS"],
+            "default_portion": { "amount": 1.0, "unit": "CUPS" },
+        }])
+    );
+    assert_eq!(
+        attributes["supportedDispensePresets"],
+        json!([{ "preset_name": "breakfast", "preset_name_synonyms": [{ "synonym": ["breakfast"], "lang": "en" }] }])
+    );
+}
+
+#[test]
+fn dispense_cups() {
+    let mut homelander = setup_homelander();
+    let payload = common::execute(
+        &mut homelander,
+        &["00"],
+        CommandType::Dispense {
+            item: Some("dry_food".to_string()),
+            amount: Some(2),
+            unit: Some(SizeUnit::Cups),
+            preset_name: None,
+        },
+    );
+    assert_eq!(payload.commands[0].status, CommandStatus::Success);
+
+    let payload = common::query(&mut homelander, &["00"]);
+    let state = serde_json::to_value(&payload.devices["00"]).unwrap();
+    assert_eq!(
+        state["dispenseItems"],
+        json!([{
+            "itemName": "dry_food",
+            "amountRemaining": { "amount": 8.0, "unit": "CUPS" },
+            "amountLastDispensed": { "amount": 2.0, "unit": "CUPS" },
+            "isCurrentlyDispensing": false,
+        }])
+    );
+}
+
+#[test]
+fn dispense_item_without_amount() {
+    let mut homelander = setup_homelander();
+    let payload = common::execute(
+        &mut homelander,
+        &["00"],
+        CommandType::Dispense {
+            item: Some("dry_food".to_string()),
+            amount: None,
+            unit: None,
+            preset_name: None,
+        },
+    );
+    assert_eq!(payload.commands[0].status, CommandStatus::Error);
+    assert_eq!(serde_json::to_value(&payload.commands[0].error_code).unwrap(), json!("protocolError"));
+
+    let payload = common::execute(
+        &mut homelander,
+        &["00"],
+        CommandType::Dispense {
+            item: None,
+            amount: Some(2),
+            unit: Some(SizeUnit::Cups),
+            preset_name: None,
+        },
+    );
+    assert_eq!(payload.commands[0].status, CommandStatus::Error);
+    assert_eq!(serde_json::to_value(&payload.commands[0].error_code).unwrap(), json!("dispenseNotSupported"));
+
+    // A malformed command doesn't leave the device broken
+    let payload = common::query(&mut homelander, &["00"]);
+    let state = serde_json::to_value(&payload.devices["00"]).unwrap();
+    assert_eq!(state["status"], json!("SUCCESS"));
+}
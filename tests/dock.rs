@@ -0,0 +1,63 @@
+mod common;
+
+use homelander::fulfillment::request::execute::CommandType;
+use homelander::fulfillment::response::execute::CommandStatus;
+use homelander::traits::dock::Dock;
+use homelander::traits::{CombinedDeviceError, DeviceError};
+use homelander::{Device, DeviceType, Homelander};
+use serde_json::json;
+
+#[derive(Debug)]
+struct Vacuum {
+    docked: bool,
+}
+
+google_home_device!(Vacuum, "Vacuum");
+
+impl Dock for Vacuum {
+    fn is_docked(&self) -> Result<bool, CombinedDeviceError> {
+        Ok(self.docked)
+    }
+
+    fn dock(&mut self) -> Result<(), CombinedDeviceError> {
+        self.docked = true;
+        Ok(())
+    }
+}
+
+fn setup_homelander() -> Homelander {
+    let mut device = Device::new(Vacuum { docked: false }, DeviceType::Vacuum, "00".to_string());
+    device.set_dock();
+
+    let mut homelander = Homelander::new("01".to_string());
+    homelander.add_device(device);
+    homelander
+}
+
+#[test]
+fn sync_dock() {
+    let mut homelander = setup_homelander();
+    let payload = common::sync(&mut homelander);
+    let device = serde_json::to_value(&payload.devices[0]).unwrap();
+
+    assert_eq!(device["type"], json!("action.devices.types.VACUUM"));
+    assert_eq!(device["traits"], json!(["action.devices.traits.Dock"]));
+}
+
+#[test]
+fn dock_and_query() {
+    let mut homelander = setup_homelander();
+
+    let payload = common::execute(&mut homelander, &["00"], CommandType::Dock);
+    assert_eq!(payload.commands[0].status, CommandStatus::Success);
+
+    let payload = common::query(&mut homelander, &["00"]);
+    let state = serde_json::to_value(&payload.devices["00"]).unwrap();
+    assert_eq!(state["isDocked"], json!(true));
+}
+
+#[test]
+fn undock_not_supported_by_default() {
+    let mut vacuum = Vacuum { docked: true };
+    assert!(matches!(vacuum.undock(), Err(CombinedDeviceError::DeviceError(DeviceError::NotSupported))));
+}
@@ -1,10 +1,14 @@
-use homelander::fulfillment::request::execute::{Command, CommandType, Execute};
+mod common;
+
+use homelander::fulfillment::request::execute::{Command, CommandType, Execute, Execution};
 use homelander::fulfillment::request::Input;
-use homelander::fulfillment::response::execute::CommandStatus;
+use homelander::fulfillment::response::execute::{CommandState, CommandStatus, OnOffCommandState, TraitCommandState};
 use homelander::fulfillment::response::ResponsePayload;
 use homelander::traits::on_off::OnOff;
-use homelander::traits::{CombinedDeviceError, DeviceInfo, DeviceName, GoogleHomeDevice};
+use homelander::traits::temperature_setting::{QueryThermostatMode, QueryThermostatModeFixed, TemperatureSetting, ThermostatMode};
+use homelander::traits::{CombinedDeviceError, DeviceError, DeviceInfo, DeviceName, GoogleHomeDevice, TemperatureUnit};
 use homelander::{Device, DeviceType, Homelander, Request, Response};
+use serde_json::json;
 
 #[derive(Debug)]
 struct UltimateSwitch {
@@ -36,6 +40,8 @@ impl GoogleHomeDevice for UltimateSwitch {
     fn is_online(&self) -> bool {
         true
     }
+
+    fn disconnect(&mut self) {}
 }
 
 impl OnOff for UltimateSwitch {
@@ -65,9 +71,16 @@ fn get_request_payload() -> Request {
         request_id: "02".to_string(),
         inputs: vec![Input::Execute(Execute {
             commands: vec![Command {
-                devices: vec![homelander::fulfillment::request::execute::Device { id: "00".to_string() }],
-                execution: vec![CommandType::OnOff { on: true }],
+                devices: vec![homelander::fulfillment::request::execute::Device {
+                    id: "00".to_string(),
+                    custom_data: None,
+                }],
+                execution: vec![Execution {
+                    command: CommandType::OnOff { on: true },
+                    challenge: None,
+                }],
             }],
+            structure_data: None,
         })],
     }
 }
@@ -79,16 +92,188 @@ fn get_response_payload() -> Response {
             commands: vec![homelander::fulfillment::response::execute::Command {
                 debug_string: None,
                 error_code: None,
+                error_code_string: None,
                 status: CommandStatus::Success,
                 ids: vec!["00".to_string()],
-                states: None,
+                states: Some(CommandState {
+                    online: true,
+                    traits: TraitCommandState::OnOff(OnOffCommandState { on: true }),
+                }),
+                challenge_needed: None,
             }],
         }),
     }
 }
 
-fn main() {
+#[test]
+fn execute_on_off() {
     let mut homelander = setup_homelander();
     let response = homelander.handle_request(get_request_payload());
     assert_eq!(response, get_response_payload());
 }
+
+#[test]
+fn execute_unhandled_command() {
+    let mut homelander = setup_homelander();
+    let payload = common::execute(&mut homelander, &["00"], CommandType::SetHumidity { humidity: 40 });
+    let command = serde_json::to_value(&payload.commands[0]).unwrap();
+    assert_eq!(payload.commands[0].status, CommandStatus::Error);
+    assert_eq!(command["errorCode"], json!("functionNotSupported"));
+}
+
+/// A switch which can't be switched remotely
+#[derive(Debug)]
+struct LockedSwitch;
+
+google_home_device!(LockedSwitch, "LockedSwitch");
+
+impl OnOff for LockedSwitch {
+    fn is_on(&self) -> Result<bool, CombinedDeviceError> {
+        Ok(false)
+    }
+
+    fn set_on(&mut self, _on: bool) -> Result<(), CombinedDeviceError> {
+        Err(CombinedDeviceError::DeviceError(DeviceError::FunctionNotSupported))
+    }
+}
+
+#[test]
+fn execute_groups_identical_results() {
+    let mut homelander = Homelander::new("01".to_string());
+    for id in ["00", "10", "20"] {
+        let mut device = Device::new(UltimateSwitch { on: false }, DeviceType::Light, id.to_string());
+        device.set_on_off();
+        homelander.add_device(device);
+    }
+    let mut device = Device::new(LockedSwitch, DeviceType::Light, "30".to_string());
+    device.set_on_off();
+    homelander.add_device(device);
+
+    let payload = common::execute(&mut homelander, &["00", "10", "20", "30"], CommandType::OnOff { on: true });
+    let commands = serde_json::to_value(&payload.commands).unwrap();
+    assert_eq!(
+        commands,
+        json!([
+            { "ids": ["00", "10", "20"], "status": "SUCCESS", "states": { "online": true, "on": true }, "errorCode": null, "debugString": null },
+            { "ids": ["30"], "status": "ERROR", "states": null, "errorCode": "functionNotSupported", "debugString": null }
+        ])
+    );
+}
+
+#[derive(Debug)]
+struct Thermostat {
+    setpoint: f32,
+}
+
+google_home_device!(Thermostat, "Thermostat");
+
+impl TemperatureSetting for Thermostat {
+    fn get_available_thermostat_modes(&self) -> Result<Vec<ThermostatMode>, CombinedDeviceError> {
+        Ok(vec![ThermostatMode::Off, ThermostatMode::Heat])
+    }
+
+    fn get_thermostat_temperature_unit(&self) -> Result<TemperatureUnit, CombinedDeviceError> {
+        Ok(TemperatureUnit::Celsius)
+    }
+
+    fn get_active_thermostat_mode(&self) -> Result<ThermostatMode, CombinedDeviceError> {
+        Ok(ThermostatMode::Heat)
+    }
+
+    fn get_thermostat_mode(&self) -> Result<QueryThermostatMode, CombinedDeviceError> {
+        Ok(QueryThermostatMode::Fixed(QueryThermostatModeFixed {
+            thermostat_mode: ThermostatMode::Heat,
+            thermostat_temperature_ambient: 19.0,
+            thermostat_temperature_setpoint: self.setpoint,
+        }))
+    }
+
+    fn set_temperature_setpoint(&mut self, setpoint: f32) -> Result<(), CombinedDeviceError> {
+        self.setpoint = setpoint;
+        Ok(())
+    }
+
+    fn set_temperature_set_range(&mut self, _setpoint_high: f32, _setpoint_low: f32) -> Result<(), CombinedDeviceError> {
+        Err(CombinedDeviceError::DeviceError(DeviceError::FunctionNotSupported))
+    }
+
+    fn set_thermostat_mode(&mut self, _mode: ThermostatMode) -> Result<(), CombinedDeviceError> {
+        Ok(())
+    }
+
+    fn set_temperature_relative_degree(&mut self, relative_degrees: f32) -> Result<(), CombinedDeviceError> {
+        self.setpoint += relative_degrees;
+        Ok(())
+    }
+
+    fn set_temperature_relative_weight(&mut self, _weight: f32) -> Result<(), CombinedDeviceError> {
+        Ok(())
+    }
+}
+
+fn command(ids: &[&str], command: CommandType) -> Command {
+    Command {
+        devices: ids
+            .iter()
+            .map(|id| homelander::fulfillment::request::execute::Device {
+                id: id.to_string(),
+                custom_data: None,
+            })
+            .collect(),
+        execution: vec![Execution { command, challenge: None }],
+    }
+}
+
+#[test]
+fn execute_multiple_command_groups() {
+    let mut homelander = Homelander::new("01".to_string());
+    for id in ["00", "10"] {
+        let mut device = Device::new(UltimateSwitch { on: false }, DeviceType::Light, id.to_string());
+        device.set_on_off();
+        homelander.add_device(device);
+    }
+    let mut device = Device::new(Thermostat { setpoint: 18.0 }, DeviceType::Thermostat, "20".to_string());
+    device.set_temperature_setting();
+    homelander.add_device(device);
+
+    let request = Request {
+        request_id: "02".to_string(),
+        inputs: vec![Input::Execute(Execute {
+            commands: vec![
+                command(&["00", "10"], CommandType::OnOff { on: true }),
+                command(
+                    &["20"],
+                    CommandType::ThermostatTemperatureSetpoint {
+                        thermostat_temperature_setpoint: 21.5,
+                    },
+                ),
+            ],
+            structure_data: None,
+        })],
+    };
+
+    let response = serde_json::to_value(homelander.handle_request(request)).unwrap();
+    assert_eq!(
+        response["payload"]["commands"],
+        json!([
+            { "ids": ["00", "10"], "status": "SUCCESS", "states": { "online": true, "on": true }, "errorCode": null, "debugString": null },
+            { "ids": ["20"], "status": "SUCCESS", "states": null, "errorCode": null, "debugString": null }
+        ])
+    );
+    assert_eq!(homelander.with_device_mut("20", |thermostat: &mut Thermostat| thermostat.setpoint), Some(21.5));
+    assert_eq!(homelander.with_device_mut("10", |switch: &mut UltimateSwitch| switch.on), Some(true));
+}
+
+#[test]
+fn execute_unknown_and_duplicate_devices() {
+    let mut homelander = setup_homelander();
+    let payload = common::execute(&mut homelander, &["00", "99", "00"], CommandType::OnOff { on: true });
+    let commands = serde_json::to_value(&payload.commands).unwrap();
+    assert_eq!(
+        commands,
+        json!([
+            { "ids": ["00"], "status": "SUCCESS", "states": { "online": true, "on": true }, "errorCode": null, "debugString": null },
+            { "ids": ["99"], "status": "ERROR", "states": null, "errorCode": "deviceNotFound", "debugString": null }
+        ])
+    );
+}
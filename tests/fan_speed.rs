@@ -0,0 +1,183 @@
+mod common;
+
+use homelander::fulfillment::request::execute::CommandType;
+use homelander::fulfillment::response::execute::CommandStatus;
+use homelander::traits::fan_speed::{AvailableFanSpeeds, FanSpeed, FanSpeedError, FanSpeedItem, FanSpeedValue};
+use homelander::traits::Language;
+use homelander::{Device, DeviceType, Homelander};
+use serde_json::json;
+use std::sync::Mutex;
+
+#[derive(Debug)]
+struct Fan {
+    speed: Mutex<String>,
+    percent: Mutex<f32>,
+}
+
+google_home_device!(Fan, "Fan");
+
+fn speed(name: &str) -> FanSpeedItem {
+    FanSpeedItem::new(name.to_string(), vec![FanSpeedValue::new(vec![name.to_string()], Language::English)])
+}
+
+impl FanSpeed for Fan {
+    fn get_available_fan_speeds(&self) -> Result<Option<AvailableFanSpeeds>, FanSpeedError> {
+        Ok(Some(AvailableFanSpeeds {
+            speeds: vec![speed("low"), speed("high")],
+            ordered: true,
+        }))
+    }
+
+    fn is_support_fan_speed_percent(&self) -> Result<Option<bool>, FanSpeedError> {
+        Ok(Some(true))
+    }
+
+    fn get_current_fan_speed_setting(&self) -> Result<Option<String>, FanSpeedError> {
+        Ok(Some(self.speed.lock().unwrap().clone()))
+    }
+
+    fn get_current_fan_speed_percent(&self) -> Result<Option<f32>, FanSpeedError> {
+        Ok(Some(*self.percent.lock().unwrap()))
+    }
+
+    fn set_fan_speed_setting(&self, name: String) -> Result<(), FanSpeedError> {
+        *self.speed.lock().unwrap() = name;
+        Ok(())
+    }
+
+    fn set_fan_speed_percent(&self, percent: f32) -> Result<(), FanSpeedError> {
+        *self.percent.lock().unwrap() = percent;
+        Ok(())
+    }
+
+    fn set_fan_speed_relative_weight(&self, _weight: i32) -> Result<(), FanSpeedError> {
+        Ok(())
+    }
+
+    fn set_fan_speed_relative_percent(&self, _percent: f32) -> Result<(), FanSpeedError> {
+        Ok(())
+    }
+
+    fn set_fan_reverse(&self) -> Result<(), FanSpeedError> {
+        Ok(())
+    }
+}
+
+fn setup_homelander() -> Homelander {
+    let fan = Fan {
+        speed: Mutex::new("low".to_string()),
+        percent: Mutex::new(10.0),
+    };
+    let mut device = Device::new(fan, DeviceType::Fan, "00".to_string());
+    device.set_fan_speed();
+
+    let mut homelander = Homelander::new("01".to_string());
+    homelander.add_device(device);
+    homelander
+}
+
+#[test]
+fn sync_available_fan_speeds() {
+    let mut homelander = setup_homelander();
+    let payload = common::sync(&mut homelander);
+    let attributes = serde_json::to_value(&payload.devices[0].attributes).unwrap();
+
+    assert_eq!(
+        attributes["availableFanSpeeds"],
+        json!({
+            "speeds": [
+                { "speed_name": "low", "speed_values": [{ "speed_synonym": ["low"], "lang": "en" }] },
+                { "speed_name": "high", "speed_values": [{ "speed_synonym": ["high"], "lang": "en" }] },
+            ],
+            "ordered": true,
+        })
+    );
+    assert_eq!(attributes["supportsFanSpeedPercent"], json!(true));
+}
+
+fn assert_fan_speed(homelander: &mut Homelander, speed: &str, percent: f32) {
+    let payload = common::query(homelander, &["00"]);
+    let state = serde_json::to_value(&payload.devices["00"]).unwrap();
+    assert_eq!(state["currentFanSpeedSetting"], json!(speed));
+    assert_eq!(state["currentFanSpeedPercent"], json!(percent));
+}
+
+fn assert_value_out_of_range(homelander: &mut Homelander, command: CommandType) {
+    let payload = common::execute(homelander, &["00"], command);
+    let command = serde_json::to_value(&payload.commands[0]).unwrap();
+    assert_eq!(payload.commands[0].status, CommandStatus::Error);
+    assert_eq!(command["errorCode"], json!("valueOutOfRange"));
+}
+
+#[test]
+fn set_fan_speed_valid_name() {
+    let mut homelander = setup_homelander();
+
+    let payload = common::execute(
+        &mut homelander,
+        &["00"],
+        CommandType::SetFanSpeed {
+            fan_speed: Some("high".to_string()),
+            fan_speed_percent: None,
+        },
+    );
+    assert_eq!(payload.commands[0].status, CommandStatus::Success);
+    assert_fan_speed(&mut homelander, "high", 10.0);
+}
+
+#[test]
+fn set_fan_speed_invalid_name() {
+    let mut homelander = setup_homelander();
+
+    assert_value_out_of_range(
+        &mut homelander,
+        CommandType::SetFanSpeed {
+            fan_speed: Some("turbo".to_string()),
+            fan_speed_percent: None,
+        },
+    );
+    assert_fan_speed(&mut homelander, "low", 10.0);
+}
+
+#[test]
+fn set_fan_speed_percent() {
+    let mut homelander = setup_homelander();
+
+    let payload = common::execute(
+        &mut homelander,
+        &["00"],
+        CommandType::SetFanSpeed {
+            fan_speed: None,
+            fan_speed_percent: Some(100.0),
+        },
+    );
+    assert_eq!(payload.commands[0].status, CommandStatus::Success);
+    assert_fan_speed(&mut homelander, "low", 100.0);
+
+    for percent in [-1.0, 100.5] {
+        assert_value_out_of_range(
+            &mut homelander,
+            CommandType::SetFanSpeed {
+                fan_speed: None,
+                fan_speed_percent: Some(percent),
+            },
+        );
+    }
+    assert_fan_speed(&mut homelander, "low", 100.0);
+}
+
+#[test]
+fn set_fan_speed_name_and_percent() {
+    let mut homelander = setup_homelander();
+
+    let payload = common::execute(
+        &mut homelander,
+        &["00"],
+        CommandType::SetFanSpeed {
+            fan_speed: Some("high".to_string()),
+            fan_speed_percent: Some(50.0),
+        },
+    );
+    assert_eq!(payload.commands[0].status, CommandStatus::Success);
+    assert_fan_speed(&mut homelander, "high", 10.0);
+}
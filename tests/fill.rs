@@ -0,0 +1,141 @@
+mod common;
+
+use homelander::fulfillment::request::execute::CommandType;
+use homelander::fulfillment::response::execute::CommandStatus;
+use homelander::traits::fill::{AvailableFillLevels, Fill, FillLevel, LevelValue};
+use homelander::traits::{CombinedDeviceError, Language};
+use homelander::{Device, DeviceType, Homelander};
+use serde_json::json;
+
+#[derive(Debug, Default, PartialEq)]
+struct Bathtub {
+    level: Option<String>,
+    percent: Option<f32>,
+}
+
+google_home_device!(Bathtub, "Bathtub");
+
+impl Fill for Bathtub {
+    fn get_available_fill_levels(&self) -> Result<AvailableFillLevels, CombinedDeviceError> {
+        Ok(AvailableFillLevels::new(
+            vec![FillLevel::new(
+                "half".to_string(),
+                vec![LevelValue::new(vec!["half".to_string()], Language::English)],
+            )],
+            true,
+            true,
+        ))
+    }
+
+    fn is_filled(&self) -> Result<bool, CombinedDeviceError> {
+        Ok(self.level.is_some() || self.percent.is_some())
+    }
+
+    fn get_current_fill_level(&self) -> Result<Option<String>, CombinedDeviceError> {
+        Ok(self.level.clone())
+    }
+
+    fn get_current_fill_percent(&self) -> Result<Option<f32>, CombinedDeviceError> {
+        Ok(self.percent)
+    }
+
+    fn fill(&mut self, fill: bool) -> Result<(), CombinedDeviceError> {
+        if fill {
+            self.percent = Some(100.0);
+        } else {
+            self.level = None;
+            self.percent = None;
+        }
+        Ok(())
+    }
+
+    fn fill_to_level(&mut self, level: String) -> Result<(), CombinedDeviceError> {
+        self.level = Some(level);
+        Ok(())
+    }
+
+    fn fill_to_percent(&mut self, percent: f32) -> Result<(), CombinedDeviceError> {
+        self.percent = Some(percent);
+        Ok(())
+    }
+}
+
+#[test]
+fn sync_bathtub() {
+    let mut device = Device::new(Bathtub::default(), DeviceType::Bathtub, "00".to_string());
+    device.set_fill();
+
+    let mut homelander = Homelander::new("01".to_string());
+    homelander.add_device(device);
+
+    let payload = common::sync(&mut homelander);
+    let device = serde_json::to_value(&payload.devices[0]).unwrap();
+
+    assert_eq!(device["traits"], json!(["action.devices.traits.Fill"]));
+    assert_eq!(
+        device["attributes"],
+        json!({
+            "availableFillLevels": {
+                "levels": [{ "level_name": "half", "level_values": [{ "level_synonym": ["half"], "lang": "en" }] }],
+                "ordered": true,
+                "supportsFillPercent": true,
+            },
+        })
+    );
+}
+
+/// Execute Fill on a bathtub in state `bathtub` and return the resulting state
+fn fill(bathtub: Bathtub, fill: bool, fill_level: Option<&str>, fill_percent: Option<f32>) -> Bathtub {
+    let mut device = Device::new(bathtub, DeviceType::Bathtub, "00".to_string());
+    device.set_fill();
+
+    let mut homelander = Homelander::new("01".to_string());
+    homelander.add_device(device);
+
+    let payload = common::execute(
+        &mut homelander,
+        &["00"],
+        CommandType::Fill {
+            fill,
+            fill_level: fill_level.map(str::to_string),
+            fill_percent,
+        },
+    );
+    assert_eq!(payload.commands[0].status, CommandStatus::Success);
+
+    homelander.with_device_mut("00", |bathtub: &mut Bathtub| std::mem::take(bathtub)).unwrap()
+}
+
+#[test]
+fn fill_to_level_and_percent() {
+    let bathtub = fill(Bathtub::default(), true, Some("half"), Some(30.0));
+    assert_eq!(
+        bathtub,
+        Bathtub {
+            level: Some("half".to_string()),
+            percent: None
+        }
+    );
+}
+
+#[test]
+fn fill_to_percent() {
+    let bathtub = fill(Bathtub::default(), true, None, Some(30.0));
+    assert_eq!(
+        bathtub,
+        Bathtub {
+            level: None,
+            percent: Some(30.0)
+        }
+    );
+}
+
+#[test]
+fn drain_ignores_level() {
+    let filled = Bathtub {
+        level: Some("half".to_string()),
+        percent: Some(50.0),
+    };
+    let bathtub = fill(filled, false, Some("half"), Some(30.0));
+    assert_eq!(bathtub, Bathtub::default());
+}
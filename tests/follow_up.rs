@@ -0,0 +1,55 @@
+use homelander::follow_up::{FollowUpNotification, FollowUpResponse};
+use homelander::traits::DeviceError;
+use homelander::{Homelander, Trait};
+use serde_json::json;
+
+#[test]
+fn follow_up_network_speed_test() {
+    let response = FollowUpResponse::success("PLACEHOLDER".to_string())
+        .download_speed_mbps(123.5)
+        .upload_speed_mbps(67.25);
+    let notification = FollowUpNotification::new("23957".to_string(), "01".to_string(), "00".to_string(), Trait::NetworkControl, response);
+
+    assert_eq!(
+        serde_json::to_value(&notification).unwrap(),
+        json!({
+            "requestId": "23957",
+            "agentUserId": "01",
+            "payload": {
+                "devices": {
+                    "notifications": {
+                        "00": {
+                            "NetworkControl": {
+                                "priority": 0,
+                                "followUpResponse": {
+                                    "status": "SUCCESS",
+                                    "followUpToken": "PLACEHOLDER",
+                                    "downloadSpeedMbps": 123.5,
+                                    "uploadSpeedMbps": 67.25
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        })
+    );
+}
+
+#[test]
+fn follow_up_failure() {
+    let homelander = Homelander::new("01".to_string());
+    let response = FollowUpResponse::failure("PLACEHOLDER".to_string(), DeviceError::FunctionNotSupported);
+    let notification = homelander.follow_up_notification("23957".to_string(), "00".to_string(), Trait::LockUnlock, response);
+
+    let notification = serde_json::to_value(&notification).unwrap();
+    assert_eq!(notification["agentUserId"], json!("01"));
+    assert_eq!(
+        notification["payload"]["devices"]["notifications"]["00"]["LockUnlock"]["followUpResponse"],
+        json!({
+            "status": "FAILURE",
+            "followUpToken": "PLACEHOLDER",
+            "errorCode": "functionNotSupported"
+        })
+    );
+}
@@ -0,0 +1,69 @@
+mod common;
+
+use homelander::fulfillment::request::execute::CommandType;
+use homelander::traits::on_off::OnOff;
+use homelander::traits::{ChallengeType, CombinedDeviceError, DeviceError};
+use homelander::{Device, DeviceType, Homelander};
+use serde_json::json;
+
+/// A lamp which fails every call with the error returned by `error`
+#[derive(Debug)]
+struct FaultyLamp {
+    error: fn() -> CombinedDeviceError,
+}
+
+google_home_device!(FaultyLamp, "FaultyLamp");
+
+impl OnOff for FaultyLamp {
+    fn is_command_only(&self) -> Result<Option<bool>, CombinedDeviceError> {
+        Err((self.error)())
+    }
+
+    fn is_query_only(&self) -> Result<Option<bool>, CombinedDeviceError> {
+        Ok(None)
+    }
+
+    fn is_on(&self) -> Result<bool, CombinedDeviceError> {
+        Err((self.error)())
+    }
+
+    fn set_on(&mut self, _on: bool) -> Result<(), CombinedDeviceError> {
+        Err((self.error)())
+    }
+}
+
+fn setup_homelander(error: fn() -> CombinedDeviceError) -> Homelander {
+    let mut device = Device::new(FaultyLamp { error }, DeviceType::Light, "00".to_string());
+    device.set_on_off();
+
+    let mut homelander = Homelander::new("01".to_string());
+    homelander.add_device(device);
+    homelander
+}
+
+/// Assert every intent reports `error_code` for a lamp failing with `error`
+fn assert_error_code(error: fn() -> CombinedDeviceError, error_code: &str) {
+    let mut homelander = setup_homelander(error);
+
+    let payload = serde_json::to_value(common::sync(&mut homelander)).unwrap();
+    assert_eq!(payload["errorCode"], json!(error_code));
+
+    let payload = common::query(&mut homelander, &["00"]);
+    let state = serde_json::to_value(&payload.devices["00"]).unwrap();
+    assert_eq!(state["errorCode"], json!(error_code));
+
+    let payload = common::execute(&mut homelander, &["00"], CommandType::OnOff { on: true });
+    let command = serde_json::to_value(&payload.commands[0]).unwrap();
+    assert_eq!(command["status"], json!("ERROR"));
+    assert_eq!(command["errorCode"], json!(error_code));
+}
+
+#[test]
+fn device_error_in_every_intent() {
+    assert_error_code(|| CombinedDeviceError::DeviceError(DeviceError::ValueOutOfRange), "valueOutOfRange");
+}
+
+#[test]
+fn challenge_needed_in_every_intent() {
+    assert_error_code(|| CombinedDeviceError::ChallengeNeeded(ChallengeType::AckNeeded), "challengeNeeded");
+}
@@ -0,0 +1,99 @@
+mod common;
+
+use homelander::fulfillment::request::execute::CommandType;
+use homelander::fulfillment::response::execute::CommandStatus;
+use homelander::traits::humidity_setting::{HumiditySetPointRange, HumiditySetting};
+use homelander::traits::CombinedDeviceError;
+use homelander::{DeviceBuilder, DeviceType, Homelander, Trait};
+use serde_json::json;
+
+#[derive(Debug)]
+struct Humidifier {
+    setpoint: i32,
+    ambient: i32,
+}
+
+google_home_device!(Humidifier, "Humidifier");
+
+impl HumiditySetting for Humidifier {
+    fn get_humidity_set_point_range_minmax(&self) -> Result<Option<HumiditySetPointRange>, CombinedDeviceError> {
+        Ok(Some(HumiditySetPointRange::new(Some(25), Some(75))))
+    }
+
+    fn get_current_humidity_setpoint_percent(&self) -> Result<i32, CombinedDeviceError> {
+        Ok(self.setpoint)
+    }
+
+    fn get_current_humidity_ambient_percent(&self) -> Result<i32, CombinedDeviceError> {
+        Ok(self.ambient)
+    }
+
+    fn set_humidity(&mut self, humidity: i32) -> Result<(), CombinedDeviceError> {
+        self.setpoint = humidity;
+        Ok(())
+    }
+
+    fn set_humidity_relative_percent(&mut self, percent: i32) -> Result<(), CombinedDeviceError> {
+        self.setpoint += percent;
+        Ok(())
+    }
+
+    fn set_humidity_relative_weight(&mut self, weight: i32) -> Result<(), CombinedDeviceError> {
+        self.setpoint += weight * 5;
+        Ok(())
+    }
+}
+
+fn setup_homelander() -> Homelander {
+    let device = DeviceBuilder::new(Humidifier { setpoint: 40, ambient: 30 }, DeviceType::Humidifier, "00".to_string())
+        .humidity_setting()
+        .build();
+
+    let mut homelander = Homelander::new("01".to_string());
+    homelander.add_device(device);
+    homelander
+}
+
+fn query_humidity(homelander: &mut Homelander) -> serde_json::Value {
+    let payload = common::query(homelander, &["00"]);
+    serde_json::to_value(&payload.devices["00"]).unwrap()
+}
+
+#[test]
+fn sync_humidity_setting() {
+    let mut homelander = setup_homelander();
+    let payload = common::sync(&mut homelander);
+    let device = &payload.devices[0];
+
+    assert_eq!(device.traits, vec![Trait::HumiditySetting]);
+    assert_eq!(serde_json::to_value(&device.attributes).unwrap(), json!({ "humiditySetpointRange": { "minPercent": 25, "maxPercent": 75 } }));
+}
+
+#[test]
+fn query_humidity_setting() {
+    let mut homelander = setup_homelander();
+    let state = query_humidity(&mut homelander);
+
+    assert_eq!(state["humiditySetpointPercent"], json!(40));
+    assert_eq!(state["humidityAmbientPercent"], json!(30));
+}
+
+#[test]
+fn execute_set_humidity() {
+    let mut homelander = setup_homelander();
+
+    let payload = common::execute(&mut homelander, &["00"], CommandType::SetHumidity { humidity: 60 });
+    assert_eq!(payload.commands[0].status, CommandStatus::Success);
+    assert_eq!(query_humidity(&mut homelander)["humiditySetpointPercent"], json!(60));
+
+    let payload = common::execute(
+        &mut homelander,
+        &["00"],
+        CommandType::HumidityRelative {
+            humidity_relative_percent: Some(-10),
+            humidity_relative_weight: None,
+        },
+    );
+    assert_eq!(payload.commands[0].status, CommandStatus::Success);
+    assert_eq!(query_humidity(&mut homelander)["humiditySetpointPercent"], json!(50));
+}
@@ -0,0 +1,88 @@
+mod common;
+
+use homelander::fulfillment::request::execute::CommandType;
+use homelander::fulfillment::response::execute::CommandStatus;
+use homelander::traits::input_selector::{AvailableInput, InputName, InputSelector, InputSelectorError};
+use homelander::traits::Language;
+use homelander::{Device, DeviceType, Homelander};
+use serde_json::json;
+
+const INPUTS: [&str; 2] = ["hdmi_1", "hdmi_2"];
+
+#[derive(Debug)]
+struct Receiver {
+    input: usize,
+}
+
+google_home_device!(Receiver, "Receiver");
+
+impl InputSelector for Receiver {
+    fn get_available_inputs(&self) -> Result<Vec<AvailableInput>, InputSelectorError> {
+        Ok(INPUTS
+            .iter()
+            .enumerate()
+            .map(|(i, key)| AvailableInput::new(key.to_string(), vec![InputName::new(Language::English, vec![format!("HDMI {}", i + 1)])]))
+            .collect())
+    }
+
+    fn has_ordered_inputs(&self) -> Result<Option<bool>, InputSelectorError> {
+        Ok(Some(true))
+    }
+
+    fn get_current_input(&self) -> Result<String, InputSelectorError> {
+        Ok(INPUTS[self.input].to_string())
+    }
+
+    fn set_input(&mut self, input: String) -> Result<(), InputSelectorError> {
+        self.input = INPUTS.iter().position(|key| *key == input).unwrap();
+        Ok(())
+    }
+
+    fn set_next_input(&mut self) -> Result<(), InputSelectorError> {
+        self.input = (self.input + 1) % INPUTS.len();
+        Ok(())
+    }
+
+    fn set_previous_input(&mut self) -> Result<(), InputSelectorError> {
+        self.input = (self.input + INPUTS.len() - 1) % INPUTS.len();
+        Ok(())
+    }
+}
+
+fn setup_homelander() -> Homelander {
+    let mut device = Device::new(Receiver { input: 0 }, DeviceType::AudioVideoReceiver, "00".to_string());
+    device.set_input_selector();
+
+    let mut homelander = Homelander::new("01".to_string());
+    homelander.add_device(device);
+    homelander
+}
+
+#[test]
+fn sync_hdmi_inputs() {
+    let mut homelander = setup_homelander();
+    let payload = common::sync(&mut homelander);
+    let attributes = serde_json::to_value(&payload.devices[0].attributes).unwrap();
+
+    assert_eq!(
+        attributes,
+        json!({
+            "availableInputs": [
+                { "key": "hdmi_1", "names": [{ "lang": "en", "name_synonym": ["HDMI 1"] }] },
+                { "key": "hdmi_2", "names": [{ "lang": "en", "name_synonym": ["HDMI 2"] }] },
+            ],
+            "orderedInputs": true,
+        })
+    );
+}
+
+#[test]
+fn select_next_input() {
+    let mut homelander = setup_homelander();
+    let payload = common::execute(&mut homelander, &["00"], CommandType::NextInput);
+    assert_eq!(payload.commands[0].status, CommandStatus::Success);
+
+    let payload = common::query(&mut homelander, &["00"]);
+    let state = serde_json::to_value(&payload.devices["00"]).unwrap();
+    assert_eq!(state["currentInput"], json!("hdmi_2"));
+}
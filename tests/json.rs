@@ -0,0 +1,209 @@
+mod common;
+
+use homelander::traits::on_off::OnOff;
+use homelander::traits::CombinedDeviceError;
+use homelander::{Device, DeviceType, Homelander};
+use serde_json::{json, Value};
+
+#[derive(Debug)]
+struct Outlet {
+    on: bool,
+}
+
+google_home_device!(Outlet, "Outlet");
+
+impl OnOff for Outlet {
+    fn is_on(&self) -> Result<bool, CombinedDeviceError> {
+        Ok(self.on)
+    }
+
+    fn set_on(&mut self, on: bool) -> Result<(), CombinedDeviceError> {
+        self.on = on;
+        Ok(())
+    }
+}
+
+fn setup_homelander() -> Homelander {
+    let mut device = Device::new(Outlet { on: false }, DeviceType::Outlet, "00".to_string());
+    device.set_on_off();
+
+    let mut homelander = Homelander::new("01".to_string());
+    homelander.add_device(device);
+    homelander
+}
+
+#[test]
+fn sync_json() {
+    let mut homelander = setup_homelander();
+    let body = r#"{
+        "requestId": "ff36a3cc-ec34-11e6-b1a0-64510650abcf",
+        "inputs": [{
+            "intent": "action.devices.SYNC"
+        }]
+    }"#;
+
+    let response = homelander.handle_request_json(body).unwrap();
+    let response: Value = serde_json::from_str(&response).unwrap();
+
+    assert_eq!(response["requestId"], json!("ff36a3cc-ec34-11e6-b1a0-64510650abcf"));
+    assert_eq!(response["payload"]["agentUserId"], json!("01"));
+    assert_eq!(response["payload"]["devices"][0]["id"], json!("00"));
+    assert_eq!(response["payload"]["devices"][0]["traits"], json!(["action.devices.traits.OnOff"]));
+}
+
+#[test]
+fn disconnect_json() {
+    let mut homelander = setup_homelander();
+    let body = r#"{
+        "requestId": "ff36a3cc-ec34-11e6-b1a0-64510650abcf",
+        "inputs": [{
+            "intent": "action.devices.DISCONNECT"
+        }]
+    }"#;
+
+    let response = homelander.handle_request_json(body).unwrap();
+    let response: Value = serde_json::from_str(&response).unwrap();
+    assert_eq!(response["payload"], json!({}));
+}
+
+#[test]
+fn malformed_json() {
+    let mut homelander = setup_homelander();
+
+    assert!(homelander.handle_request_json("{\"requestId\": ").is_err());
+    assert!(homelander.handle_request_json(r#"{"requestId": "1"}"#).is_err());
+    assert!(homelander
+        .handle_request_json(r#"{"requestId": "1", "inputs": [{"intent": "action.devices.UNKNOWN"}]}"#)
+        .is_err());
+}
+
+#[test]
+fn unknown_command_json() {
+    let mut homelander = setup_homelander();
+    let body = r#"{
+        "requestId": "ff36a3cc-ec34-11e6-b1a0-64510650abcf",
+        "inputs": [{
+            "intent": "action.devices.EXECUTE",
+            "payload": {
+                "commands": [{
+                    "devices": [{ "id": "00" }],
+                    "execution": [
+                        { "command": "action.devices.commands.Levitate", "params": { "heightCm": 30 } },
+                        { "command": "action.devices.commands.OnOff", "params": { "on": true } }
+                    ]
+                }]
+            }
+        }]
+    }"#;
+
+    let response = homelander.handle_request_json(body).unwrap();
+    let response: Value = serde_json::from_str(&response).unwrap();
+    let commands = response["payload"]["commands"].as_array().unwrap();
+
+    assert_eq!(commands.len(), 2);
+    assert_eq!(commands[0]["status"], json!("ERROR"));
+    assert_eq!(commands[0]["errorCode"], json!("commandDispatchFailed"));
+    assert_eq!(commands[1]["status"], json!("SUCCESS"));
+}
+
+#[test]
+fn invalid_params_json() {
+    let mut homelander = setup_homelander();
+    let body = r#"{
+        "requestId": "1",
+        "inputs": [{
+            "intent": "action.devices.EXECUTE",
+            "payload": {
+                "commands": [{
+                    "devices": [{ "id": "00" }],
+                    "execution": [{ "command": "action.devices.commands.OnOff", "params": { "on": "yes" } }]
+                }]
+            }
+        }]
+    }"#;
+
+    assert!(homelander.handle_request_json(body).is_err());
+}
+
+#[test]
+fn augmented_execute_json() {
+    let mut homelander = setup_homelander();
+    let body = r#"{
+        "requestId": "1",
+        "inputs": [{
+            "intent": "action.devices.EXECUTE",
+            "payload": {
+                "structureData": { "structureId": "home" },
+                "commands": [{
+                    "devices": [{ "id": "00", "customData": null, "deviceTags": ["kitchen"] }],
+                    "execution": [{
+                        "command": "action.devices.commands.OnOff",
+                        "params": { "on": true, "transitionMs": 500 },
+                        "reason": "voice"
+                    }]
+                }]
+            }
+        }]
+    }"#;
+
+    let response = homelander.handle_request_json(body).unwrap();
+    let response: Value = serde_json::from_str(&response).unwrap();
+    assert_eq!(response["payload"]["commands"][0]["status"], json!("SUCCESS"));
+}
+
+#[test]
+fn empty_payload_json() {
+    let mut homelander = setup_homelander();
+
+    let body = r#"{ "requestId": "1", "inputs": [{ "intent": "action.devices.SYNC", "payload": {} }] }"#;
+    let response: Value = serde_json::from_str(&homelander.handle_request_json(body).unwrap()).unwrap();
+    assert_eq!(response["payload"]["devices"][0]["id"], json!("00"));
+
+    let body = r#"{
+        "requestId": "1",
+        "inputs": [{
+            "intent": "action.devices.EXECUTE",
+            "payload": {
+                "commands": [{
+                    "devices": [{ "id": "00" }],
+                    "execution": [{ "command": "action.devices.commands.Reboot", "params": {} }]
+                }]
+            }
+        }]
+    }"#;
+    let response: Value = serde_json::from_str(&homelander.handle_request_json(body).unwrap()).unwrap();
+    assert_eq!(response["payload"]["commands"][0]["errorCode"], json!("functionNotSupported"));
+}
+
+#[test]
+fn debug_json() {
+    let mut device = Device::new(Outlet { on: true }, DeviceType::Outlet, "kitchenOutlet".to_string());
+    device.set_on_off();
+
+    let mut homelander = Homelander::new("01".to_string());
+    homelander.add_device(device);
+
+    let response = homelander.handle_request(common::sync_request());
+
+    let wire = serde_json::to_value(&response).unwrap();
+    assert_eq!(wire["requestId"], json!("sync"));
+    assert_eq!(wire["payload"]["agentUserId"], json!("01"));
+    assert_eq!(wire["payload"]["devices"][0]["willReportState"], json!(false));
+    assert_eq!(wire["payload"]["devices"][0]["deviceInfo"]["hwVersion"], json!("0.1.0"));
+
+    let debug: Value = serde_json::from_str(&response.to_debug_json().unwrap()).unwrap();
+    assert_eq!(debug["request_id"], json!("sync"));
+    assert_eq!(debug["payload"]["agent_user_id"], json!("01"));
+    assert_eq!(debug["payload"]["devices"][0]["will_report_state"], json!(false));
+    assert_eq!(debug["payload"]["devices"][0]["device_info"]["hw_version"], json!("0.1.0"));
+    assert_eq!(debug["payload"]["devices"][0]["attributes"], json!({}));
+
+    // Values are left intact
+    assert_eq!(debug["payload"]["devices"][0]["id"], json!("kitchenOutlet"));
+    assert_eq!(debug["payload"]["devices"][0]["traits"], wire["payload"]["devices"][0]["traits"]);
+
+    // As are device IDs used as keys
+    let response = homelander.handle_request(common::query_request(&["kitchenOutlet"]));
+    let debug: Value = serde_json::from_str(&response.to_debug_json().unwrap()).unwrap();
+    assert_eq!(debug["payload"]["devices"]["kitchenOutlet"], json!({ "on": true, "online": true, "status": "SUCCESS" }));
+}
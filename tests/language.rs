@@ -0,0 +1,50 @@
+use homelander::traits::{Language, Synonym, UnknownLanguage};
+use serde_json::json;
+use std::str::FromStr;
+
+#[test]
+fn language_round_trip() {
+    for (language, code) in [
+        (Language::EnglishUnitedStates, "en-US"),
+        (Language::EnglishUnitedKingdom, "en-GB"),
+        (Language::SpanishLatinAmerica, "es-419"),
+        (Language::ChineseChina, "zh-CN"),
+        (Language::PortuguesePortugal, "pt-PT"),
+        (Language::Polish, "pl"),
+        (Language::Russian, "ru"),
+        (Language::Turkish, "tr"),
+        (Language::Vietnamese, "vi"),
+        (Language::English, "en"),
+    ] {
+        assert_eq!(serde_json::to_value(&language).unwrap(), json!(code));
+        assert_eq!(serde_json::from_value::<Language>(json!(code)).unwrap(), language);
+        assert_eq!(Language::from_str(code), Ok(language.clone()));
+        assert_eq!(language.to_string(), code);
+    }
+}
+
+#[test]
+fn language_unknown() {
+    assert_eq!("xx".parse::<Language>(), Err(UnknownLanguage("xx".to_string())));
+    assert!(serde_json::from_value::<Language>(json!("xx")).is_err());
+}
+
+#[test]
+fn synonyms_in_multiple_languages() {
+    let english = Synonym::new(vec!["rice".to_string(), "white rice".to_string()], Language::English);
+    let synonyms = vec![
+        english.clone(),
+        Synonym::new(vec!["rijst".to_string()], Language::Dutch),
+        Synonym::new(vec!["Reis".to_string()], Language::German),
+    ];
+
+    assert_eq!(synonyms[0], english);
+    assert_eq!(
+        serde_json::to_value(&synonyms).unwrap(),
+        json!([
+            { "synonym": ["rice", "white rice"], "lang": "en" },
+            { "synonym": ["rijst"], "lang": "nl" },
+            { "synonym": ["Reis"], "lang": "de" },
+        ])
+    );
+}
@@ -0,0 +1,224 @@
+mod common;
+
+use homelander::fulfillment::request::execute::CommandType;
+use homelander::fulfillment::response::execute::CommandStatus;
+use homelander::fulfillment::response::ResponsePayload;
+use homelander::traits::brightness::Brightness;
+use homelander::traits::modes::{AvailableMode, Modes, NameValue, Setting, SettingValue};
+use homelander::traits::{CombinedDeviceError, DeviceInfo, DeviceName, GoogleHomeDevice, Language};
+use homelander::{Device, DeviceType, Homelander};
+use serde_json::json;
+use std::collections::HashMap;
+
+#[derive(Debug)]
+struct Lamp;
+
+impl GoogleHomeDevice for Lamp {
+    fn get_device_info(&self) -> DeviceInfo {
+        DeviceInfo {
+            manufacturer: "Array21 Development".to_string(),
+            model: "Lamp".to_string(),
+            hw: "0.1.0".to_string(),
+            sw: "0.1.0".to_string(),
+        }
+    }
+
+    fn will_report_state(&self) -> bool {
+        false
+    }
+
+    fn get_device_name(&self) -> DeviceName {
+        DeviceName {
+            name: "Lamp".to_string(),
+            nicknames: Vec::new(),
+            default_names: Vec::new(),
+        }
+    }
+
+    fn is_online(&self) -> bool {
+        true
+    }
+
+    fn get_localized_error(&self, error_code: &str, language: &Language) -> Option<String> {
+        match (error_code, language) {
+            ("valueOutOfRange", Language::English) => Some("The lamp can't be set to that brightness".to_string()),
+            ("valueOutOfRange", Language::German) => Some("Die Lampe kann nicht auf diese Helligkeit eingestellt werden".to_string()),
+            _ => None,
+        }
+    }
+
+    fn disconnect(&mut self) {}
+}
+
+impl Brightness for Lamp {
+    fn is_command_only_brightness(&self) -> Result<bool, CombinedDeviceError> {
+        Ok(false)
+    }
+
+    fn get_brightness(&self) -> Result<i32, CombinedDeviceError> {
+        Ok(50)
+    }
+
+    fn set_brightness_absolute(&mut self, _brightness: i32) -> Result<(), CombinedDeviceError> {
+        Ok(())
+    }
+
+    fn set_brightness_relative_percent(&mut self, _brightness: i32) -> Result<(), CombinedDeviceError> {
+        Ok(())
+    }
+
+    fn set_brightness_relative_weight(&mut self, _weight: i32) -> Result<(), CombinedDeviceError> {
+        Ok(())
+    }
+}
+
+fn setup_homelander() -> Homelander {
+    let mut device = Device::new(Lamp, DeviceType::Light, "00".to_string());
+    device.set_brightness();
+
+    let mut homelander = Homelander::new("01".to_string());
+    homelander.add_device(device);
+    homelander
+}
+
+/// Set a brightness which is out of range, localizing the error in `locale`
+fn execute_out_of_range(locale: Option<Language>) -> serde_json::Value {
+    let mut homelander = setup_homelander();
+    homelander.set_locale(locale);
+
+    match homelander
+        .handle_request(common::execute_request(&["00"], CommandType::BrightnessAbsolute { brightness: 150 }))
+        .payload
+    {
+        ResponsePayload::Execute(payload) => {
+            assert_eq!(payload.commands[0].status, CommandStatus::Error);
+            serde_json::to_value(&payload.commands[0]).unwrap()
+        }
+        _ => panic!("Expected an EXECUTE payload"),
+    }
+}
+
+#[test]
+fn localized_error() {
+    let command = execute_out_of_range(Some(Language::English));
+    assert_eq!(command["errorCode"], json!("valueOutOfRange"));
+    assert_eq!(command["errorCodeString"], json!("The lamp can't be set to that brightness"));
+
+    let command = execute_out_of_range(Some(Language::German));
+    assert_eq!(command["errorCode"], json!("valueOutOfRange"));
+    assert_eq!(command["errorCodeString"], json!("Die Lampe kann nicht auf diese Helligkeit eingestellt werden"));
+}
+
+#[test]
+fn localized_error_json() {
+    let mut homelander = setup_homelander();
+    homelander.set_locale(Some(Language::German));
+
+    let body = r#"{
+        "requestId": "ff36a3cc-ec34-11e6-b1a0-64510650abcf",
+        "inputs": [{
+            "intent": "action.devices.EXECUTE",
+            "payload": {
+                "commands": [{
+                    "devices": [{ "id": "00" }],
+                    "execution": [{ "command": "action.devices.commands.BrightnessAbsolute", "params": { "brightness": 150 } }]
+                }]
+            }
+        }]
+    }"#;
+
+    let response = homelander.handle_request_json(body).unwrap();
+    let response = serde_json::from_str::<serde_json::Value>(&response).unwrap();
+    assert_eq!(response["payload"]["commands"][0]["errorCodeString"], json!("Die Lampe kann nicht auf diese Helligkeit eingestellt werden"));
+}
+
+#[test]
+fn no_locale() {
+    let command = execute_out_of_range(None);
+    assert_eq!(command["errorCode"], json!("valueOutOfRange"));
+    assert!(command.get("errorCodeString").is_none());
+}
+
+#[derive(Debug)]
+struct Washer;
+
+google_home_device!(Washer, "Washer");
+
+fn setting(name: &str, english: &str, dutch: &str) -> Setting {
+    Setting {
+        setting_name: name.to_string(),
+        setting_values: vec![
+            SettingValue {
+                setting_synonym: vec![english.to_string()],
+                lang: Language::English,
+            },
+            SettingValue {
+                setting_synonym: vec![dutch.to_string()],
+                lang: Language::Dutch,
+            },
+        ],
+    }
+}
+
+impl Modes for Washer {
+    fn get_available_modes(&self) -> Result<Vec<AvailableMode>, CombinedDeviceError> {
+        Ok(vec![AvailableMode {
+            name: "load".to_string(),
+            name_values: vec![
+                NameValue {
+                    name_synonym: vec!["load".to_string(), "size".to_string()],
+                    lang: Language::English,
+                },
+                NameValue {
+                    name_synonym: vec!["lading".to_string()],
+                    lang: Language::Dutch,
+                },
+            ],
+            settings: vec![setting("small", "small", "klein"), setting("large", "large", "groot")],
+            ordered: true,
+        }])
+    }
+
+    fn get_current_mode_settings(&self) -> Result<HashMap<String, String>, CombinedDeviceError> {
+        Ok(HashMap::from([("load".to_string(), "small".to_string())]))
+    }
+
+    fn update_mode(&self, _mode_name: String, _setting_name: String) -> Result<(), CombinedDeviceError> {
+        Ok(())
+    }
+}
+
+/// SYNC a washer with modes, only including synonyms in `locale`
+fn sync_modes(locale: Option<Language>) -> serde_json::Value {
+    let mut device = Device::new(Washer, DeviceType::Washer, "00".to_string());
+    device.set_modes();
+
+    let mut homelander = Homelander::new("01".to_string());
+    homelander.add_device(device);
+    homelander.set_locale(locale);
+
+    match homelander.handle_request(common::sync_request()).payload {
+        ResponsePayload::Sync(payload) => serde_json::to_value(&payload.devices[0].attributes).unwrap()["availableModes"].take(),
+        _ => panic!("Expected a SYNC payload"),
+    }
+}
+
+#[test]
+fn sync_localized_synonyms() {
+    let modes = sync_modes(Some(Language::Dutch));
+    assert_eq!(modes[0]["name_values"], json!([{ "name_synonym": ["lading"], "lang": "nl" }]));
+    assert_eq!(modes[0]["settings"][0]["setting_values"], json!([{ "setting_synonym": ["klein"], "lang": "nl" }]));
+    assert_eq!(modes[0]["settings"][1]["setting_values"], json!([{ "setting_synonym": ["groot"], "lang": "nl" }]));
+}
+
+#[test]
+fn sync_localized_synonyms_fallback() {
+    // There are no French synonyms, so the English ones are used
+    let modes = sync_modes(Some(Language::French));
+    assert_eq!(modes[0]["name_values"], json!([{ "name_synonym": ["load", "size"], "lang": "en" }]));
+
+    // Without a locale, all synonyms are included
+    let modes = sync_modes(None);
+    assert_eq!(modes[0]["name_values"].as_array().unwrap().len(), 2);
+    assert_eq!(modes[0]["settings"][0]["setting_values"].as_array().unwrap().len(), 2);
+}
@@ -0,0 +1,57 @@
+mod common;
+
+use homelander::fulfillment::request::execute::CommandType;
+use homelander::fulfillment::response::execute::CommandStatus;
+use homelander::traits::locator::Locator;
+use homelander::traits::{CombinedDeviceError, Language};
+use homelander::{Device, DeviceType, Homelander};
+use serde_json::json;
+
+#[derive(Debug)]
+struct Vacuum {
+    located: Option<(bool, Language)>,
+}
+
+google_home_device!(Vacuum, "Vacuum");
+
+impl Locator for Vacuum {
+    fn locate(&mut self, silence: bool, lang: Language) -> Result<(), CombinedDeviceError> {
+        self.located = Some((silence, lang));
+        Ok(())
+    }
+}
+
+fn setup_homelander() -> Homelander {
+    let mut device = Device::new(Vacuum { located: None }, DeviceType::Vacuum, "00".to_string());
+    device.set_locator();
+
+    let mut homelander = Homelander::new("01".to_string());
+    homelander.add_device(device);
+    homelander
+}
+
+#[test]
+fn locate_silence_in_french() {
+    let mut homelander = setup_homelander();
+    let payload = common::execute(
+        &mut homelander,
+        &["00"],
+        CommandType::Locate {
+            silence: true,
+            lang: Language::French,
+        },
+    );
+
+    assert_eq!(payload.commands[0].status, CommandStatus::Success);
+    assert_eq!(homelander.with_device_mut("00", |vacuum: &mut Vacuum| vacuum.located.clone()), Some(Some((true, Language::French))));
+}
+
+#[test]
+fn sync_locator() {
+    let mut homelander = setup_homelander();
+    let payload = common::sync(&mut homelander);
+    let device = serde_json::to_value(&payload.devices[0]).unwrap();
+
+    assert_eq!(device["traits"], json!(["action.devices.traits.Locator"]));
+    assert_eq!(device["attributes"], json!({}));
+}
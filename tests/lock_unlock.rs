@@ -0,0 +1,155 @@
+mod common;
+
+use homelander::traits::lock_unlock::{LockUnlock, LockUnlockError};
+use homelander::traits::{Challenge, ChallengeType, CombinedDeviceError, DeviceInfo, DeviceName, GoogleHomeDevice};
+use homelander::{Device, DeviceType, Homelander};
+use serde_json::{json, Value};
+
+/// A lock which asks for a PIN to unlock, and for an acknowledgement to lock
+#[derive(Debug)]
+struct FrontDoor {
+    locked: bool,
+    challenge: Option<Challenge>,
+}
+
+impl GoogleHomeDevice for FrontDoor {
+    fn get_device_info(&self) -> DeviceInfo {
+        DeviceInfo {
+            manufacturer: "Array21 Development".to_string(),
+            model: "FrontDoor".to_string(),
+            hw: "0.1.0".to_string(),
+            sw: "0.1.0".to_string(),
+        }
+    }
+
+    fn will_report_state(&self) -> bool {
+        false
+    }
+
+    fn get_device_name(&self) -> DeviceName {
+        DeviceName {
+            name: "FrontDoor".to_string(),
+            nicknames: Vec::new(),
+            default_names: Vec::new(),
+        }
+    }
+
+    fn is_online(&self) -> bool {
+        true
+    }
+
+    fn set_challenge(&mut self, challenge: Option<Challenge>) {
+        self.challenge = challenge;
+    }
+
+    fn disconnect(&mut self) {}
+}
+
+impl LockUnlock for FrontDoor {
+    fn is_locked(&self) -> Result<bool, CombinedDeviceError> {
+        Ok(self.locked)
+    }
+
+    fn is_jammed(&self) -> Result<bool, CombinedDeviceError> {
+        Ok(false)
+    }
+
+    fn set_locked(&mut self, lock: bool) -> Result<(), LockUnlockError> {
+        let challenge = self.challenge.as_ref();
+        if lock {
+            if challenge.and_then(|c| c.ack) != Some(true) {
+                return Err(LockUnlockError::Other(CombinedDeviceError::ChallengeNeeded(ChallengeType::AckNeeded)));
+            }
+        } else {
+            match challenge.and_then(|c| c.pin.as_deref()) {
+                None => return Err(LockUnlockError::Other(CombinedDeviceError::ChallengeNeeded(ChallengeType::PinNeeded))),
+                Some(pin) if pin != "1234" => {
+                    return Err(LockUnlockError::Other(CombinedDeviceError::ChallengeNeeded(ChallengeType::ChallengeFailedPinNeeded)))
+                }
+                Some(_) => {}
+            }
+        }
+
+        self.locked = lock;
+        Ok(())
+    }
+}
+
+fn setup_homelander() -> Homelander {
+    let mut device = Device::new(FrontDoor { locked: true, challenge: None }, DeviceType::Lock, "00".to_string());
+    device.set_lock_unlock();
+
+    let mut homelander = Homelander::new("01".to_string());
+    homelander.add_device(device);
+    homelander
+}
+
+/// Execute LockUnlock and return the single command in the response
+fn lock_unlock(homelander: &mut Homelander, lock: bool, challenge: Option<Value>) -> Value {
+    let mut execution = json!({
+        "command": "action.devices.commands.LockUnlock",
+        "params": { "lock": lock, "followUpToken": "token" }
+    });
+    if let Some(challenge) = challenge {
+        execution["challenge"] = challenge;
+    }
+
+    let body = json!({
+        "requestId": "02",
+        "inputs": [{
+            "intent": "action.devices.EXECUTE",
+            "payload": {
+                "commands": [{
+                    "devices": [{ "id": "00" }],
+                    "execution": [execution]
+                }]
+            }
+        }]
+    });
+
+    let response: Value = serde_json::from_str(&homelander.handle_request_json(&body.to_string()).unwrap()).unwrap();
+    response["payload"]["commands"][0].clone()
+}
+
+fn is_locked(homelander: &mut Homelander) -> bool {
+    let payload = common::query(homelander, &["00"]);
+    serde_json::to_value(&payload.devices["00"]).unwrap()["isLocked"].as_bool().unwrap()
+}
+
+#[test]
+fn unlock_requires_pin() {
+    let mut homelander = setup_homelander();
+
+    let command = lock_unlock(&mut homelander, false, None);
+    assert_eq!(command["status"], json!("ERROR"));
+    assert_eq!(command["errorCode"], json!("challengeNeeded"));
+    assert_eq!(command["challengeNeeded"], json!({ "type": "pinNeeded" }));
+    assert!(is_locked(&mut homelander));
+
+    let command = lock_unlock(&mut homelander, false, Some(json!({ "pin": "0000" })));
+    assert_eq!(command["status"], json!("ERROR"));
+    assert_eq!(command["errorCode"], json!("challengeNeeded"));
+    assert_eq!(command["challengeNeeded"], json!({ "type": "challengeFailedPinNeeded" }));
+    assert!(is_locked(&mut homelander));
+
+    let command = lock_unlock(&mut homelander, false, Some(json!({ "pin": "1234" })));
+    assert_eq!(command["status"], json!("SUCCESS"));
+    assert_eq!(command["states"], json!({ "online": true, "isLocked": false }));
+    assert!(command.get("challengeNeeded").is_none());
+    assert!(!is_locked(&mut homelander));
+}
+
+#[test]
+fn lock_requires_ack() {
+    let mut homelander = setup_homelander();
+    lock_unlock(&mut homelander, false, Some(json!({ "pin": "1234" })));
+
+    let command = lock_unlock(&mut homelander, true, None);
+    assert_eq!(command["status"], json!("ERROR"));
+    assert_eq!(command["challengeNeeded"], json!({ "type": "ackNeeded" }));
+    assert!(!is_locked(&mut homelander));
+
+    let command = lock_unlock(&mut homelander, true, Some(json!({ "ack": true })));
+    assert_eq!(command["status"], json!("SUCCESS"));
+    assert!(is_locked(&mut homelander));
+}
@@ -0,0 +1,122 @@
+mod common;
+
+use homelander::fulfillment::request::execute::CommandType;
+use homelander::fulfillment::response::execute::CommandStatus;
+use homelander::metrics::{Intent, MetricsSink};
+use homelander::traits::on_off::OnOff;
+use homelander::traits::{CombinedDeviceError, DeviceError};
+use homelander::{Device, DeviceType, Homelander, Request, Trait};
+use std::sync::{Arc, Mutex};
+
+#[derive(Debug)]
+struct Lamp {
+    on: bool,
+    jammed: bool,
+}
+
+google_home_device!(Lamp, "Lamp");
+
+impl OnOff for Lamp {
+    fn is_on(&self) -> Result<bool, CombinedDeviceError> {
+        Ok(self.on)
+    }
+
+    fn set_on(&mut self, on: bool) -> Result<(), CombinedDeviceError> {
+        if self.jammed {
+            return Err(CombinedDeviceError::DeviceError(DeviceError::ValueOutOfRange));
+        }
+
+        self.on = on;
+        Ok(())
+    }
+}
+
+/// The trait and status of an executed command
+type RecordedCommand = (Option<Trait>, String);
+
+/// Records every intent and command outcome it receives
+#[derive(Debug, Clone, Default)]
+struct RecordingSink {
+    intents: Arc<Mutex<Vec<Intent>>>,
+    commands: Arc<Mutex<Vec<RecordedCommand>>>,
+}
+
+impl MetricsSink for RecordingSink {
+    fn record_intent(&self, intent: Intent) {
+        self.intents.lock().unwrap().push(intent);
+    }
+
+    fn record_command(&self, device_trait: Option<Trait>, status: &CommandStatus) {
+        self.commands.lock().unwrap().push((device_trait, format!("{status:?}")));
+    }
+}
+
+impl RecordingSink {
+    fn intent_count(&self, intent: Intent) -> usize {
+        self.intents.lock().unwrap().iter().filter(|x| **x == intent).count()
+    }
+
+    fn command_count(&self, device_trait: Trait, status: &str) -> usize {
+        self.commands
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(t, s)| t.as_ref() == Some(&device_trait) && s == status)
+            .count()
+    }
+}
+
+fn lamp(id: &str, jammed: bool) -> Device<Lamp> {
+    let mut device = Device::new(Lamp { on: false, jammed }, DeviceType::Light, id.to_string());
+    device.set_on_off();
+    device
+}
+
+#[test]
+fn metrics_after_batch() {
+    let mut homelander = Homelander::new("01".to_string());
+    homelander.add_device(lamp("00", false));
+    homelander.add_device(lamp("01", true));
+
+    let sink = RecordingSink::default();
+    homelander.set_metrics_sink(sink.clone());
+
+    let inputs = [
+        common::sync_request(),
+        common::query_request(&["00", "01"]),
+        common::execute_request(&["00", "01"], CommandType::OnOff { on: true }),
+        common::execute_request(&["00"], CommandType::BrightnessAbsolute { brightness: 50 }),
+    ]
+    .into_iter()
+    .flat_map(|request| request.inputs)
+    .collect();
+
+    homelander.handle_request(Request {
+        request_id: "batch".to_string(),
+        inputs,
+    });
+
+    assert_eq!(sink.intent_count(Intent::Sync), 1);
+    assert_eq!(sink.intent_count(Intent::Query), 1);
+    assert_eq!(sink.intent_count(Intent::Execute), 2);
+    assert_eq!(sink.intent_count(Intent::Disconnect), 0);
+
+    assert_eq!(sink.commands.lock().unwrap().len(), 3);
+    assert_eq!(sink.command_count(Trait::OnOff, "Success"), 1);
+    assert_eq!(sink.command_count(Trait::OnOff, "Error"), 1);
+    assert_eq!(sink.command_count(Trait::Brightness, "Error"), 1);
+}
+
+#[test]
+fn execute_command_records_outcome() {
+    let mut homelander = Homelander::new("01".to_string());
+    homelander.add_device(lamp("00", false));
+
+    let sink = RecordingSink::default();
+    homelander.set_metrics_sink(sink.clone());
+
+    homelander.execute_command("00", CommandType::OnOff { on: true });
+
+    assert_eq!(sink.intents.lock().unwrap().len(), 0);
+    assert_eq!(sink.command_count(Trait::OnOff, "Success"), 1);
+}
@@ -0,0 +1,90 @@
+mod common;
+
+use homelander::traits::modes::{AvailableMode, Modes, NameValue, Setting, SettingValue};
+use homelander::traits::{CombinedDeviceError, Language};
+use homelander::{Device, DeviceType, Homelander, Trait};
+use serde_json::json;
+use std::collections::HashMap;
+
+#[derive(Debug)]
+struct Washer;
+
+google_home_device!(Washer, "Washer");
+
+fn name(synonym: &str) -> Vec<NameValue> {
+    vec![NameValue {
+        name_synonym: vec![synonym.to_string()],
+        lang: Language::English,
+    }]
+}
+
+fn setting(name: &str) -> Setting {
+    Setting {
+        setting_name: name.to_string(),
+        setting_values: vec![SettingValue {
+            setting_synonym: vec![name.to_string()],
+            lang: Language::English,
+        }],
+    }
+}
+
+impl Modes for Washer {
+    fn get_available_modes(&self) -> Result<Vec<AvailableMode>, CombinedDeviceError> {
+        Ok(vec![
+            AvailableMode::new("load".to_string(), name("load size"), vec![setting("small"), setting("large")], true).unwrap(),
+            AvailableMode::new("temperature".to_string(), name("temperature"), vec![setting("cold"), setting("warm"), setting("hot")], true).unwrap(),
+        ])
+    }
+
+    fn get_current_mode_settings(&self) -> Result<HashMap<String, String>, CombinedDeviceError> {
+        Ok(HashMap::from([("load".to_string(), "small".to_string()), ("temperature".to_string(), "cold".to_string())]))
+    }
+
+    fn update_mode(&self, _mode_name: String, _setting_name: String) -> Result<(), CombinedDeviceError> {
+        Ok(())
+    }
+}
+
+#[test]
+fn sync_washer_modes() {
+    let mut device = Device::new(Washer, DeviceType::Washer, "00".to_string());
+    device.set_modes();
+
+    let mut homelander = Homelander::new("01".to_string());
+    homelander.add_device(device);
+
+    let payload = common::sync(&mut homelander);
+    let attributes = serde_json::to_value(&payload.devices[0].attributes).unwrap();
+
+    let settings = |names: &[&str]| -> serde_json::Value {
+        names
+            .iter()
+            .map(|name| json!({ "setting_name": name, "setting_values": [{ "setting_synonym": [name], "lang": "en" }] }))
+            .collect()
+    };
+
+    assert_eq!(
+        attributes["availableModes"],
+        json!([
+            {
+                "name": "load",
+                "name_values": [{ "name_synonym": ["load size"], "lang": "en" }],
+                "settings": settings(&["small", "large"]),
+                "ordered": true,
+            },
+            {
+                "name": "temperature",
+                "name_values": [{ "name_synonym": ["temperature"], "lang": "en" }],
+                "settings": settings(&["cold", "warm", "hot"]),
+                "ordered": true,
+            },
+        ])
+    );
+}
+
+#[test]
+fn mode_requires_two_settings() {
+    let error = AvailableMode::new("load".to_string(), name("load size"), vec![setting("small")], false).unwrap_err();
+    assert_eq!(error.device_trait, Trait::Modes);
+    assert_eq!(error.message, "mode 'load' must have at least 2 settings");
+}
@@ -0,0 +1,265 @@
+mod common;
+
+use homelander::fulfillment::request::execute::CommandType;
+use homelander::fulfillment::response::execute::CommandStatus;
+use homelander::traits::network_control::{
+    DeviceError, DownloadSpeedTestResult, NetworkControl, NetworkControlError, NetworkProfileState, NetworkSettings, SpeedTestStatus, UploadSpeedTestResult,
+};
+use homelander::{Device, DeviceType, Homelander};
+use serde_json::json;
+use std::collections::HashMap;
+
+#[derive(Debug)]
+struct Router {
+    guest_network_enabled: bool,
+    usage_unlimited: bool,
+    speed_test_in_progress: bool,
+}
+
+google_home_device!(Router, "Router");
+
+impl NetworkControl for Router {
+    fn supports_enabling_guest_network(&self) -> Result<Option<bool>, NetworkControlError> {
+        Ok(Some(true))
+    }
+
+    fn supports_disabling_guest_network(&self) -> Result<Option<bool>, NetworkControlError> {
+        Ok(Some(false))
+    }
+
+    fn supports_getting_guest_network_password(&self) -> Result<Option<bool>, NetworkControlError> {
+        Ok(Some(true))
+    }
+
+    fn supports_enabling_network_profile(&self) -> Result<Option<bool>, NetworkControlError> {
+        Ok(Some(true))
+    }
+
+    fn supports_disabling_network_profile(&self) -> Result<Option<bool>, NetworkControlError> {
+        Ok(Some(true))
+    }
+
+    fn supports_network_download_speed_test(&self) -> Result<Option<bool>, NetworkControlError> {
+        Ok(Some(true))
+    }
+
+    fn supports_network_upload_speed_test(&self) -> Result<Option<bool>, NetworkControlError> {
+        Ok(Some(false))
+    }
+
+    fn get_network_profiles(&self) -> Result<Option<Vec<String>>, NetworkControlError> {
+        Ok(Some(vec!["kids".to_string()]))
+    }
+
+    fn is_network_enabled(&self) -> Result<bool, NetworkControlError> {
+        Ok(true)
+    }
+
+    fn get_network_settings(&self) -> Result<NetworkSettings, NetworkControlError> {
+        Ok(NetworkSettings { ssid: "home".to_string() })
+    }
+
+    fn is_guest_network_enabled(&self) -> Result<bool, NetworkControlError> {
+        Ok(self.guest_network_enabled)
+    }
+
+    fn get_guest_network_settings(&self) -> Result<NetworkSettings, NetworkControlError> {
+        Ok(NetworkSettings { ssid: "guest".to_string() })
+    }
+
+    fn get_num_connected_devices(&self) -> Result<i32, NetworkControlError> {
+        Ok(4)
+    }
+
+    fn get_network_usage_mb(&self) -> Result<f32, NetworkControlError> {
+        Ok(150.5)
+    }
+
+    fn get_network_usage_limit_mb(&self) -> Result<f32, NetworkControlError> {
+        Ok(500.0)
+    }
+
+    fn is_network_usage_unlimited(&self) -> Result<bool, NetworkControlError> {
+        Ok(self.usage_unlimited)
+    }
+
+    fn get_last_network_download_speed_test(&self) -> Result<DownloadSpeedTestResult, NetworkControlError> {
+        if self.speed_test_in_progress {
+            return Err(DeviceError::NetworkSpeedTestInProgress.into());
+        }
+
+        Ok(DownloadSpeedTestResult {
+            download_speed_mbps: 100.0,
+            unix_timestamp_sec: 1660000000,
+            status: SpeedTestStatus::Success,
+        })
+    }
+
+    fn get_last_network_upload_speed_test(&self) -> Result<UploadSpeedTestResult, NetworkControlError> {
+        Ok(UploadSpeedTestResult {
+            upload_speed_mbps: 10.0,
+            unix_timestamp_sec: 1660000000,
+            status: SpeedTestStatus::Failure,
+        })
+    }
+
+    fn get_network_profiles_state(&self) -> Result<HashMap<String, NetworkProfileState>, NetworkControlError> {
+        Ok(HashMap::from([("kids".to_string(), NetworkProfileState { enabled: false })]))
+    }
+
+    fn set_guest_network_enabled(&mut self, enable: bool) -> Result<(), NetworkControlError> {
+        self.guest_network_enabled = enable;
+        Ok(())
+    }
+
+    fn set_network_profile_enabled(&mut self, _profile: String, _enable: bool) -> Result<(), NetworkControlError> {
+        Ok(())
+    }
+
+    fn get_guest_network_password(&self) -> Result<String, NetworkControlError> {
+        Ok("hunter2".to_string())
+    }
+
+    fn test_network_speed(&mut self, _download: bool, _upload: bool) -> Result<(), NetworkControlError> {
+        Ok(())
+    }
+}
+
+fn setup_homelander(router: Router) -> Homelander {
+    let mut device = Device::new(router, DeviceType::Router, "00".to_string());
+    device.set_network_control();
+
+    let mut homelander = Homelander::new("01".to_string());
+    homelander.add_device(device);
+    homelander
+}
+
+fn router() -> Router {
+    Router {
+        guest_network_enabled: true,
+        usage_unlimited: false,
+        speed_test_in_progress: false,
+    }
+}
+
+#[test]
+fn sync_network_control() {
+    let mut homelander = setup_homelander(router());
+    let payload = common::sync(&mut homelander);
+    let attributes = serde_json::to_value(&payload.devices[0].attributes).unwrap();
+
+    assert_eq!(attributes["networkProfiles"], json!(["kids"]));
+    assert_eq!(attributes["supportsEnablingGuestNetwork"], json!(true));
+    assert_eq!(attributes["supportsDisablingGuestNetwork"], json!(false));
+    assert_eq!(attributes["supportsGettingGuestNetworkPassword"], json!(true));
+    assert_eq!(attributes["supportsEnablingNetworkProfile"], json!(true));
+    assert_eq!(attributes["supportsDisablingNetworkProfile"], json!(true));
+    assert_eq!(attributes["supportsNetworkDownloadSpeedTest"], json!(true));
+    assert_eq!(attributes["supportsNetworkUploadSpeedTest"], json!(false));
+}
+
+#[test]
+fn query_network_control() {
+    let mut homelander = setup_homelander(router());
+    let payload = common::query(&mut homelander, &["00"]);
+    let state = serde_json::to_value(&payload.devices["00"]).unwrap();
+
+    assert_eq!(state["networkEnabled"], json!(true));
+    assert_eq!(state["networkSettings"], json!({ "ssid": "home" }));
+    assert_eq!(state["guestNetworkEnabled"], json!(true));
+    assert_eq!(state["guestNetworkSettings"], json!({ "ssid": "guest" }));
+    assert_eq!(state["numConnectedDevices"], json!(4));
+    assert_eq!(state["networkUsageMB"], json!(150.5));
+    assert_eq!(state["networkUsageUnlimited"], json!(false));
+    assert_eq!(state["networkProfilesState"], json!({ "kids": { "enabled": false } }));
+}
+
+#[test]
+fn query_network_usage_limit() {
+    let mut homelander = setup_homelander(router());
+    let payload = common::query(&mut homelander, &["00"]);
+    let state = serde_json::to_value(&payload.devices["00"]).unwrap();
+    assert_eq!(state["networkUsageLimitMB"], json!(500.0));
+
+    let mut homelander = setup_homelander(Router {
+        usage_unlimited: true,
+        ..router()
+    });
+    let payload = common::query(&mut homelander, &["00"]);
+    let state = serde_json::to_value(&payload.devices["00"]).unwrap();
+    assert_eq!(state["networkUsageUnlimited"], json!(true));
+    assert!(state.get("networkUsageLimitMB").is_none());
+}
+
+#[test]
+fn query_network_speed_tests() {
+    let mut homelander = setup_homelander(router());
+    let payload = common::query(&mut homelander, &["00"]);
+    let state = serde_json::to_value(&payload.devices["00"]).unwrap();
+
+    assert_eq!(state["lastNetworkDownloadSpeedTest"], json!({ "downloadSpeedMbps": 100.0, "unixTimestampSec": 1660000000, "status": "SUCCESS" }));
+    assert_eq!(state["lastNetworkUploadSpeedTest"], json!({ "uploadSpeedMbps": 10.0, "unixTimestampSec": 1660000000, "status": "FAILURE" }));
+}
+
+#[test]
+fn query_speed_test_in_progress() {
+    let mut homelander = setup_homelander(Router {
+        speed_test_in_progress: true,
+        ..router()
+    });
+    let payload = common::query(&mut homelander, &["00"]);
+    let state = serde_json::to_value(&payload.devices["00"]).unwrap();
+
+    assert_eq!(state, json!({ "online": true, "status": "ERROR", "errorCode": "networkSpeedTestInProgress" }));
+}
+
+#[test]
+fn execute_test_network_speed() {
+    let mut homelander = setup_homelander(router());
+    let payload = common::execute(
+        &mut homelander,
+        &["00"],
+        CommandType::TestNetworkSpeed {
+            test_download_speed: true,
+            test_upload_speed: false,
+            follow_up_token: "token".to_string(),
+        },
+    );
+    assert_eq!(payload.commands[0].status, CommandStatus::Success);
+
+    let states = serde_json::to_value(&payload.commands[0].states).unwrap();
+    assert_eq!(states["lastNetworkDownloadSpeedTest"], json!({ "downloadSpeedMbps": 100.0, "unixTimestampSec": 1660000000, "status": "SUCCESS" }));
+    assert!(states.get("lastNetworkUploadSpeedTest").is_none());
+}
+
+#[test]
+fn execute_test_network_speed_still_running() {
+    let mut homelander = setup_homelander(Router {
+        speed_test_in_progress: true,
+        ..router()
+    });
+    let payload = common::execute(
+        &mut homelander,
+        &["00"],
+        CommandType::TestNetworkSpeed {
+            test_download_speed: true,
+            test_upload_speed: false,
+            follow_up_token: "token".to_string(),
+        },
+    );
+
+    // The test was started, the result not being ready yet is not an error
+    assert_eq!(payload.commands[0].status, CommandStatus::Success);
+    let states = serde_json::to_value(&payload.commands[0].states).unwrap();
+    assert!(states.get("lastNetworkDownloadSpeedTest").is_none());
+}
+
+#[test]
+fn execute_get_guest_network_password() {
+    let mut homelander = setup_homelander(router());
+    let payload = common::execute(&mut homelander, &["00"], CommandType::GetGuestNetworkPassword);
+    assert_eq!(payload.commands[0].status, CommandStatus::Success);
+
+    let states = serde_json::to_value(&payload.commands[0].states).unwrap();
+    assert_eq!(states, json!({ "online": true, "guestNetworkPassword": "hunter2" }));
+}
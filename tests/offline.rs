@@ -0,0 +1,63 @@
+mod common;
+
+use homelander::traits::on_off::OnOff;
+use homelander::traits::{CombinedDeviceError, DeviceInfo, DeviceName, GoogleHomeDevice};
+use homelander::{Device, DeviceType, Homelander};
+use serde_json::json;
+
+/// A lamp which lost its connection. Talking to it will never work
+#[derive(Debug)]
+struct UnreachableLamp;
+
+impl GoogleHomeDevice for UnreachableLamp {
+    fn get_device_info(&self) -> DeviceInfo {
+        DeviceInfo {
+            manufacturer: "Array21 Development".to_string(),
+            model: "UnreachableLamp".to_string(),
+            hw: "0.1.0".to_string(),
+            sw: "0.1.0".to_string(),
+        }
+    }
+
+    fn will_report_state(&self) -> bool {
+        false
+    }
+
+    fn get_device_name(&self) -> DeviceName {
+        DeviceName {
+            name: "UnreachableLamp".to_string(),
+            nicknames: Vec::new(),
+            default_names: Vec::new(),
+        }
+    }
+
+    fn is_online(&self) -> bool {
+        false
+    }
+
+    fn disconnect(&mut self) {}
+}
+
+impl OnOff for UnreachableLamp {
+    fn is_on(&self) -> Result<bool, CombinedDeviceError> {
+        panic!("The state of an offline device should not be queried")
+    }
+
+    fn set_on(&mut self, _on: bool) -> Result<(), CombinedDeviceError> {
+        panic!("An offline device should not be controlled")
+    }
+}
+
+#[test]
+fn query_offline_device() {
+    let mut device = Device::new(UnreachableLamp, DeviceType::Light, "00".to_string());
+    device.set_on_off();
+
+    let mut homelander = Homelander::new("01".to_string());
+    homelander.add_device(device);
+
+    let payload = common::query(&mut homelander, &["00"]);
+
+    let state = serde_json::to_value(&payload.devices["00"]).unwrap();
+    assert_eq!(state, json!({ "online": false, "status": "OFFLINE" }));
+}
@@ -0,0 +1,200 @@
+mod common;
+
+use homelander::fulfillment::request::execute::CommandType;
+use homelander::fulfillment::response::execute::CommandStatus;
+use homelander::traits::on_off::OnOff;
+use homelander::traits::sensor_state::{CurrentSensorState, DescriptiveCapabilities, SensorState, SupportedSensorState};
+use homelander::traits::CombinedDeviceError;
+use homelander::{Device, DeviceType, Homelander};
+use serde_json::{json, Value};
+
+#[derive(Debug)]
+struct Switch {
+    on: bool,
+}
+
+google_home_device!(Switch, "Switch");
+
+impl OnOff for Switch {
+    fn is_on(&self) -> Result<bool, CombinedDeviceError> {
+        Ok(self.on)
+    }
+
+    fn set_on(&mut self, on: bool) -> Result<(), CombinedDeviceError> {
+        self.on = on;
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+struct SmokeDetector;
+
+google_home_device!(SmokeDetector, "SmokeDetector");
+
+impl SensorState for SmokeDetector {
+    fn get_supported_sensor_states(&self) -> Result<Vec<SupportedSensorState>, CombinedDeviceError> {
+        Ok(vec![SupportedSensorState {
+            name: "SmokeLevel".to_string(),
+            descriptive_capabilities: Some(DescriptiveCapabilities {
+                available_states: vec!["smoke detected".to_string(), "no smoke detected".to_string()],
+            }),
+            numeric_capabilities: None,
+        }])
+    }
+
+    fn get_current_sensor_states(&self) -> Result<Vec<CurrentSensorState>, CombinedDeviceError> {
+        Ok(vec![CurrentSensorState {
+            name: "SmokeLevel".to_string(),
+            current_sensor_state: Some("no smoke detected".to_string()),
+            raw_value: None,
+        }])
+    }
+}
+
+/// Reports whether a machine it is attached to is running, but can't control it
+#[derive(Debug)]
+struct PowerSensor {
+    on: bool,
+}
+
+google_home_device!(PowerSensor, "PowerSensor");
+
+impl OnOff for PowerSensor {
+    fn is_query_only(&self) -> Result<Option<bool>, CombinedDeviceError> {
+        Ok(Some(true))
+    }
+
+    fn is_on(&self) -> Result<bool, CombinedDeviceError> {
+        Ok(self.on)
+    }
+
+    fn set_on(&mut self, on: bool) -> Result<(), CombinedDeviceError> {
+        self.on = on;
+        Ok(())
+    }
+}
+
+/// Sends infrared signals to a device, but has no way of knowing whether the device is on
+#[derive(Debug, Default)]
+struct InfraredSwitch {
+    signals: Vec<bool>,
+}
+
+google_home_device!(InfraredSwitch, "InfraredSwitch");
+
+impl OnOff for InfraredSwitch {
+    fn is_command_only(&self) -> Result<Option<bool>, CombinedDeviceError> {
+        Ok(Some(true))
+    }
+
+    fn is_on(&self) -> Result<bool, CombinedDeviceError> {
+        unimplemented!("An infrared switch can't be queried")
+    }
+
+    fn set_on(&mut self, on: bool) -> Result<(), CombinedDeviceError> {
+        self.signals.push(on);
+        Ok(())
+    }
+}
+
+fn query_state(homelander: &mut Homelander, id: &str) -> Value {
+    let payload = common::query(homelander, &[id]);
+    serde_json::to_value(&payload.devices[id]).unwrap()
+}
+
+#[test]
+fn query_switch_reflects_state() {
+    let mut device = Device::new(Switch { on: true }, DeviceType::Switch, "00".to_string());
+    device.set_on_off();
+
+    let mut homelander = Homelander::new("01".to_string());
+    homelander.add_device(device);
+
+    assert_eq!(query_state(&mut homelander, "00")["on"], json!(true));
+}
+
+#[test]
+fn query_light_off() {
+    let mut device = Device::new(Switch { on: false }, DeviceType::Light, "00".to_string());
+    device.set_on_off();
+
+    let mut homelander = Homelander::new("01".to_string());
+    homelander.add_device(device);
+
+    assert_eq!(query_state(&mut homelander, "00"), json!({ "on": false, "online": true, "status": "SUCCESS" }));
+}
+
+#[test]
+fn query_sensor_without_on() {
+    let mut device = Device::new(SmokeDetector, DeviceType::SmokeDetector, "00".to_string());
+    device.set_sensor_state();
+
+    let mut homelander = Homelander::new("01".to_string());
+    homelander.add_device(device);
+
+    let state = query_state(&mut homelander, "00");
+    assert!(state.get("on").is_none());
+    assert_eq!(state["online"], json!(true));
+}
+
+#[test]
+fn execute_on_off_reports_state() {
+    let mut device = Device::new(Switch { on: false }, DeviceType::Switch, "00".to_string());
+    device.set_on_off();
+
+    let mut homelander = Homelander::new("01".to_string());
+    homelander.add_device(device);
+
+    let payload = common::execute(&mut homelander, &["00"], CommandType::OnOff { on: true });
+    assert_eq!(payload.commands[0].status, CommandStatus::Success);
+    assert_eq!(serde_json::to_value(&payload.commands[0].states).unwrap(), json!({ "online": true, "on": true }));
+}
+
+#[test]
+fn execute_on_off_query_only() {
+    let mut device = Device::new(PowerSensor { on: false }, DeviceType::Switch, "00".to_string());
+    device.set_on_off();
+
+    let mut homelander = Homelander::new("01".to_string());
+    homelander.add_device(device);
+
+    let payload = common::execute(&mut homelander, &["00"], CommandType::OnOff { on: true });
+    assert_eq!(payload.commands[0].status, CommandStatus::Error);
+    assert_eq!(serde_json::to_value(&payload.commands[0].error_code).unwrap(), json!("notSupported"));
+
+    // The device can still be queried
+    assert_eq!(query_state(&mut homelander, "00")["on"], json!(false));
+}
+
+#[test]
+fn query_on_off_command_only() {
+    let mut device = Device::new(InfraredSwitch::default(), DeviceType::Switch, "00".to_string());
+    device.set_on_off();
+
+    let mut homelander = Homelander::new("01".to_string());
+    homelander.add_device(device);
+
+    assert_eq!(query_state(&mut homelander, "00"), json!({ "online": true, "status": "ERROR", "errorCode": "notSupported" }));
+
+    let payload = common::execute(&mut homelander, &["00"], CommandType::OnOff { on: true });
+    assert_eq!(payload.commands[0].status, CommandStatus::Success);
+    assert!(payload.commands[0].states.is_none());
+    assert_eq!(homelander.with_device_mut("00", |switch: &mut InfraredSwitch| switch.signals.clone()), Some(vec![true]));
+}
+
+#[test]
+fn execute_command_turns_on() {
+    let mut device = Device::new(Switch { on: false }, DeviceType::Switch, "00".to_string());
+    device.set_on_off();
+
+    let mut homelander = Homelander::new("01".to_string());
+    homelander.add_device(device);
+
+    let command = homelander.execute_command("00", CommandType::OnOff { on: true }).unwrap();
+    assert_eq!(command.ids, vec!["00".to_string()]);
+    assert_eq!(command.status, CommandStatus::Success);
+    assert_eq!(serde_json::to_value(&command.states).unwrap(), json!({ "online": true, "on": true }));
+    assert_eq!(query_state(&mut homelander, "00")["on"], json!(true));
+
+    assert!(homelander.execute_command("10", CommandType::OnOff { on: true }).is_none());
+}
@@ -0,0 +1,193 @@
+mod common;
+
+use homelander::fulfillment::request::execute::CommandType;
+use homelander::fulfillment::response::execute::CommandStatus;
+use homelander::traits::open_close::{OpenClose, OpenCloseError, OpenDirection, OpenState};
+use homelander::{Device, DeviceType, Homelander};
+use serde_json::json;
+
+#[derive(Debug)]
+struct Blind {
+    open_percent: f32,
+    discrete: bool,
+}
+
+google_home_device!(Blind, "Blind");
+
+impl OpenClose for Blind {
+    fn is_discrete_only_open_close(&self) -> Result<Option<bool>, OpenCloseError> {
+        Ok(Some(self.discrete))
+    }
+
+    fn get_open_percent(&self) -> Result<Option<f32>, OpenCloseError> {
+        Ok(Some(self.open_percent))
+    }
+
+    fn get_open_state(&self) -> Result<Option<Vec<OpenState>>, OpenCloseError> {
+        Ok(None)
+    }
+
+    fn set_open(&mut self, percent: f32, _direction: Option<OpenDirection>) -> Result<(), OpenCloseError> {
+        self.open_percent = percent;
+        Ok(())
+    }
+
+    fn set_open_relative(&mut self, relative_percent: f32, _direction: Option<OpenDirection>) -> Result<(), OpenCloseError> {
+        self.open_percent = (self.open_percent + relative_percent).clamp(0.0, 100.0);
+        Ok(())
+    }
+}
+
+/// A window which opens to the left and to the right independently
+#[derive(Debug)]
+struct Window {
+    left: f32,
+    right: f32,
+}
+
+google_home_device!(Window, "Window");
+
+impl OpenClose for Window {
+    fn get_supported_opening_directions(&self) -> Result<Option<Vec<OpenDirection>>, OpenCloseError> {
+        Ok(Some(vec![OpenDirection::Left, OpenDirection::Right]))
+    }
+
+    fn get_open_percent(&self) -> Result<Option<f32>, OpenCloseError> {
+        Ok(None)
+    }
+
+    fn get_open_state(&self) -> Result<Option<Vec<OpenState>>, OpenCloseError> {
+        Ok(Some(vec![
+            OpenState {
+                open_percent: self.left,
+                open_direction: OpenDirection::Left,
+            },
+            OpenState {
+                open_percent: self.right,
+                open_direction: OpenDirection::Right,
+            },
+        ]))
+    }
+
+    fn set_open(&mut self, percent: f32, direction: Option<OpenDirection>) -> Result<(), OpenCloseError> {
+        match direction {
+            Some(OpenDirection::Left) => self.left = percent,
+            Some(OpenDirection::Right) => self.right = percent,
+            _ => {
+                self.left = percent;
+                self.right = percent;
+            }
+        }
+        Ok(())
+    }
+
+    fn set_open_relative(&mut self, _relative_percent: f32, _direction: Option<OpenDirection>) -> Result<(), OpenCloseError> {
+        Ok(())
+    }
+}
+
+fn setup_homelander(blind: Blind) -> Homelander {
+    let mut device = Device::new(blind, DeviceType::Blinds, "00".to_string());
+    device.set_open_close();
+
+    let mut homelander = Homelander::new("01".to_string());
+    homelander.add_device(device);
+    homelander
+}
+
+#[test]
+fn open_blind_halfway() {
+    let mut homelander = setup_homelander(Blind {
+        open_percent: 0.0,
+        discrete: false,
+    });
+    let payload = common::execute(
+        &mut homelander,
+        &["00"],
+        CommandType::OpenClose {
+            open_percent: 50.0,
+            open_direction: None,
+        },
+    );
+
+    assert_eq!(payload.commands[0].status, CommandStatus::Success);
+    assert_eq!(serde_json::to_value(&payload.commands[0].states).unwrap(), json!({ "online": true, "openPercent": 50.0 }));
+}
+
+#[test]
+fn open_blind_relative() {
+    let mut homelander = setup_homelander(Blind {
+        open_percent: 80.0,
+        discrete: false,
+    });
+    let payload = common::execute(
+        &mut homelander,
+        &["00"],
+        CommandType::OpenCloseRelative {
+            open_relative_percent: 30.0,
+            open_direction: None,
+        },
+    );
+
+    assert_eq!(payload.commands[0].status, CommandStatus::Success);
+    assert_eq!(serde_json::to_value(&payload.commands[0].states).unwrap(), json!({ "online": true, "openPercent": 100.0 }));
+}
+
+#[test]
+fn discrete_blind_rejects_partial_open() {
+    let mut homelander = setup_homelander(Blind {
+        open_percent: 0.0,
+        discrete: true,
+    });
+    let payload = common::execute(
+        &mut homelander,
+        &["00"],
+        CommandType::OpenClose {
+            open_percent: 50.0,
+            open_direction: None,
+        },
+    );
+    assert_eq!(payload.commands[0].status, CommandStatus::Error);
+    assert_eq!(serde_json::to_value(&payload.commands[0].error_code).unwrap(), json!("valueOutOfRange"));
+
+    let payload = common::execute(
+        &mut homelander,
+        &["00"],
+        CommandType::OpenClose {
+            open_percent: 100.0,
+            open_direction: None,
+        },
+    );
+    assert_eq!(payload.commands[0].status, CommandStatus::Success);
+    assert_eq!(serde_json::to_value(&payload.commands[0].states).unwrap(), json!({ "online": true, "openPercent": 100.0 }));
+}
+
+#[test]
+fn open_window_in_direction() {
+    let mut device = Device::new(Window { left: 0.0, right: 0.0 }, DeviceType::Window, "00".to_string());
+    device.set_open_close();
+
+    let mut homelander = Homelander::new("01".to_string());
+    homelander.add_device(device);
+
+    let payload = common::execute(
+        &mut homelander,
+        &["00"],
+        CommandType::OpenClose {
+            open_percent: 25.0,
+            open_direction: Some(OpenDirection::Left),
+        },
+    );
+
+    assert_eq!(payload.commands[0].status, CommandStatus::Success);
+    assert_eq!(
+        serde_json::to_value(&payload.commands[0].states).unwrap(),
+        json!({
+            "online": true,
+            "openState": [
+                { "openPercent": 25.0, "openDirection": "LEFT" },
+                { "openPercent": 0.0, "openDirection": "RIGHT" }
+            ]
+        })
+    );
+}
@@ -0,0 +1,98 @@
+#![cfg(feature = "parallel")]
+
+mod common;
+
+use homelander::fulfillment::request::execute::CommandType;
+use homelander::fulfillment::response::execute::CommandStatus;
+use homelander::traits::on_off::OnOff;
+use homelander::traits::CombinedDeviceError;
+use homelander::{Device, DeviceType, Homelander};
+use std::thread;
+use std::time::{Duration, Instant};
+
+const DEVICES: usize = 5;
+const DELAY: Duration = Duration::from_millis(100);
+
+/// A light behind a slow bridge, every call takes [DELAY]
+#[derive(Debug)]
+struct SlowLight {
+    on: bool,
+}
+
+google_home_device!(SlowLight, "SlowLight");
+
+impl OnOff for SlowLight {
+    fn is_on(&self) -> Result<bool, CombinedDeviceError> {
+        thread::sleep(DELAY);
+        Ok(self.on)
+    }
+
+    fn set_on(&mut self, on: bool) -> Result<(), CombinedDeviceError> {
+        thread::sleep(DELAY);
+        self.on = on;
+        Ok(())
+    }
+}
+
+fn setup_homelander() -> (Homelander, Vec<String>) {
+    let mut homelander = Homelander::new("01".to_string());
+    // The devices only wait, so don't depend on the number of cores
+    homelander.set_max_threads(DEVICES);
+    let ids = (0..DEVICES).map(|i| format!("{i:02}")).collect::<Vec<_>>();
+    for id in &ids {
+        let mut device = Device::new(SlowLight { on: false }, DeviceType::Light, id.clone());
+        device.set_on_off();
+        homelander.add_device(device);
+    }
+
+    (homelander, ids)
+}
+
+/// Sequentially, all devices would take `DEVICES * DELAY`
+fn assert_concurrent(elapsed: Duration) {
+    assert!(elapsed < DELAY * (DEVICES as u32 - 1), "Handling {DEVICES} devices took {elapsed:?}");
+}
+
+#[test]
+fn query_devices_concurrently() {
+    let (mut homelander, ids) = setup_homelander();
+    let ids = ids.iter().map(String::as_str).collect::<Vec<_>>();
+
+    let start = Instant::now();
+    let payload = common::query(&mut homelander, &ids);
+    assert_concurrent(start.elapsed());
+
+    assert_eq!(payload.devices.len(), DEVICES);
+}
+
+#[test]
+fn execute_devices_concurrently() {
+    let (mut homelander, ids) = setup_homelander();
+    let ids = ids.iter().map(String::as_str).collect::<Vec<_>>();
+
+    let start = Instant::now();
+    let payload = common::execute(&mut homelander, &ids, CommandType::OnOff { on: true });
+    assert_concurrent(start.elapsed());
+
+    // Results are grouped in the order of the request
+    assert_eq!(payload.commands.len(), 1);
+    assert_eq!(payload.commands[0].status, CommandStatus::Success);
+    assert_eq!(payload.commands[0].ids, ids);
+}
+
+#[test]
+fn execute_devices_on_limited_threads() {
+    let (mut homelander, ids) = setup_homelander();
+    homelander.set_max_threads(2);
+    let ids = ids.iter().map(String::as_str).collect::<Vec<_>>();
+
+    // 3 devices on one thread, 2 on the other
+    let start = Instant::now();
+    let payload = common::execute(&mut homelander, &ids, CommandType::OnOff { on: true });
+    let elapsed = start.elapsed();
+    assert!(elapsed >= DELAY * 2 * 3, "Handling {DEVICES} devices on 2 threads took {elapsed:?}");
+    assert!(elapsed < DELAY * 2 * DEVICES as u32, "Handling {DEVICES} devices on 2 threads took {elapsed:?}");
+
+    assert_eq!(payload.commands.len(), 1);
+    assert_eq!(payload.commands[0].ids, ids);
+}
@@ -0,0 +1,95 @@
+mod common;
+
+use homelander::traits::brightness::Brightness;
+use homelander::traits::on_off::OnOff;
+use homelander::traits::CombinedDeviceError;
+use homelander::{Device, DeviceType, Homelander};
+use serde_json::json;
+
+#[derive(Debug)]
+struct DimmableLight {
+    on: bool,
+    brightness: i32,
+}
+
+google_home_device!(DimmableLight, "DimmableLight");
+
+impl OnOff for DimmableLight {
+    fn is_on(&self) -> Result<bool, CombinedDeviceError> {
+        Ok(self.on)
+    }
+
+    fn set_on(&mut self, on: bool) -> Result<(), CombinedDeviceError> {
+        self.on = on;
+        Ok(())
+    }
+}
+
+impl Brightness for DimmableLight {
+    fn is_command_only_brightness(&self) -> Result<bool, CombinedDeviceError> {
+        Ok(false)
+    }
+
+    fn get_brightness(&self) -> Result<i32, CombinedDeviceError> {
+        Ok(self.brightness)
+    }
+
+    fn set_brightness_absolute(&mut self, brightness: i32) -> Result<(), CombinedDeviceError> {
+        self.brightness = brightness;
+        Ok(())
+    }
+
+    fn set_brightness_relative_percent(&mut self, _brightness: i32) -> Result<(), CombinedDeviceError> {
+        Ok(())
+    }
+
+    fn set_brightness_relative_weight(&mut self, _weight: i32) -> Result<(), CombinedDeviceError> {
+        Ok(())
+    }
+}
+
+fn setup_homelander() -> Homelander {
+    let mut homelander = Homelander::new("01".to_string());
+    for (id, on, brightness) in [("00", true, 60), ("10", false, 0)] {
+        let mut device = Device::new(DimmableLight { on, brightness }, DeviceType::Light, id.to_string());
+        device.set_on_off();
+        device.set_brightness();
+        homelander.add_device(device);
+    }
+
+    homelander
+}
+
+#[test]
+fn query_device_states() {
+    let mut homelander = setup_homelander();
+
+    let payload = common::query(&mut homelander, &["00", "10"]);
+    assert!(payload.error_code.is_none());
+    assert_eq!(
+        serde_json::to_value(&payload.devices).unwrap(),
+        json!({
+            "00": { "on": true, "brightness": 60, "online": true, "status": "SUCCESS" },
+            "10": { "on": false, "brightness": 0, "online": true, "status": "SUCCESS" },
+        })
+    );
+}
+
+#[test]
+fn query_unknown_device() {
+    let mut homelander = setup_homelander();
+
+    let payload = common::query(&mut homelander, &["00", "20"]);
+    assert_eq!(payload.devices.len(), 2);
+    assert_eq!(serde_json::to_value(&payload.devices["00"]).unwrap(), json!({ "on": true, "brightness": 60, "online": true, "status": "SUCCESS" }));
+    assert_eq!(serde_json::to_value(&payload.devices["20"]).unwrap(), json!({ "online": false, "status": "ERROR", "errorCode": "deviceNotFound" }));
+}
+
+#[test]
+fn query_single_device() {
+    let homelander = setup_homelander();
+
+    let state = homelander.query_device("10").unwrap();
+    assert_eq!(serde_json::to_value(&state).unwrap(), json!({ "on": false, "brightness": 0, "online": true, "status": "SUCCESS" }));
+    assert!(homelander.query_device("20").is_none());
+}
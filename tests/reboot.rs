@@ -0,0 +1,47 @@
+mod common;
+
+use homelander::fulfillment::request::execute::CommandType;
+use homelander::fulfillment::response::execute::CommandStatus;
+use homelander::traits::reboot::Reboot;
+use homelander::traits::CombinedDeviceError;
+use homelander::{Device, DeviceType, Homelander, Trait};
+
+#[derive(Debug)]
+struct Router {
+    reboots: u32,
+}
+
+google_home_device!(Router, "Router");
+
+impl Reboot for Router {
+    fn reboot(&mut self) -> Result<(), CombinedDeviceError> {
+        self.reboots += 1;
+        Ok(())
+    }
+}
+
+fn setup_homelander() -> Homelander {
+    let mut device = Device::new(Router { reboots: 0 }, DeviceType::Router, "00".to_string());
+    device.set_reboot();
+
+    let mut homelander = Homelander::new("01".to_string());
+    homelander.add_device(device);
+    homelander
+}
+
+#[test]
+fn sync_reboot() {
+    let mut homelander = setup_homelander();
+    let payload = common::sync(&mut homelander);
+
+    assert_eq!(payload.devices[0].traits, vec![Trait::Reboot]);
+}
+
+#[test]
+fn reboot_router() {
+    let mut homelander = setup_homelander();
+    let payload = common::execute(&mut homelander, &["00"], CommandType::Reboot);
+
+    assert_eq!(payload.commands[0].status, CommandStatus::Success);
+    assert_eq!(homelander.with_device_mut("00", |router: &mut Router| router.reboots), Some(1));
+}
@@ -0,0 +1,145 @@
+mod common;
+
+use homelander::traits::brightness::Brightness;
+use homelander::traits::on_off::OnOff;
+use homelander::traits::CombinedDeviceError;
+use homelander::{register_traits, Device, DeviceType, Homelander, Trait};
+use serde_json::json;
+
+#[derive(Debug)]
+struct Lamp {
+    on: bool,
+    brightness: i32,
+}
+
+google_home_device!(Lamp, "Lamp");
+
+impl OnOff for Lamp {
+    fn is_on(&self) -> Result<bool, CombinedDeviceError> {
+        Ok(self.on)
+    }
+
+    fn set_on(&mut self, on: bool) -> Result<(), CombinedDeviceError> {
+        self.on = on;
+        Ok(())
+    }
+}
+
+impl Brightness for Lamp {
+    fn is_command_only_brightness(&self) -> Result<bool, CombinedDeviceError> {
+        Ok(false)
+    }
+
+    fn get_brightness(&self) -> Result<i32, CombinedDeviceError> {
+        Ok(self.brightness)
+    }
+
+    fn set_brightness_absolute(&mut self, brightness: i32) -> Result<(), CombinedDeviceError> {
+        self.brightness = brightness;
+        Ok(())
+    }
+
+    fn set_brightness_relative_percent(&mut self, brightness: i32) -> Result<(), CombinedDeviceError> {
+        self.brightness += brightness;
+        Ok(())
+    }
+
+    fn set_brightness_relative_weight(&mut self, weight: i32) -> Result<(), CombinedDeviceError> {
+        self.brightness += weight * 10;
+        Ok(())
+    }
+}
+
+fn setup_homelander(device: Device<Lamp>) -> Homelander {
+    let mut homelander = Homelander::new("01".to_string());
+    homelander.add_device(device);
+    homelander
+}
+
+fn assert_traits(homelander: &mut Homelander, traits: serde_json::Value, brightness: serde_json::Value) {
+    let payload = common::sync(homelander);
+    let device = serde_json::to_value(&payload.devices[0]).unwrap();
+    assert_eq!(device["traits"], traits);
+
+    let payload = common::query(homelander, &["00"]);
+    let state = serde_json::to_value(&payload.devices["00"]).unwrap();
+    assert_eq!(state.get("brightness").cloned().unwrap_or(json!(null)), brightness);
+}
+
+#[test]
+fn unset_trait() {
+    let mut device = Device::new(Lamp { on: true, brightness: 50 }, DeviceType::Light, "00".to_string());
+    device.set_on_off();
+    device.set_brightness();
+    device.unset_brightness();
+
+    let mut homelander = setup_homelander(device);
+    assert_traits(&mut homelander, json!(["action.devices.traits.OnOff"]), json!(null));
+}
+
+#[test]
+fn toggle_trait() {
+    let mut device = Device::new(Lamp { on: true, brightness: 50 }, DeviceType::Light, "00".to_string());
+    device.set_on_off();
+    device.set_brightness();
+    device.unset_brightness();
+    device.set_brightness();
+
+    let mut homelander = setup_homelander(device);
+    assert_traits(&mut homelander, json!(["action.devices.traits.OnOff", "action.devices.traits.Brightness"]), json!(50));
+}
+
+#[test]
+fn registered_traits() {
+    let mut device = Device::new(Lamp { on: true, brightness: 50 }, DeviceType::Light, "00".to_string());
+    assert!(device.registered_traits().is_empty());
+
+    device.set_on_off();
+    device.set_brightness();
+    assert_eq!(device.registered_traits(), &[Trait::OnOff, Trait::Brightness]);
+
+    device.unset_on_off();
+    assert_eq!(device.registered_traits(), &[Trait::Brightness]);
+}
+
+#[test]
+fn register_traits_from_list() {
+    // E.g. read from a configuration file
+    let traits = vec![Trait::OnOff, Trait::Brightness];
+
+    let mut device = Device::new(Lamp { on: true, brightness: 50 }, DeviceType::Light, "00".to_string());
+    register_traits!(device, &traits).unwrap();
+    assert_eq!(device.registered_traits(), &[Trait::OnOff, Trait::Brightness]);
+
+    let mut homelander = setup_homelander(device);
+    assert_traits(&mut homelander, json!(["action.devices.traits.OnOff", "action.devices.traits.Brightness"]), json!(50));
+}
+
+#[test]
+fn register_unimplemented_trait() {
+    let mut device = Device::new(Lamp { on: true, brightness: 50 }, DeviceType::Light, "00".to_string());
+    let error = register_traits!(device, &[Trait::OnOff, Trait::ColorSetting, Trait::Brightness]).unwrap_err();
+    assert_eq!(error.device_trait, Trait::ColorSetting);
+
+    // Registration stops at the unimplemented trait
+    assert_eq!(device.registered_traits(), &[Trait::OnOff]);
+}
+
+#[test]
+fn register_duplicate_traits() {
+    let mut device = Device::new(Lamp { on: true, brightness: 50 }, DeviceType::Light, "00".to_string());
+    device.set_on_off();
+    register_traits!(device, &[Trait::OnOff, Trait::Brightness, Trait::Brightness]).unwrap();
+    assert_eq!(device.registered_traits(), &[Trait::OnOff, Trait::Brightness]);
+
+    let mut homelander = setup_homelander(device);
+    assert_traits(&mut homelander, json!(["action.devices.traits.OnOff", "action.devices.traits.Brightness"]), json!(50));
+}
+
+#[test]
+fn register_object_detection() {
+    let mut device = Device::new(Lamp { on: true, brightness: 50 }, DeviceType::Light, "00".to_string());
+    let error = register_traits!(device, &[Trait::ObjectDetection]).unwrap_err();
+    assert_eq!(error.device_trait, Trait::ObjectDetection);
+    assert_eq!(error.message, "only used for notifications, it can't be registered");
+}
@@ -0,0 +1,68 @@
+mod common;
+
+use homelander::fulfillment::response::ResponsePayload;
+use homelander::traits::on_off::OnOff;
+use homelander::traits::CombinedDeviceError;
+use homelander::{Device, DeviceType, HomelanderRegistry};
+use serde_json::json;
+
+#[derive(Debug)]
+struct Switch;
+
+google_home_device!(Switch, "Switch");
+
+impl OnOff for Switch {
+    fn is_on(&self) -> Result<bool, CombinedDeviceError> {
+        Ok(true)
+    }
+
+    fn set_on(&mut self, _on: bool) -> Result<(), CombinedDeviceError> {
+        Ok(())
+    }
+}
+
+fn setup_registry() -> HomelanderRegistry {
+    let mut registry = HomelanderRegistry::new();
+    for (user_id, device_id) in [("alice", "00"), ("bob", "01")] {
+        let mut device = Device::new(Switch, DeviceType::Switch, device_id.to_string());
+        device.set_on_off();
+        registry.get_or_create(user_id).add_device(device);
+    }
+
+    registry
+}
+
+/// SYNC the devices of `user_id`, returning the agent user ID and device IDs
+fn sync(registry: &mut HomelanderRegistry, user_id: &str) -> (String, Vec<String>) {
+    match registry.handle_request(user_id, common::sync_request()).payload {
+        ResponsePayload::Sync(payload) => (payload.agent_user_id, payload.devices.into_iter().map(|device| device.id).collect()),
+        _ => panic!("Expected a SYNC payload"),
+    }
+}
+
+#[test]
+fn routes_requests_per_user() {
+    let mut registry = setup_registry();
+    assert_eq!(sync(&mut registry, "alice"), ("alice".to_string(), vec!["00".to_string()]));
+    assert_eq!(sync(&mut registry, "bob"), ("bob".to_string(), vec!["01".to_string()]));
+
+    match registry.handle_request("alice", common::query_request(&["01"])).payload {
+        // The devices of other users are unknown
+        ResponsePayload::Query(payload) => {
+            assert_eq!(serde_json::to_value(&payload.devices["01"]).unwrap(), json!({ "online": false, "status": "ERROR", "errorCode": "deviceNotFound" }))
+        }
+        _ => panic!("Expected a QUERY payload"),
+    }
+}
+
+#[test]
+fn unknown_and_removed_users() {
+    let mut registry = setup_registry();
+    assert!(registry.get("carol").is_none());
+    assert_eq!(sync(&mut registry, "carol"), ("carol".to_string(), Vec::new()));
+    assert!(registry.get("carol").is_some());
+
+    assert!(registry.remove("alice").is_some());
+    assert!(registry.get("alice").is_none());
+    assert_eq!(sync(&mut registry, "alice"), ("alice".to_string(), Vec::new()));
+}
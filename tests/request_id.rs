@@ -0,0 +1,141 @@
+mod common;
+
+use homelander::fulfillment::request::execute::CommandType;
+use homelander::traits::on_off::OnOff;
+use homelander::traits::{CombinedDeviceError, DeviceInfo, DeviceName, GoogleHomeDevice};
+use homelander::{Device, DeviceType, Homelander};
+use serde_json::{json, Value};
+use std::sync::{Arc, Mutex};
+
+/// Keeps the request ID of every command it executed in a handle shared with the test,
+/// and ignores commands from a request it already handled. Commands without a request ID are never ignored
+#[derive(Debug)]
+struct Light {
+    toggles: u32,
+    request_id: Option<String>,
+    handled_request_id: Option<String>,
+    executed_in: Arc<Mutex<Vec<String>>>,
+}
+
+impl GoogleHomeDevice for Light {
+    fn get_device_info(&self) -> DeviceInfo {
+        DeviceInfo {
+            manufacturer: "Array21 Development".to_string(),
+            model: "Light".to_string(),
+            hw: "0.1.0".to_string(),
+            sw: "0.1.0".to_string(),
+        }
+    }
+
+    fn will_report_state(&self) -> bool {
+        false
+    }
+
+    fn get_device_name(&self) -> DeviceName {
+        DeviceName {
+            name: "Light".to_string(),
+            nicknames: Vec::new(),
+            default_names: Vec::new(),
+        }
+    }
+
+    fn is_online(&self) -> bool {
+        true
+    }
+
+    fn set_request_id(&mut self, request_id: Option<&str>) {
+        self.request_id = request_id.map(str::to_string);
+    }
+
+    fn disconnect(&mut self) {}
+}
+
+impl OnOff for Light {
+    fn is_on(&self) -> Result<bool, CombinedDeviceError> {
+        Ok(self.toggles % 2 == 1)
+    }
+
+    fn set_on(&mut self, _on: bool) -> Result<(), CombinedDeviceError> {
+        self.executed_in.lock().unwrap().push(self.request_id.clone().unwrap_or_default());
+        if self.request_id.is_none() || self.handled_request_id != self.request_id {
+            self.handled_request_id = self.request_id.clone();
+            self.toggles += 1;
+        }
+        Ok(())
+    }
+}
+
+fn setup_homelander(executed_in: &Arc<Mutex<Vec<String>>>) -> Homelander {
+    let mut homelander = Homelander::new("01".to_string());
+    for id in ["00", "10"] {
+        let light = Light {
+            toggles: 0,
+            request_id: None,
+            handled_request_id: None,
+            executed_in: executed_in.clone(),
+        };
+        let mut device = Device::new(light, DeviceType::Light, id.to_string());
+        device.set_on_off();
+        homelander.add_device(device);
+    }
+
+    homelander
+}
+
+fn execute(homelander: &mut Homelander, request_id: &str) {
+    let mut request = common::execute_request(&["00", "10"], CommandType::OnOff { on: true });
+    request.request_id = request_id.to_string();
+    homelander.handle_request(request);
+}
+
+#[test]
+fn request_id_visible_to_all_devices() {
+    let executed_in = Arc::new(Mutex::new(Vec::new()));
+    let mut homelander = setup_homelander(&executed_in);
+
+    execute(&mut homelander, "request-1");
+    assert_eq!(*executed_in.lock().unwrap(), vec!["request-1", "request-1"]);
+}
+
+#[test]
+fn retried_request_is_deduplicated() {
+    let executed_in = Arc::new(Mutex::new(Vec::new()));
+    let mut homelander = setup_homelander(&executed_in);
+
+    execute(&mut homelander, "request-1");
+    execute(&mut homelander, "request-1");
+    assert_eq!(homelander.with_device_mut("00", |light: &mut Light| light.toggles), Some(1));
+
+    execute(&mut homelander, "request-2");
+    assert_eq!(homelander.with_device_mut("00", |light: &mut Light| light.toggles), Some(2));
+}
+
+#[test]
+fn local_command_has_no_request_id() {
+    let executed_in = Arc::new(Mutex::new(Vec::new()));
+    let mut homelander = setup_homelander(&executed_in);
+
+    execute(&mut homelander, "request-1");
+    homelander.execute_command("00", CommandType::OnOff { on: true });
+
+    // The local command is not mistaken for a retry of the EXECUTE from Google
+    assert_eq!(*executed_in.lock().unwrap(), vec!["request-1", "request-1", ""]);
+    assert_eq!(homelander.with_device_mut("00", |light: &mut Light| light.toggles), Some(2));
+}
+
+#[test]
+fn request_id_on_empty_request() {
+    let executed_in = Arc::new(Mutex::new(Vec::new()));
+    let mut homelander = setup_homelander(&executed_in);
+
+    let response = homelander.handle_request_json(r#"{"requestId": "request-1", "inputs": []}"#).unwrap();
+    let response: Value = serde_json::from_str(&response).unwrap();
+    assert_eq!(
+        response,
+        json!({
+            "requestId": "request-1",
+            "payload": { "errorCode": "protocolError", "debugString": "The request has no inputs" }
+        })
+    );
+    assert!(executed_in.lock().unwrap().is_empty());
+}
@@ -0,0 +1,93 @@
+mod common;
+
+use homelander::fulfillment::request::execute::CommandType;
+use homelander::fulfillment::response::execute::CommandStatus;
+use homelander::traits::rotation::{Rotation, RotationDegreeRange};
+use homelander::traits::CombinedDeviceError;
+use homelander::{Device, DeviceType, Homelander};
+use serde_json::json;
+
+#[derive(Debug)]
+struct Slats {
+    degrees: f32,
+    percent: f32,
+}
+
+google_home_device!(Slats, "Slats");
+
+impl Rotation for Slats {
+    fn supports_degrees(&self) -> Result<bool, CombinedDeviceError> {
+        Ok(true)
+    }
+
+    fn supports_percent(&self) -> Result<bool, CombinedDeviceError> {
+        Ok(true)
+    }
+
+    fn get_rotation_degree_range(&self) -> Result<RotationDegreeRange, CombinedDeviceError> {
+        Ok(RotationDegreeRange {
+            rotation_degree_min: 0.0,
+            rotation_degree_max: 180.0,
+        })
+    }
+
+    fn get_rotation_degrees(&self) -> Result<f32, CombinedDeviceError> {
+        Ok(self.degrees)
+    }
+
+    fn get_rotation_percent(&self) -> Result<f32, CombinedDeviceError> {
+        Ok(self.percent)
+    }
+
+    fn set_rotation_degrees(&mut self, degrees: f32) -> Result<(), CombinedDeviceError> {
+        self.degrees = degrees;
+        Ok(())
+    }
+
+    fn set_rotation_percent(&mut self, percent: f32) -> Result<(), CombinedDeviceError> {
+        self.percent = percent;
+        Ok(())
+    }
+}
+
+fn setup_homelander() -> Homelander {
+    let mut device = Device::new(Slats { degrees: 0.0, percent: 0.0 }, DeviceType::Blinds, "00".to_string());
+    device.set_rotation();
+
+    let mut homelander = Homelander::new("01".to_string());
+    homelander.add_device(device);
+    homelander
+}
+
+fn rotate(homelander: &mut Homelander, rotation_degrees: Option<f32>, rotation_percent: Option<f32>) {
+    let payload = common::execute(
+        homelander,
+        &["00"],
+        CommandType::RotationAbsolute {
+            rotation_degrees,
+            rotation_percent,
+        },
+    );
+    assert_eq!(payload.commands[0].status, CommandStatus::Success);
+}
+
+fn assert_rotation(homelander: &mut Homelander, degrees: f32, percent: f32) {
+    let payload = common::query(homelander, &["00"]);
+    let state = serde_json::to_value(&payload.devices["00"]).unwrap();
+    assert_eq!(state["rotationDegrees"], json!(degrees));
+    assert_eq!(state["rotationPercent"], json!(percent));
+}
+
+#[test]
+fn rotate_percent() {
+    let mut homelander = setup_homelander();
+    rotate(&mut homelander, None, Some(40.0));
+    assert_rotation(&mut homelander, 0.0, 40.0);
+}
+
+#[test]
+fn rotate_degrees_and_percent() {
+    let mut homelander = setup_homelander();
+    rotate(&mut homelander, Some(90.0), Some(40.0));
+    assert_rotation(&mut homelander, 90.0, 0.0);
+}
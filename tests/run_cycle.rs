@@ -0,0 +1,60 @@
+mod common;
+
+use homelander::traits::run_cycle::{CurrentRunCycle, RunCycle};
+use homelander::traits::{CombinedDeviceError, Language};
+use homelander::{Device, DeviceType, Homelander};
+use serde_json::json;
+
+#[derive(Debug)]
+struct Washer;
+
+google_home_device!(Washer, "Washer");
+
+impl RunCycle for Washer {
+    fn get_current_run_cycle(&self) -> Result<Vec<CurrentRunCycle>, CombinedDeviceError> {
+        Ok(vec![CurrentRunCycle {
+            current_cycle: "rinse".to_string(),
+            next_cycle: Some("spin".to_string()),
+            lang: Language::English,
+        }])
+    }
+
+    fn get_current_total_remaining_time(&self) -> Result<i32, CombinedDeviceError> {
+        Ok(1212)
+    }
+
+    fn get_current_cycle_remaining_time(&self) -> Result<i32, CombinedDeviceError> {
+        Ok(301)
+    }
+}
+
+fn setup_homelander() -> Homelander {
+    let mut device = Device::new(Washer, DeviceType::Washer, "00".to_string());
+    device.set_run_cycle();
+
+    let mut homelander = Homelander::new("01".to_string());
+    homelander.add_device(device);
+    homelander
+}
+
+#[test]
+fn sync_run_cycle() {
+    let mut homelander = setup_homelander();
+    let payload = common::sync(&mut homelander);
+    let device = serde_json::to_value(&payload.devices[0]).unwrap();
+
+    // RunCycle has no attributes, but must still be listed
+    assert_eq!(device["traits"], json!(["action.devices.traits.RunCycle"]));
+    assert_eq!(device["attributes"], json!({}));
+}
+
+#[test]
+fn query_run_cycle() {
+    let mut homelander = setup_homelander();
+    let payload = common::query(&mut homelander, &["00"]);
+    let state = serde_json::to_value(&payload.devices["00"]).unwrap();
+
+    assert_eq!(state["currentRunCycle"], json!([{ "currentCycle": "rinse", "nextCycle": "spin", "lang": "en" }]));
+    assert_eq!(state["currentTotalRemainingTime"], json!(1212));
+    assert_eq!(state["currentCycleRemainingTime"], json!(301));
+}
@@ -0,0 +1,49 @@
+mod common;
+
+use homelander::fulfillment::request::execute::CommandType;
+use homelander::fulfillment::response::execute::CommandStatus;
+use homelander::traits::scene::Scene;
+use homelander::traits::CombinedDeviceError;
+use homelander::{Device, DeviceType, Homelander};
+use serde_json::json;
+
+/// A scene which can't be undone, e.g. because it doesn't remember the previous state
+#[derive(Debug, Default)]
+struct PartyMode {
+    active: bool,
+}
+
+google_home_device!(PartyMode, "PartyMode");
+
+impl Scene for PartyMode {
+    fn activate(&mut self) -> Result<(), CombinedDeviceError> {
+        self.active = true;
+        Ok(())
+    }
+
+    fn deactivate(&mut self) -> Result<(), CombinedDeviceError> {
+        panic!("A non-reversible scene should not be deactivated")
+    }
+}
+
+fn setup_homelander() -> Homelander {
+    let mut device = Device::new(PartyMode::default(), DeviceType::Scene, "00".to_string());
+    device.set_scene();
+
+    let mut homelander = Homelander::new("01".to_string());
+    homelander.add_device(device);
+    homelander
+}
+
+#[test]
+fn deactivate_non_reversible_scene() {
+    let mut homelander = setup_homelander();
+
+    let payload = common::execute(&mut homelander, &["00"], CommandType::ActivateScene { deactivate: false });
+    assert_eq!(payload.commands[0].status, CommandStatus::Success);
+
+    let payload = common::execute(&mut homelander, &["00"], CommandType::ActivateScene { deactivate: true });
+    assert_eq!(payload.commands[0].status, CommandStatus::Error);
+    assert_eq!(serde_json::to_value(&payload.commands[0].error_code).unwrap(), json!("notSupported"));
+    assert_eq!(homelander.with_device_mut("00", |scene: &mut PartyMode| scene.active), Some(true));
+}
@@ -0,0 +1,63 @@
+mod common;
+
+use homelander::fulfillment::request::execute::CommandType;
+use homelander::fulfillment::response::execute::CommandStatus;
+use homelander::traits::software_update::SoftwareUpdate;
+use homelander::traits::CombinedDeviceError;
+use homelander::{Device, DeviceType, Homelander, Trait};
+use serde_json::json;
+
+#[derive(Debug)]
+struct Router {
+    last_update: i64,
+}
+
+google_home_device!(Router, "Router");
+
+impl SoftwareUpdate for Router {
+    fn get_last_software_update_unix_timestamp_sec(&self) -> Result<i64, CombinedDeviceError> {
+        Ok(self.last_update)
+    }
+
+    fn perform_update(&mut self) -> Result<(), CombinedDeviceError> {
+        self.last_update = 1_700_000_000;
+        Ok(())
+    }
+}
+
+fn setup_homelander() -> Homelander {
+    let mut device = Device::new(Router { last_update: 1_600_000_000 }, DeviceType::Router, "00".to_string());
+    device.set_software_update();
+
+    let mut homelander = Homelander::new("01".to_string());
+    homelander.add_device(device);
+    homelander
+}
+
+#[test]
+fn sync_software_update() {
+    let mut homelander = setup_homelander();
+    let payload = common::sync(&mut homelander);
+
+    assert_eq!(payload.devices[0].traits, vec![Trait::SoftwareUpdate]);
+}
+
+#[test]
+fn query_last_software_update() {
+    let mut homelander = setup_homelander();
+    let payload = common::query(&mut homelander, &["00"]);
+    let state = serde_json::to_value(&payload.devices["00"]).unwrap();
+
+    assert_eq!(state["lastSoftwareUpdateUnixTimestampSec"], json!(1_600_000_000));
+}
+
+#[test]
+fn execute_software_update() {
+    let mut homelander = setup_homelander();
+    let payload = common::execute(&mut homelander, &["00"], CommandType::SoftwareUpdate);
+    assert_eq!(payload.commands[0].status, CommandStatus::Success);
+
+    let payload = common::query(&mut homelander, &["00"]);
+    let state = serde_json::to_value(&payload.devices["00"]).unwrap();
+    assert_eq!(state["lastSoftwareUpdateUnixTimestampSec"], json!(1_700_000_000));
+}
@@ -0,0 +1,65 @@
+mod common;
+
+use homelander::traits::status_report::{CurrentStatusReport, StatusReport};
+use homelander::traits::CombinedDeviceError;
+use homelander::{Device, DeviceType, Homelander};
+use serde_json::json;
+
+#[derive(Debug)]
+struct SecuritySystem;
+
+google_home_device!(SecuritySystem, "SecuritySystem");
+
+impl StatusReport for SecuritySystem {
+    fn get_current_status_report(&self) -> Result<Vec<CurrentStatusReport>, CombinedDeviceError> {
+        Ok(vec![
+            CurrentStatusReport {
+                blocking: false,
+                device_target: "00".to_string(),
+                priority: 0,
+                status_code: Some("lowBattery".to_string()),
+            },
+            CurrentStatusReport {
+                blocking: true,
+                device_target: "10".to_string(),
+                priority: 1,
+                status_code: None,
+            },
+        ])
+    }
+}
+
+fn setup_homelander() -> Homelander {
+    let mut device = Device::new(SecuritySystem, DeviceType::SecuritySystem, "00".to_string());
+    device.set_status_report();
+
+    let mut homelander = Homelander::new("01".to_string());
+    homelander.add_device(device);
+    homelander
+}
+
+#[test]
+fn sync_status_report() {
+    let mut homelander = setup_homelander();
+    let payload = common::sync(&mut homelander);
+    let device = serde_json::to_value(&payload.devices[0]).unwrap();
+
+    // StatusReport has no attributes, but must still be listed
+    assert_eq!(device["traits"], json!(["action.devices.traits.StatusReport"]));
+    assert_eq!(device["attributes"], json!({}));
+}
+
+#[test]
+fn query_status_report() {
+    let mut homelander = setup_homelander();
+    let payload = common::query(&mut homelander, &["00"]);
+    let state = serde_json::to_value(&payload.devices["00"]).unwrap();
+
+    assert_eq!(
+        state["currentStatusReport"],
+        json!([
+            { "blocking": false, "deviceTarget": "00", "priority": 0, "statusCode": "lowBattery" },
+            { "blocking": true, "deviceTarget": "10", "priority": 1 }
+        ])
+    );
+}
@@ -0,0 +1,523 @@
+mod common;
+
+use homelander::fulfillment::response::sync::SyncAttributes;
+use homelander::traits::brightness::Brightness;
+use homelander::traits::color_setting::{Color, ColorCommand, ColorModel, ColorModelSupport, ColorSetting, ColorTemperatureRange};
+use homelander::traits::fan_speed::{AvailableFanSpeeds, FanSpeed, FanSpeedError, FanSpeedItem, FanSpeedValue};
+use homelander::traits::on_off::OnOff;
+use homelander::traits::transport_control::{SupportedCommand, TransportControl};
+use homelander::traits::{CombinedDeviceError, DeviceInfo, DeviceName, GoogleHomeDevice, Language, OtherDeviceId};
+use homelander::{Device, DeviceType, ErrorCode, Homelander};
+use serde_json::json;
+
+#[derive(Debug)]
+struct MediaPlayer;
+
+google_home_device!(MediaPlayer, "MediaPlayer");
+
+impl TransportControl for MediaPlayer {
+    fn get_supported_control_commands(&self) -> Result<Vec<SupportedCommand>, CombinedDeviceError> {
+        Ok(vec![
+            SupportedCommand::Pause,
+            SupportedCommand::Resume,
+            SupportedCommand::SeekRelative,
+            SupportedCommand::SeekToPosition,
+        ])
+    }
+
+    fn media_stop(&mut self) -> Result<(), CombinedDeviceError> {
+        Ok(())
+    }
+
+    fn media_next(&mut self) -> Result<(), CombinedDeviceError> {
+        Ok(())
+    }
+
+    fn media_previous(&mut self) -> Result<(), CombinedDeviceError> {
+        Ok(())
+    }
+
+    fn media_pause(&mut self) -> Result<(), CombinedDeviceError> {
+        Ok(())
+    }
+
+    fn media_resume(&mut self) -> Result<(), CombinedDeviceError> {
+        Ok(())
+    }
+
+    fn media_seek_relative(&mut self, _relative_position_ms: i32) -> Result<(), CombinedDeviceError> {
+        Ok(())
+    }
+
+    fn media_seek_to_position(&mut self, _abs_position_ms: i32) -> Result<(), CombinedDeviceError> {
+        Ok(())
+    }
+
+    fn media_repeat_mode(&mut self, _is_on: bool, _single_mode: bool) -> Result<(), CombinedDeviceError> {
+        Ok(())
+    }
+
+    fn media_shuffle(&mut self) -> Result<(), CombinedDeviceError> {
+        Ok(())
+    }
+
+    fn media_closed_captioning_on(&mut self, _cc_lang: String, _user_query_lang: String) -> Result<(), CombinedDeviceError> {
+        Ok(())
+    }
+
+    fn media_closed_captioning_off(&mut self) -> Result<(), CombinedDeviceError> {
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+struct Lamp;
+
+impl GoogleHomeDevice for Lamp {
+    fn get_device_info(&self) -> DeviceInfo {
+        DeviceInfo {
+            manufacturer: "Array21 Development".to_string(),
+            model: "Lamp".to_string(),
+            hw: "0.1.0".to_string(),
+            sw: "0.1.0".to_string(),
+        }
+    }
+
+    fn get_room_hint(&self) -> Option<String> {
+        Some("Living room".to_string())
+    }
+
+    fn get_structure_hint(&self) -> Option<String> {
+        Some("Cabin".to_string())
+    }
+
+    fn will_report_state(&self) -> bool {
+        false
+    }
+
+    fn get_device_name(&self) -> DeviceName {
+        DeviceName {
+            name: "Lamp".to_string(),
+            nicknames: Vec::new(),
+            default_names: Vec::new(),
+        }
+    }
+
+    fn is_online(&self) -> bool {
+        true
+    }
+
+    fn disconnect(&mut self) {}
+}
+
+impl OnOff for Lamp {
+    fn is_on(&self) -> Result<bool, CombinedDeviceError> {
+        Ok(true)
+    }
+
+    fn set_on(&mut self, _on: bool) -> Result<(), CombinedDeviceError> {
+        Ok(())
+    }
+}
+
+#[test]
+fn sync_transport_control() {
+    let mut device = Device::new(MediaPlayer, DeviceType::Speaker, "00".to_string());
+    device.set_transport_control();
+
+    let mut homelander = Homelander::new("01".to_string());
+    homelander.add_device(device);
+
+    let payload = common::sync(&mut homelander);
+    let device = serde_json::to_value(&payload.devices[0]).unwrap();
+
+    assert_eq!(device["traits"], json!(["action.devices.traits.TransportControl"]));
+    assert_eq!(device["attributes"]["transportControlSupportedCommands"], json!(["PAUSE", "RESUME", "SEEK_RELATIVE", "SEEK_TO_POSITION"]));
+}
+
+#[test]
+fn sync_room_and_structure_hint() {
+    let mut homelander = Homelander::new("01".to_string());
+    homelander.add_device(Device::new(Lamp, DeviceType::Light, "00".to_string()));
+    homelander.add_device(Device::new(MediaPlayer, DeviceType::Speaker, "10".to_string()));
+
+    let payload = common::sync(&mut homelander);
+
+    let lamp = serde_json::to_value(&payload.devices[0]).unwrap();
+    assert_eq!(lamp["roomHint"], json!("Living room"));
+    assert_eq!(lamp["structureHint"], json!("Cabin"));
+
+    // Hints are left out entirely if the device doesn't provide them
+    let media_player = serde_json::to_value(&payload.devices[1]).unwrap();
+    assert!(media_player.get("roomHint").is_none());
+    assert!(media_player.get("structureHint").is_none());
+}
+
+#[test]
+fn sync_omits_absent_attributes() {
+    let mut device = Device::new(Lamp, DeviceType::Light, "00".to_string());
+    device.set_on_off();
+
+    let mut homelander = Homelander::new("01".to_string());
+    homelander.add_device(device);
+
+    let payload = common::sync(&mut homelander);
+    let device = serde_json::to_value(&payload.devices[0]).unwrap();
+    assert_eq!(device["attributes"], json!({}));
+
+    let payload = common::query(&mut homelander, &["00"]);
+    let state = serde_json::to_value(&payload.devices["00"]).unwrap();
+    assert_eq!(state, json!({ "on": true, "online": true, "status": "SUCCESS" }));
+}
+
+#[derive(Debug)]
+struct Doorbell;
+
+impl GoogleHomeDevice for Doorbell {
+    fn get_device_info(&self) -> DeviceInfo {
+        DeviceInfo {
+            manufacturer: "Array21 Development".to_string(),
+            model: "Doorbell".to_string(),
+            hw: "0.1.0".to_string(),
+            sw: "0.1.0".to_string(),
+        }
+    }
+
+    fn will_report_state(&self) -> bool {
+        true
+    }
+
+    fn supports_notifications(&self) -> bool {
+        true
+    }
+
+    fn get_other_device_ids(&self) -> Vec<OtherDeviceId> {
+        vec![OtherDeviceId {
+            device_id: "local-doorbell-00".to_string(),
+        }]
+    }
+
+    fn get_device_name(&self) -> DeviceName {
+        DeviceName {
+            name: "Doorbell".to_string(),
+            nicknames: Vec::new(),
+            default_names: Vec::new(),
+        }
+    }
+
+    fn is_online(&self) -> bool {
+        true
+    }
+
+    fn disconnect(&mut self) {}
+}
+
+#[test]
+fn sync_notification_supported_by_agent() {
+    let mut homelander = Homelander::new("01".to_string());
+    homelander.add_device(Device::new(Doorbell, DeviceType::Doorbell, "00".to_string()));
+    homelander.add_device(Device::new(Lamp, DeviceType::Light, "10".to_string()));
+
+    let payload = common::sync(&mut homelander);
+
+    let doorbell = serde_json::to_value(&payload.devices[0]).unwrap();
+    assert_eq!(doorbell["notificationSupportedByAgent"], json!(true));
+
+    // Left out for devices which don't send notifications
+    let lamp = serde_json::to_value(&payload.devices[1]).unwrap();
+    assert!(lamp.get("notificationSupportedByAgent").is_none());
+}
+
+#[test]
+fn sync_other_device_ids() {
+    let mut homelander = Homelander::new("01".to_string());
+    homelander.add_device(Device::new(Doorbell, DeviceType::Doorbell, "00".to_string()));
+    homelander.add_device(Device::new(Lamp, DeviceType::Light, "10".to_string()));
+
+    let payload = common::sync(&mut homelander);
+
+    let doorbell = serde_json::to_value(&payload.devices[0]).unwrap();
+    assert_eq!(doorbell["otherDeviceIds"], json!([{ "deviceId": "local-doorbell-00" }]));
+
+    // Left out for devices which can't be reached locally
+    let lamp = serde_json::to_value(&payload.devices[1]).unwrap();
+    assert!(lamp.get("otherDeviceIds").is_none());
+}
+
+#[test]
+fn sync_attributes_builder() {
+    let attributes = SyncAttributes::default()
+        .with_command_only_on_off(false)
+        .with_transport_control_supported_commands(vec![SupportedCommand::Pause, SupportedCommand::Resume])
+        .with_volume_max_level(100)
+        .with_volume_can_mute_and_unmute(true);
+
+    assert_eq!(
+        serde_json::to_value(&attributes).unwrap(),
+        json!({
+            "commandOnlyOnOff": false,
+            "transportControlSupportedCommands": ["PAUSE", "RESUME"],
+            "volumeMaxLevel": 100,
+            "volumeCanMuteAndUnmute": true,
+        })
+    );
+
+    let mut device = Device::new(MediaPlayer, DeviceType::Speaker, "00".to_string());
+    device.set_transport_control();
+
+    let mut homelander = Homelander::new("01".to_string());
+    homelander.add_device(device);
+
+    let payload = common::sync(&mut homelander);
+    assert_eq!(
+        payload.devices[0].attributes,
+        SyncAttributes::default().with_transport_control_supported_commands(vec![
+            SupportedCommand::Pause,
+            SupportedCommand::Resume,
+            SupportedCommand::SeekRelative,
+            SupportedCommand::SeekToPosition,
+        ])
+    );
+}
+
+/// A lamp whose bridge failed to initialize
+#[derive(Debug)]
+struct BrokenLamp;
+
+impl GoogleHomeDevice for BrokenLamp {
+    fn get_device_info(&self) -> DeviceInfo {
+        DeviceInfo {
+            manufacturer: "Array21 Development".to_string(),
+            model: "Lamp".to_string(),
+            hw: "0.1.0".to_string(),
+            sw: "0.1.0".to_string(),
+        }
+    }
+
+    fn will_report_state(&self) -> bool {
+        false
+    }
+
+    fn get_device_name(&self) -> DeviceName {
+        DeviceName {
+            name: "Broken lamp".to_string(),
+            nicknames: Vec::new(),
+            default_names: Vec::new(),
+        }
+    }
+
+    fn is_online(&self) -> bool {
+        false
+    }
+
+    fn get_sync_error(&self) -> Option<ErrorCode> {
+        Some(ErrorCode::DeviceNotReady)
+    }
+
+    fn disconnect(&mut self) {}
+}
+
+#[test]
+fn sync_overridden_agent_user_id() {
+    let mut homelander = Homelander::new("01".to_string());
+    homelander.add_device(Device::new(Lamp, DeviceType::Light, "00".to_string()));
+
+    let payload = serde_json::to_value(common::sync(&mut homelander)).unwrap();
+    assert_eq!(payload["agentUserId"], json!("01"));
+
+    homelander.set_agent_user_id("tenant-02".to_string());
+    let payload = serde_json::to_value(common::sync(&mut homelander)).unwrap();
+    assert_eq!(payload["agentUserId"], json!("tenant-02"));
+    assert_eq!(payload["devices"][0]["id"], json!("00"));
+}
+
+#[test]
+fn sync_device_error_code() {
+    let mut homelander = Homelander::new("01".to_string());
+    homelander.add_device(Device::new(Lamp, DeviceType::Light, "00".to_string()));
+    homelander.add_device(Device::new(BrokenLamp, DeviceType::Light, "10".to_string()));
+
+    let payload = common::sync(&mut homelander);
+    assert!(payload.error_code.is_none());
+    assert_eq!(payload.devices.len(), 2);
+
+    // Left out for healthy devices
+    let lamp = serde_json::to_value(&payload.devices[0]).unwrap();
+    assert!(lamp.get("errorCode").is_none());
+
+    let broken_lamp = serde_json::to_value(&payload.devices[1]).unwrap();
+    assert_eq!(broken_lamp["id"], json!("10"));
+    assert_eq!(broken_lamp["errorCode"], json!("deviceNotReady"));
+}
+
+/// A ceiling fan with a built-in color light
+#[derive(Debug)]
+struct CeilingFan;
+
+google_home_device!(CeilingFan, "CeilingFan");
+
+impl OnOff for CeilingFan {
+    fn is_on(&self) -> Result<bool, CombinedDeviceError> {
+        Ok(true)
+    }
+
+    fn set_on(&mut self, _on: bool) -> Result<(), CombinedDeviceError> {
+        Ok(())
+    }
+}
+
+impl Brightness for CeilingFan {
+    fn is_command_only_brightness(&self) -> Result<bool, CombinedDeviceError> {
+        Ok(false)
+    }
+
+    fn get_brightness(&self) -> Result<i32, CombinedDeviceError> {
+        Ok(80)
+    }
+
+    fn set_brightness_absolute(&mut self, _brightness: i32) -> Result<(), CombinedDeviceError> {
+        Ok(())
+    }
+
+    fn set_brightness_relative_percent(&mut self, _brightness: i32) -> Result<(), CombinedDeviceError> {
+        Ok(())
+    }
+
+    fn set_brightness_relative_weight(&mut self, _weight: i32) -> Result<(), CombinedDeviceError> {
+        Ok(())
+    }
+}
+
+impl ColorSetting for CeilingFan {
+    fn is_command_only_color_setting(&self) -> Result<bool, CombinedDeviceError> {
+        Ok(false)
+    }
+
+    fn get_color_model_support(&self) -> Result<ColorModelSupport, CombinedDeviceError> {
+        Ok(ColorModelSupport::new(Some(ColorModel::Rgb), Some(ColorTemperatureRange::new(2000, 6500))))
+    }
+
+    fn get_color(&self) -> Result<Color, CombinedDeviceError> {
+        Ok(Color {
+            temperature_k: Some(2700),
+            spectrum_rgb: None,
+            spectrum_hsv: None,
+        })
+    }
+
+    fn set_color(&mut self, _command: ColorCommand) -> Result<(), CombinedDeviceError> {
+        Ok(())
+    }
+}
+
+impl FanSpeed for CeilingFan {
+    fn is_reversable(&self) -> Result<Option<bool>, FanSpeedError> {
+        Ok(Some(true))
+    }
+
+    fn get_available_fan_speeds(&self) -> Result<Option<AvailableFanSpeeds>, FanSpeedError> {
+        Ok(Some(AvailableFanSpeeds {
+            speeds: vec![FanSpeedItem {
+                speed_name: "low".to_string(),
+                speed_values: vec![FanSpeedValue {
+                    speed_synonym: vec!["low".to_string(), "slow".to_string()],
+                    lang: Language::English,
+                }],
+            }],
+            ordered: true,
+        }))
+    }
+
+    fn is_support_fan_speed_percent(&self) -> Result<Option<bool>, FanSpeedError> {
+        Ok(Some(false))
+    }
+
+    fn get_current_fan_speed_setting(&self) -> Result<Option<String>, FanSpeedError> {
+        Ok(Some("low".to_string()))
+    }
+
+    fn get_current_fan_speed_percent(&self) -> Result<Option<f32>, FanSpeedError> {
+        Ok(None)
+    }
+
+    fn set_fan_speed_setting(&self, _name: String) -> Result<(), FanSpeedError> {
+        Ok(())
+    }
+
+    fn set_fan_speed_percent(&self, _percent: f32) -> Result<(), FanSpeedError> {
+        Ok(())
+    }
+
+    fn set_fan_speed_relative_weight(&self, _weight: i32) -> Result<(), FanSpeedError> {
+        Ok(())
+    }
+
+    fn set_fan_speed_relative_percent(&self, _percent: f32) -> Result<(), FanSpeedError> {
+        Ok(())
+    }
+
+    fn set_fan_reverse(&self) -> Result<(), FanSpeedError> {
+        Ok(())
+    }
+}
+
+#[test]
+fn sync_multi_trait_device() {
+    let mut device = Device::new(CeilingFan, DeviceType::Fan, "00".to_string());
+    device.set_on_off();
+    device.set_brightness();
+    device.set_color_setting();
+    device.set_fan_speed();
+
+    let mut homelander = Homelander::new("01".to_string());
+    homelander.add_device(device);
+
+    let payload = common::sync(&mut homelander);
+    let device = serde_json::to_value(&payload.devices[0]).unwrap();
+
+    assert_eq!(
+        device["traits"],
+        json!([
+            "action.devices.traits.OnOff",
+            "action.devices.traits.Brightness",
+            "action.devices.traits.ColorSetting",
+            "action.devices.traits.FanSpeed",
+        ])
+    );
+    assert_eq!(
+        device["attributes"],
+        json!({
+            "commandOnlyBrightness": false,
+            "commandOnlyColorSetting": false,
+            "colorModel": "rgb",
+            "colorTemperatureRange": { "temperatureMinK": 2000, "temperatureMaxK": 6500 },
+            "reversible": true,
+            "availableFanSpeeds": {
+                "speeds": [{
+                    "speed_name": "low",
+                    "speed_values": [{ "speed_synonym": ["low", "slow"], "lang": "en" }],
+                }],
+                "ordered": true,
+            },
+            "supportsFanSpeedPercent": false,
+        })
+    );
+}
+
+#[test]
+fn sync_estimated_size() {
+    let mut homelander = Homelander::new("01".to_string());
+    assert_eq!(homelander.sync_estimated_size(), r#"{"agentUserId":"01","devices":[],"errorCode":null,"debugString":null}"#.len());
+
+    let mut device = Device::new(Lamp, DeviceType::Light, "00".to_string());
+    device.set_on_off();
+    homelander.add_device(device);
+
+    let mut device = Device::new(MediaPlayer, DeviceType::Speaker, "10".to_string());
+    device.set_transport_control();
+    homelander.add_device(device);
+
+    let size = homelander.sync_estimated_size();
+    assert_eq!(size, 817);
+    assert_eq!(size, serde_json::to_vec(&common::sync(&mut homelander)).unwrap().len());
+}
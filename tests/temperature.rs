@@ -0,0 +1,45 @@
+use homelander::traits::{Temperature, TemperatureUnit};
+use serde_json::json;
+
+#[test]
+fn temperature_conversions() {
+    assert_eq!(32.0, Temperature::from_celsius(0.0).as_fahrenheit());
+    assert_eq!(212.0, Temperature::from_celsius(100.0).as_fahrenheit());
+    assert_eq!(0.0, Temperature::from_fahrenheit(32.0).as_celsius());
+    assert_eq!(100.0, Temperature::from_fahrenheit(212.0).as_celsius());
+
+    // Both scales meet at -40 degrees
+    let temperature = Temperature::from_celsius(-40.0);
+    assert_eq!(-40.0, temperature.as_fahrenheit());
+    assert_eq!(-40.0, temperature.as_unit(&TemperatureUnit::Celsius));
+    assert_eq!(-40.0, temperature.as_unit(&TemperatureUnit::Fahrenheit));
+}
+
+#[test]
+fn temperature_round_trip() {
+    for fahrenheit in [-459.67_f32, -0.5, 0.0, 50.0, 70.0, 98.6, 451.0] {
+        let round_trip = Temperature::from_celsius(Temperature::from_fahrenheit(fahrenheit).as_celsius()).as_fahrenheit();
+        assert!((round_trip - fahrenheit).abs() < 1e-3, "{fahrenheit} became {round_trip}");
+    }
+
+    for celsius in [-273.15_f32, -17.5, 0.0, 21.5, 37.0] {
+        let round_trip = Temperature::from_fahrenheit(Temperature::from_celsius(celsius).as_fahrenheit()).as_celsius();
+        assert!((round_trip - celsius).abs() < 1e-3, "{celsius} became {round_trip}");
+    }
+}
+
+#[test]
+fn temperature_rounding() {
+    // 70F is not representable exactly in Celsius, rounding to one decimal gives what a thermostat would display
+    let celsius = Temperature::from_fahrenheit(70.0).as_celsius();
+    assert_eq!(21.1, (celsius * 10.0).round() / 10.0);
+
+    let fahrenheit = Temperature::from_celsius(37.0).as_fahrenheit();
+    assert_eq!(98.6, (fahrenheit * 10.0).round() / 10.0);
+}
+
+#[test]
+fn temperature_serializes_as_celsius() {
+    assert_eq!(json!(20.0), serde_json::to_value(Temperature::from_fahrenheit(68.0)).unwrap());
+    assert_eq!(Temperature::from_celsius(21.5), serde_json::from_value(json!(21.5)).unwrap());
+}
@@ -0,0 +1,94 @@
+mod common;
+
+use homelander::fulfillment::request::execute::CommandType;
+use homelander::fulfillment::response::execute::CommandStatus;
+use homelander::traits::temperature_control::TemperatureControl;
+use homelander::traits::{CombinedDeviceError, TemperatureRange, TemperatureUnit};
+use homelander::{Device, DeviceType, Homelander};
+use serde_json::json;
+
+#[derive(Debug)]
+struct Oven {
+    setpoint: f32,
+}
+
+google_home_device!(Oven, "Oven");
+
+impl TemperatureControl for Oven {
+    fn get_temperature_range(&self) -> Result<TemperatureRange, CombinedDeviceError> {
+        Ok(TemperatureRange {
+            min_threshold_celsius: 65.0,
+            max_threshold_celsius: 288.0,
+        })
+    }
+
+    fn get_temperature_step_celsius(&self) -> Result<Option<f32>, CombinedDeviceError> {
+        Ok(Some(5.0))
+    }
+
+    fn get_temperature_unit_for_ux(&self) -> Result<TemperatureUnit, CombinedDeviceError> {
+        Ok(TemperatureUnit::Celsius)
+    }
+
+    fn is_command_only_temperature_control(&self) -> Result<Option<bool>, CombinedDeviceError> {
+        Ok(Some(false))
+    }
+
+    fn is_query_only_temperature_control(&self) -> Result<Option<bool>, CombinedDeviceError> {
+        Ok(Some(false))
+    }
+
+    fn get_temperature_setpoint_celsius(&self) -> Result<f32, CombinedDeviceError> {
+        Ok(self.setpoint)
+    }
+
+    fn get_temperatuer_ambient_celsius(&self) -> Result<f32, CombinedDeviceError> {
+        Ok(20.0)
+    }
+
+    fn set_temperature(&mut self, temperature: f32) -> Result<(), CombinedDeviceError> {
+        self.setpoint = temperature;
+        Ok(())
+    }
+}
+
+fn setup_homelander() -> Homelander {
+    let mut device = Device::new(Oven { setpoint: 65.0 }, DeviceType::Oven, "00".to_string());
+    device.set_temperature_control();
+
+    let mut homelander = Homelander::new("01".to_string());
+    homelander.add_device(device);
+    homelander
+}
+
+#[test]
+fn sync_temperature_control() {
+    let mut homelander = setup_homelander();
+    let payload = common::sync(&mut homelander);
+    let attributes = serde_json::to_value(&payload.devices[0].attributes).unwrap();
+
+    assert_eq!(
+        attributes["temperatureRange"],
+        json!({
+            "minThresholdCelsius": 65.0,
+            "maxThresholdCelsius": 288.0,
+        })
+    );
+    assert_eq!(attributes["temperatureStepCelsius"], json!(5.0));
+    assert_eq!(attributes["temperatureUnitForUX"], json!("C"));
+    assert_eq!(attributes["commandOnlyTemperatureControl"], json!(false));
+    assert_eq!(attributes["queryOnlyTemperatureControl"], json!(false));
+}
+
+#[test]
+fn set_and_query_temperature() {
+    let mut homelander = setup_homelander();
+
+    let payload = common::execute(&mut homelander, &["00"], CommandType::SetTemperature { temperature: 180.0 });
+    assert_eq!(payload.commands[0].status, CommandStatus::Success);
+
+    let payload = common::query(&mut homelander, &["00"]);
+    let state = serde_json::to_value(&payload.devices["00"]).unwrap();
+    assert_eq!(state["temperatureSetpointCelsius"], json!(180.0));
+    assert_eq!(state["temperatureAmbientCelsius"], json!(20.0));
+}
@@ -0,0 +1,161 @@
+mod common;
+
+use homelander::fulfillment::request::execute::CommandType;
+use homelander::traits::temperature_setting::{QueryThermostatMode, QueryThermostatModeFixed, QueryThermostatModeRange, TemperatureSetting, ThermostatMode};
+use homelander::traits::{CombinedDeviceError, TemperatureUnit};
+use homelander::{Device, DeviceType, Homelander};
+use serde_json::json;
+
+#[derive(Debug)]
+struct Thermostat {
+    modes: Vec<ThermostatMode>,
+    range: Option<(f32, f32)>,
+}
+
+google_home_device!(Thermostat, "Thermostat");
+
+impl TemperatureSetting for Thermostat {
+    fn get_available_thermostat_modes(&self) -> Result<Vec<ThermostatMode>, CombinedDeviceError> {
+        Ok(self.modes.clone())
+    }
+
+    fn get_thermostat_temperature_unit(&self) -> Result<TemperatureUnit, CombinedDeviceError> {
+        Ok(TemperatureUnit::Celsius)
+    }
+
+    fn get_buffer_range_celsius(&self) -> Result<Option<f32>, CombinedDeviceError> {
+        Ok(Some(3.0))
+    }
+
+    fn get_active_thermostat_mode(&self) -> Result<ThermostatMode, CombinedDeviceError> {
+        Ok(if self.range.is_some() {
+            ThermostatMode::Heatcool
+        } else {
+            ThermostatMode::Heat
+        })
+    }
+
+    fn get_thermostat_mode(&self) -> Result<QueryThermostatMode, CombinedDeviceError> {
+        Ok(match self.range {
+            Some((high, low)) => QueryThermostatMode::Range(QueryThermostatModeRange {
+                thermostat_mode: ThermostatMode::Heatcool,
+                thermostat_temperature_ambient: 21.0,
+                thermostat_temperature_setpoint_high: high,
+                thermostat_temperature_setpoint_low: low,
+            }),
+            None => QueryThermostatMode::Fixed(QueryThermostatModeFixed {
+                thermostat_mode: ThermostatMode::Heat,
+                thermostat_temperature_ambient: 19.5,
+                thermostat_temperature_setpoint: 20.0,
+            }),
+        })
+    }
+
+    fn set_temperature_setpoint(&mut self, _setpoint: f32) -> Result<(), CombinedDeviceError> {
+        Ok(())
+    }
+
+    fn set_temperature_set_range(&mut self, setpoint_high: f32, setpoint_low: f32) -> Result<(), CombinedDeviceError> {
+        self.range = Some((setpoint_high, setpoint_low));
+        Ok(())
+    }
+
+    fn set_thermostat_mode(&mut self, _mode: ThermostatMode) -> Result<(), CombinedDeviceError> {
+        Ok(())
+    }
+
+    fn set_temperature_relative_degree(&mut self, _relative_degrees: f32) -> Result<(), CombinedDeviceError> {
+        Ok(())
+    }
+
+    fn set_temperature_relative_weight(&mut self, _weight: f32) -> Result<(), CombinedDeviceError> {
+        Ok(())
+    }
+}
+
+fn setup_homelander(modes: Vec<ThermostatMode>, range: Option<(f32, f32)>) -> Homelander {
+    let mut device = Device::new(Thermostat { modes, range }, DeviceType::Thermostat, "00".to_string());
+    device.set_temperature_setting();
+
+    let mut homelander = Homelander::new("01".to_string());
+    homelander.add_device(device);
+    homelander
+}
+
+/// Execute ThermostatTemperatureSetRange and return the command's status and error code, and the range the thermostat was set to
+fn set_range(homelander: &mut Homelander, high: f32, low: f32) -> (serde_json::Value, serde_json::Value, Option<(f32, f32)>) {
+    let payload = common::execute(
+        homelander,
+        &["00"],
+        CommandType::ThermostatTemperatureSetRange {
+            thermostat_temperature_setpoint_high: high,
+            thermostat_temperature_setpoint_low: low,
+        },
+    );
+    let command = serde_json::to_value(&payload.commands[0]).unwrap();
+    let range = homelander.with_device_mut("00", |thermostat: &mut Thermostat| thermostat.range).unwrap();
+
+    (command["status"].clone(), command["errorCode"].clone(), range)
+}
+
+#[test]
+fn set_range_with_heatcool() {
+    let mut homelander = setup_homelander(vec![ThermostatMode::Off, ThermostatMode::Heatcool], None);
+    assert_eq!(set_range(&mut homelander, 24.0, 18.0), (json!("SUCCESS"), serde_json::Value::Null, Some((24.0, 18.0))));
+}
+
+#[test]
+fn set_range_without_heatcool() {
+    let mut homelander = setup_homelander(vec![ThermostatMode::Off, ThermostatMode::Heat], None);
+    assert_eq!(set_range(&mut homelander, 24.0, 18.0), (json!("ERROR"), json!("notSupported"), None));
+}
+
+#[test]
+fn set_inverted_range() {
+    let mut homelander = setup_homelander(vec![ThermostatMode::Heatcool], None);
+    assert_eq!(set_range(&mut homelander, 18.0, 24.0), (json!("ERROR"), json!("valueOutOfRange"), None));
+}
+
+#[test]
+fn set_range_within_buffer() {
+    let mut homelander = setup_homelander(vec![ThermostatMode::Heatcool], None);
+    assert_eq!(set_range(&mut homelander, 22.0, 20.0), (json!("ERROR"), json!("rangeTooClose"), None));
+    assert_eq!(set_range(&mut homelander, 23.0, 20.0), (json!("SUCCESS"), serde_json::Value::Null, Some((23.0, 20.0))));
+}
+
+#[test]
+fn query_fixed_setpoint() {
+    let mut homelander = setup_homelander(vec![ThermostatMode::Off, ThermostatMode::Heat], None);
+    let payload = common::query(&mut homelander, &["00"]);
+
+    assert_eq!(
+        serde_json::to_value(&payload.devices["00"]).unwrap(),
+        json!({
+            "online": true,
+            "status": "SUCCESS",
+            "activeThermostatMode": "heat",
+            "thermostatMode": "heat",
+            "thermostatTemperatureAmbient": 19.5,
+            "thermostatTemperatureSetpoint": 20.0,
+        })
+    );
+}
+
+#[test]
+fn query_setpoint_range() {
+    let mut homelander = setup_homelander(vec![ThermostatMode::Off, ThermostatMode::Heatcool], Some((24.0, 18.0)));
+    let payload = common::query(&mut homelander, &["00"]);
+
+    assert_eq!(
+        serde_json::to_value(&payload.devices["00"]).unwrap(),
+        json!({
+            "online": true,
+            "status": "SUCCESS",
+            "activeThermostatMode": "heatcool",
+            "thermostatMode": "heatcool",
+            "thermostatTemperatureAmbient": 21.0,
+            "thermostatTemperatureSetpointHigh": 24.0,
+            "thermostatTemperatureSetpointLow": 18.0,
+        })
+    );
+}
@@ -0,0 +1,81 @@
+#![cfg(feature = "testkit")]
+
+mod common;
+
+use homelander::fulfillment::request::execute::CommandType;
+use homelander::testkit::TraitHarness;
+use homelander::traits::brightness::Brightness;
+use homelander::traits::on_off::OnOff;
+use homelander::traits::CombinedDeviceError;
+use homelander::{Device, DeviceType, Trait};
+use serde_json::json;
+
+#[derive(Debug)]
+struct Lamp {
+    on: bool,
+    brightness: i32,
+}
+
+google_home_device!(Lamp, "Lamp");
+
+impl OnOff for Lamp {
+    fn is_on(&self) -> Result<bool, CombinedDeviceError> {
+        Ok(self.on)
+    }
+
+    fn set_on(&mut self, on: bool) -> Result<(), CombinedDeviceError> {
+        self.on = on;
+        Ok(())
+    }
+}
+
+impl Brightness for Lamp {
+    fn is_command_only_brightness(&self) -> Result<bool, CombinedDeviceError> {
+        Ok(false)
+    }
+
+    fn get_brightness(&self) -> Result<i32, CombinedDeviceError> {
+        Ok(self.brightness)
+    }
+
+    fn set_brightness_absolute(&mut self, brightness: i32) -> Result<(), CombinedDeviceError> {
+        self.brightness = brightness;
+        Ok(())
+    }
+
+    fn set_brightness_relative_percent(&mut self, brightness: i32) -> Result<(), CombinedDeviceError> {
+        self.brightness += brightness;
+        Ok(())
+    }
+
+    fn set_brightness_relative_weight(&mut self, weight: i32) -> Result<(), CombinedDeviceError> {
+        self.brightness += weight * 10;
+        Ok(())
+    }
+}
+
+fn lamp() -> Device<Lamp> {
+    let mut device = Device::new(Lamp { on: false, brightness: 50 }, DeviceType::Light, "00".to_string());
+    device.set_on_off();
+    device.set_brightness();
+    device
+}
+
+#[test]
+fn lamp_round_trip() {
+    TraitHarness::new(lamp())
+        .assert_sync_traits(&[Trait::OnOff, Trait::Brightness])
+        .assert_query_state(json!({ "online": true, "on": false, "brightness": 50 }))
+        .assert_execute(CommandType::OnOff { on: true })
+        .assert_execute(CommandType::BrightnessAbsolute { brightness: 80 })
+        .assert_query_state(json!({ "on": true, "brightness": 80 }));
+}
+
+#[test]
+#[should_panic(expected = "Missing: [Brightness]")]
+fn unregistered_trait() {
+    let mut device = lamp();
+    device.unset_brightness();
+
+    TraitHarness::new(device).assert_sync_traits(&[Trait::OnOff, Trait::Brightness]);
+}
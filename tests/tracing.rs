@@ -0,0 +1,105 @@
+mod common;
+
+use homelander::fulfillment::request::execute::CommandType;
+use homelander::traits::on_off::OnOff;
+use homelander::traits::CombinedDeviceError;
+use homelander::{Device, DeviceType, Homelander};
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::sync::{Arc, Mutex};
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Metadata, Subscriber};
+
+#[derive(Debug)]
+struct Lamp {
+    on: bool,
+}
+
+google_home_device!(Lamp, "Lamp");
+
+impl OnOff for Lamp {
+    fn is_on(&self) -> Result<bool, CombinedDeviceError> {
+        Ok(self.on)
+    }
+
+    fn set_on(&mut self, on: bool) -> Result<(), CombinedDeviceError> {
+        self.on = on;
+        Ok(())
+    }
+}
+
+/// The name and fields of a span
+type RecordedSpan = (&'static str, HashMap<&'static str, String>);
+
+/// Records the name and fields of every span created
+#[derive(Clone, Default)]
+struct SpanRecorder {
+    spans: Arc<Mutex<Vec<RecordedSpan>>>,
+}
+
+struct Fields(HashMap<&'static str, String>);
+
+impl Visit for Fields {
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        self.0.insert(field.name(), format!("{value:?}"));
+    }
+}
+
+impl Subscriber for SpanRecorder {
+    fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, span: &Attributes<'_>) -> Id {
+        let mut fields = Fields(HashMap::new());
+        span.record(&mut fields);
+
+        let mut spans = self.spans.lock().unwrap();
+        spans.push((span.metadata().name(), fields.0));
+        Id::from_u64(spans.len() as u64)
+    }
+
+    fn record(&self, _span: &Id, _values: &Record<'_>) {}
+
+    fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+    fn event(&self, _event: &Event<'_>) {}
+
+    fn enter(&self, _span: &Id) {}
+
+    fn exit(&self, _span: &Id) {}
+}
+
+impl SpanRecorder {
+    /// The fields of the span named `name` which was created for a single device
+    fn device_span(&self, name: &str) -> HashMap<&'static str, String> {
+        let spans = self.spans.lock().unwrap();
+        spans
+            .iter()
+            .find(|(span, fields)| *span == name && fields.contains_key("device_id"))
+            .map(|(_, fields)| fields.clone())
+            .unwrap()
+    }
+}
+
+#[test]
+fn device_spans_include_device_id() {
+    let mut device = Device::new(Lamp { on: false }, DeviceType::Light, "00".to_string());
+    device.set_on_off();
+
+    let mut homelander = Homelander::new("01".to_string());
+    homelander.add_device(device);
+
+    let recorder = SpanRecorder::default();
+    tracing::subscriber::with_default(recorder.clone(), || {
+        common::query(&mut homelander, &["00"]);
+        common::execute(&mut homelander, &["00"], CommandType::OnOff { on: true });
+    });
+
+    assert_eq!(recorder.device_span("query").get("device_id").map(String::as_str), Some("00"));
+
+    let execute = recorder.device_span("execute");
+    assert_eq!(execute.get("device_id").map(String::as_str), Some("00"));
+    assert_eq!(execute.get("command").map(String::as_str), Some("OnOff { on: true }"));
+}
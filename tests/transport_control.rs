@@ -0,0 +1,112 @@
+mod common;
+
+use homelander::fulfillment::request::execute::CommandType;
+use homelander::fulfillment::response::execute::CommandStatus;
+use homelander::traits::transport_control::{SupportedCommand, TransportControl};
+use homelander::traits::CombinedDeviceError;
+use homelander::{Device, DeviceType, Homelander};
+
+/// A media player which records the transport control calls it received
+#[derive(Debug, Default)]
+struct MediaPlayer {
+    calls: Vec<&'static str>,
+}
+
+google_home_device!(MediaPlayer, "MediaPlayer");
+
+impl TransportControl for MediaPlayer {
+    fn get_supported_control_commands(&self) -> Result<Vec<SupportedCommand>, CombinedDeviceError> {
+        Ok(vec![SupportedCommand::Stop, SupportedCommand::Pause, SupportedCommand::Resume])
+    }
+
+    fn media_stop(&mut self) -> Result<(), CombinedDeviceError> {
+        self.calls.push("stop");
+        Ok(())
+    }
+
+    fn media_next(&mut self) -> Result<(), CombinedDeviceError> {
+        self.calls.push("next");
+        Ok(())
+    }
+
+    fn media_previous(&mut self) -> Result<(), CombinedDeviceError> {
+        self.calls.push("previous");
+        Ok(())
+    }
+
+    fn media_pause(&mut self) -> Result<(), CombinedDeviceError> {
+        self.calls.push("pause");
+        Ok(())
+    }
+
+    fn media_resume(&mut self) -> Result<(), CombinedDeviceError> {
+        self.calls.push("resume");
+        Ok(())
+    }
+
+    fn media_seek_relative(&mut self, _relative_position_ms: i32) -> Result<(), CombinedDeviceError> {
+        self.calls.push("seek relative");
+        Ok(())
+    }
+
+    fn media_seek_to_position(&mut self, _abs_position_ms: i32) -> Result<(), CombinedDeviceError> {
+        self.calls.push("seek to position");
+        Ok(())
+    }
+
+    fn media_repeat_mode(&mut self, _is_on: bool, _single_mode: bool) -> Result<(), CombinedDeviceError> {
+        self.calls.push("repeat mode");
+        Ok(())
+    }
+
+    fn media_shuffle(&mut self) -> Result<(), CombinedDeviceError> {
+        self.calls.push("shuffle");
+        Ok(())
+    }
+
+    fn media_closed_captioning_on(&mut self, _cc_lang: String, _user_query_lang: String) -> Result<(), CombinedDeviceError> {
+        self.calls.push("closed captioning on");
+        Ok(())
+    }
+
+    fn media_closed_captioning_off(&mut self) -> Result<(), CombinedDeviceError> {
+        self.calls.push("closed captioning off");
+        Ok(())
+    }
+}
+
+/// Execute `commands` in order and return the transport control calls the media player received
+fn execute(commands: Vec<CommandType>) -> Vec<&'static str> {
+    let mut device = Device::new(MediaPlayer::default(), DeviceType::Remotecontrol, "00".to_string());
+    device.set_transport_control();
+
+    let mut homelander = Homelander::new("01".to_string());
+    homelander.add_device(device);
+
+    for command in commands {
+        let payload = common::execute(&mut homelander, &["00"], command);
+        assert_eq!(payload.commands[0].status, CommandStatus::Success);
+    }
+
+    homelander.with_device_mut("00", |player: &mut MediaPlayer| player.calls.clone()).unwrap()
+}
+
+#[test]
+fn media_stop() {
+    assert_eq!(execute(vec![CommandType::MediaStop]), vec!["stop"]);
+}
+
+#[test]
+fn media_pause() {
+    assert_eq!(execute(vec![CommandType::MediaPause]), vec!["pause"]);
+}
+
+#[test]
+fn media_resume() {
+    assert_eq!(execute(vec![CommandType::MediaResume]), vec!["resume"]);
+}
+
+#[test]
+fn media_pause_resume_stop() {
+    assert_eq!(execute(vec![CommandType::MediaPause, CommandType::MediaResume, CommandType::MediaStop]), vec!["pause", "resume", "stop"]);
+}
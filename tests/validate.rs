@@ -0,0 +1,140 @@
+mod common;
+
+use homelander::traits::color_setting::{Color, ColorCommand, ColorModel, ColorModelSupport, ColorSetting};
+use homelander::traits::fan_speed::{AvailableFanSpeeds, FanSpeed, FanSpeedError};
+use homelander::traits::on_off::OnOff;
+use homelander::traits::CombinedDeviceError;
+use homelander::{Device, DeviceType, Trait, ValidationError};
+
+/// A fan which may or may not support setting its speed as a percentage
+#[derive(Debug)]
+struct Fan {
+    supports_percent: Option<bool>,
+}
+
+google_home_device!(Fan, "Fan");
+
+impl FanSpeed for Fan {
+    fn get_available_fan_speeds(&self) -> Result<Option<AvailableFanSpeeds>, FanSpeedError> {
+        Ok(None)
+    }
+
+    fn is_support_fan_speed_percent(&self) -> Result<Option<bool>, FanSpeedError> {
+        Ok(self.supports_percent)
+    }
+
+    fn get_current_fan_speed_setting(&self) -> Result<Option<String>, FanSpeedError> {
+        Ok(None)
+    }
+
+    fn get_current_fan_speed_percent(&self) -> Result<Option<f32>, FanSpeedError> {
+        Ok(Some(50.0))
+    }
+
+    fn set_fan_speed_setting(&self, _name: String) -> Result<(), FanSpeedError> {
+        Ok(())
+    }
+
+    fn set_fan_speed_percent(&self, _percent: f32) -> Result<(), FanSpeedError> {
+        Ok(())
+    }
+
+    fn set_fan_speed_relative_weight(&self, _weight: i32) -> Result<(), FanSpeedError> {
+        Ok(())
+    }
+
+    fn set_fan_speed_relative_percent(&self, _percent: f32) -> Result<(), FanSpeedError> {
+        Ok(())
+    }
+
+    fn set_fan_reverse(&self) -> Result<(), FanSpeedError> {
+        Ok(())
+    }
+}
+
+fn fan(supports_percent: Option<bool>) -> Device<Fan> {
+    let mut device = Device::new(Fan { supports_percent }, DeviceType::Fan, "00".to_string());
+    device.set_fan_speed();
+    device
+}
+
+#[test]
+fn validate_fan_without_speeds() {
+    for supports_percent in [None, Some(false)] {
+        let errors = fan(supports_percent).validate();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].device_trait, Trait::FanSpeed);
+    }
+}
+
+#[test]
+fn validate_fan_with_percent() {
+    assert_eq!(fan(Some(true)).validate(), Vec::<ValidationError>::new());
+}
+
+#[test]
+fn validate_unregistered_trait() {
+    // Contracts are only checked for registered traits
+    let device = Device::new(Fan { supports_percent: None }, DeviceType::Fan, "00".to_string());
+    assert!(device.validate().is_empty());
+}
+
+/// A smart plug which claims it can change color
+#[derive(Debug)]
+struct ColorPlug;
+
+google_home_device!(ColorPlug, "ColorPlug");
+
+impl OnOff for ColorPlug {
+    fn is_on(&self) -> Result<bool, CombinedDeviceError> {
+        Ok(true)
+    }
+
+    fn set_on(&mut self, _on: bool) -> Result<(), CombinedDeviceError> {
+        Ok(())
+    }
+}
+
+impl ColorSetting for ColorPlug {
+    fn is_command_only_color_setting(&self) -> Result<bool, CombinedDeviceError> {
+        Ok(false)
+    }
+
+    fn get_color_model_support(&self) -> Result<ColorModelSupport, CombinedDeviceError> {
+        Ok(ColorModelSupport::new(Some(ColorModel::Rgb), None))
+    }
+
+    fn get_color(&self) -> Result<Color, CombinedDeviceError> {
+        Ok(Color {
+            temperature_k: None,
+            spectrum_rgb: Some(0xFF0000),
+            spectrum_hsv: None,
+        })
+    }
+
+    fn set_color(&mut self, _command: ColorCommand) -> Result<(), CombinedDeviceError> {
+        Ok(())
+    }
+}
+
+fn color_plug(device_type: DeviceType) -> Device<ColorPlug> {
+    let mut device = Device::new(ColorPlug, device_type, "00".to_string());
+    device.set_on_off();
+    device.set_color_setting();
+    device
+}
+
+#[test]
+fn validate_incompatible_trait() {
+    let errors = color_plug(DeviceType::Outlet).validate_trait_compatibility();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].device_trait, Trait::ColorSetting);
+
+    // Not fatal, the device can still be used
+    assert!(color_plug(DeviceType::Outlet).validate().is_empty());
+}
+
+#[test]
+fn validate_compatible_traits() {
+    assert!(color_plug(DeviceType::Light).validate_trait_compatibility().is_empty());
+}
@@ -0,0 +1,111 @@
+mod common;
+
+use homelander::fulfillment::request::execute::CommandType;
+use homelander::fulfillment::response::execute::CommandStatus;
+use homelander::traits::volume::Volume;
+use homelander::traits::CombinedDeviceError;
+use homelander::{Device, DeviceType, Homelander};
+use serde_json::json;
+
+#[derive(Debug)]
+struct Speaker {
+    volume: i32,
+    muted: bool,
+}
+
+google_home_device!(Speaker, "Speaker");
+
+impl Volume for Speaker {
+    fn get_volume_max_level(&self) -> Result<i32, CombinedDeviceError> {
+        Ok(20)
+    }
+
+    fn can_mute_and_unmute(&self) -> Result<bool, CombinedDeviceError> {
+        Ok(true)
+    }
+
+    fn get_current_volume(&self) -> Result<Option<i32>, CombinedDeviceError> {
+        Ok(Some(self.volume))
+    }
+
+    fn is_muted(&self) -> Result<Option<bool>, CombinedDeviceError> {
+        Ok(Some(self.muted))
+    }
+
+    fn mute(&mut self, mute: bool) -> Result<(), CombinedDeviceError> {
+        self.muted = mute;
+        Ok(())
+    }
+
+    fn set_volume(&mut self, volume_level: i32) -> Result<(), CombinedDeviceError> {
+        self.volume = volume_level;
+        Ok(())
+    }
+
+    fn set_volume_relative(&mut self, relative_steps: i32) -> Result<(), CombinedDeviceError> {
+        self.volume += relative_steps;
+        Ok(())
+    }
+}
+
+fn setup_homelander() -> Homelander {
+    let mut device = Device::new(Speaker { volume: 10, muted: false }, DeviceType::Speaker, "00".to_string());
+    device.set_volume();
+
+    let mut homelander = Homelander::new("01".to_string());
+    homelander.add_device(device);
+    homelander
+}
+
+fn assert_volume(homelander: &mut Homelander, volume: i32) {
+    let payload = common::query(homelander, &["00"]);
+    let state = serde_json::to_value(&payload.devices["00"]).unwrap();
+    assert_eq!(state["currentVolume"], json!(volume));
+}
+
+#[test]
+fn set_volume_in_range() {
+    let mut homelander = setup_homelander();
+
+    for volume_level in [0, 20] {
+        let payload = common::execute(&mut homelander, &["00"], CommandType::SetVolume { volume_level });
+        assert_eq!(payload.commands[0].status, CommandStatus::Success);
+        assert_volume(&mut homelander, volume_level);
+    }
+}
+
+#[test]
+fn set_volume_out_of_range() {
+    let mut homelander = setup_homelander();
+
+    for volume_level in [-1, 21] {
+        let payload = common::execute(&mut homelander, &["00"], CommandType::SetVolume { volume_level });
+        let command = serde_json::to_value(&payload.commands[0]).unwrap();
+        assert_eq!(payload.commands[0].status, CommandStatus::Error);
+        assert_eq!(command["errorCode"], json!("valueOutOfRange"));
+        assert_volume(&mut homelander, 10);
+    }
+}
+
+#[test]
+fn volume_relative_clamps_at_max() {
+    let mut homelander = setup_homelander();
+
+    let payload = common::execute(&mut homelander, &["00"], CommandType::VolumeRelative { relative_steps: 15 });
+    assert_eq!(payload.commands[0].status, CommandStatus::Success);
+    assert_eq!(serde_json::to_value(&payload.commands[0].states).unwrap(), json!({ "online": true, "currentVolume": 20, "isMuted": false }));
+
+    let payload = common::execute(&mut homelander, &["00"], CommandType::VolumeRelative { relative_steps: -30 });
+    assert_eq!(payload.commands[0].status, CommandStatus::Success);
+    assert_volume(&mut homelander, 0);
+}
+
+#[test]
+fn volume_up_unmutes() {
+    let mut homelander = setup_homelander();
+    common::execute(&mut homelander, &["00"], CommandType::Mute { mute: true });
+
+    let payload = common::execute(&mut homelander, &["00"], CommandType::VolumeRelative { relative_steps: 2 });
+    assert_eq!(payload.commands[0].status, CommandStatus::Success);
+    assert_eq!(serde_json::to_value(&payload.commands[0].states).unwrap(), json!({ "online": true, "currentVolume": 12, "isMuted": false }));
+}